@@ -1,11 +1,20 @@
 use anchor_lang::prelude::*;
 
+use crate::DustPayoutPolicy;
+
 // Base seed string used for generating program-derived addresses (PDAs)
 #[constant]
 pub const SEED: &str = "anchor";
 
 // BETTING CONSTRAINTS
 // ===================
+//
+// These are the defaults for the GameConfig created at initialize; the house
+// can adjust them later through update_config.
+//
+// These limits are enforced at placement time only. A bet that was valid
+// when placed always resolves or refunds normally, even if the limits are
+// tightened afterwards.
 
 /// Minimum bet amount: 0.01 SOL (10,000,000 lamports)
 /// Prevents spam bets while keeping the game accessible
@@ -15,28 +24,296 @@ pub const MIN_BET_LAMPORTS: u64 = 10_000_000;
 /// Limits maximum exposure and protects the house vault
 pub const MAX_BET_LAMPORTS: u64 = 10_000_000_000;
 
+/// Default dice range: rolls are between 1 and 100
+/// Adjustable through update_config, e.g. 6 for classic dice or 1000 for finer odds
+pub const ROLL_RANGE: u16 = 100;
+
+/// Largest dice range update_config accepts: 1-10,000
+pub const MAX_ROLL_RANGE: u16 = 10_000;
+
 /// Minimum roll prediction: 2
-/// Players win if random roll (1-100) is LESS than their prediction
+/// Players win if random roll (1-ROLL_RANGE) is LESS than their prediction
 /// Minimum of 2 ensures there's always a chance to lose (if roll = 1)
-pub const MIN_ROLL: u8 = 2;
+pub const MIN_ROLL: u16 = 2;
 
 /// Maximum roll prediction: 96
 /// Maximum of 96 ensures there's always a chance to win (if roll = 97-100)
 /// This creates a balanced risk/reward system
-pub const MAX_ROLL: u8 = 96;
+pub const MAX_ROLL: u16 = 96;
+
+const _: () = assert!(
+    MIN_ROLL >= 2 && MIN_ROLL <= MAX_ROLL && MAX_ROLL <= ROLL_RANGE && ROLL_RANGE <= MAX_ROLL_RANGE,
+    "default roll limits must satisfy 2 <= MIN_ROLL <= MAX_ROLL <= ROLL_RANGE <= MAX_ROLL_RANGE"
+);
+
+/// Minimum number of distinct byte values in the low 8 bytes of a bet seed
+/// Soft anti-grinding measure: the seed is part of the signed bet data, so
+/// trivially patterned seeds (0x00.., 0xFF.., 0x4242..) are rejected when set to 2 or more
+/// 1 (the default) accepts every seed
+pub const MIN_SEED_DISTINCT_BYTES: u8 = 1;
+
+/// Maximum number of open bets across all players of a house
+/// Counted by the BetTracker PDA; further placements fail until bets settle
+pub const MAX_OPEN_BETS: u32 = 1_000;
+
+/// Maximum number of open bets a single player may hold at once
+/// Counted on the player's PlayerStats PDA; further placements fail until bets settle
+pub const MAX_OPEN_BETS_PER_PLAYER: u16 = 20;
+
+/// Maximum number of additional authorities a house can add to its game
+/// Must match the `max_len` of `GameConfig::authorities`
+pub const MAX_AUTHORITIES: usize = 4;
+
+/// Maximum number of house edge tiers in a game's schedule
+/// Must match the `max_len` of `GameConfig::edge_tiers`
+pub const MAX_EDGE_TIERS: usize = 4;
 
 // GAME ECONOMICS
 // ==============
 
-/// House edge in basis points (150 = 1.5%)
+/// Default house edge in basis points (150 = 1.5%)
+/// Stored in GameConfig at initialize and adjustable through update_config
 /// This is the house's profit margin built into payouts
 /// Example: On a winning bet, payout = (bet_amount * 98.5%) / (win_probability)
 pub const HOUSE_EDGE: u16 = 150;
 
+/// Highest house edge update_config accepts: 1000 basis points (10%)
+/// Keeps a compromised house key from setting a predatory edge
+pub const MAX_HOUSE_EDGE_BP: u16 = 1000;
+
+// An edge of 10000 bp (100%) or more would make every payout zero or underflow
+const _: () = assert!(HOUSE_EDGE < 10000, "HOUSE_EDGE must be below 10000 bp");
+const _: () = assert!(
+    HOUSE_EDGE <= MAX_HOUSE_EDGE_BP,
+    "HOUSE_EDGE must not exceed MAX_HOUSE_EDGE_BP"
+);
+
+/// Worst-case single payout: MAX_BET_LAMPORTS won on MIN_ROLL (2)
+/// At roll 2 the divisor (roll - 1) is 1, giving the largest multiplier:
+/// payout = amount * (10000 - HOUSE_EDGE) * ROLL_RANGE / 10000 (985 SOL at the defaults)
+/// Scales with the range, so a game configured with a wider range can pay more
+pub const MAX_PAYOUT_LAMPORTS: u64 =
+    (MAX_BET_LAMPORTS as u128 * (10000 - HOUSE_EDGE as u128) * ROLL_RANGE as u128
+        / (MIN_ROLL as u128 - 1)
+        / 10000) as u64;
+
+/// Largest winning payout a single bet may carry: 1000 basis points (10%) of the vault
+/// Checked at placement against the payout for the bet's roll, so long-shot rolls
+/// (e.g. 2, paying ~98x) are capped at a much smaller stake than safe ones
+pub const MAX_PAYOUT_VAULT_BPS: u16 = 1000;
+
+/// Initial vault funding required at initialize, as a multiple of the game's worst-case payout
+/// The worst case follows the max bet the game starts with, so a smaller game needs less
+/// A vault that cannot pay even one worst-case win would leave the game broken from the start
+pub const MIN_VAULT_FUNDING_PAYOUTS: u64 = 1;
+
+/// Share of each stake donated to charity in basis points (0 = disabled)
+/// The donation is routed to CHARITY_ADDRESS at placement and is never
+/// part of the wager, so it is neither paid out nor counted as house edge
+pub const CHARITY_BPS: u16 = 0;
+
+/// Fixed charity address receiving donations
+/// Must be set whenever CHARITY_BPS is non-zero
+pub const CHARITY_ADDRESS: Option<Pubkey> = None;
+
+/// Loss insurance premium: 100 basis points (1%) of the wager
+/// Paid on top of the wager by players who opt in, pooled in the insurance PDA
+/// The pool is seeded with its rent-exempt minimum at initialize, and cover never draws below it
+pub const INSURANCE_PREMIUM_BPS: u16 = 100;
+
+/// Loss insurance cover: 5000 basis points (50%) of the wager
+/// Returned to the player on an insured loss, funded by the insurance pool
+pub const INSURANCE_COVER_BPS: u16 = 5000;
+
+/// Share of each native SOL stake paid into the jackpot pool, in basis points
+/// 0 disables the jackpot. The pool PDA must hold its rent-exempt minimum
+/// before the first contribution, so the house seeds it when enabling this.
+pub const JACKPOT_BPS: u16 = 0;
+
+/// Rolled value that wins the whole jackpot pool on top of the bet's own outcome
+/// Every roll is equally likely, so the jackpot hits on 1 in roll_range resolutions
+pub const JACKPOT_ROLL: u16 = 1;
+
+/// Referral reward credited to a bet's referrer at resolution, in basis points of the wager
+/// Paid by the house and capped at the game's house edge, so it never comes out of player funds
+pub const REFERRAL_BPS: u16 = 50;
+
+/// Share of the house edge rebated to the player, in basis points of the edge (0 = disabled)
+/// Every resolved native SOL bet credits amount * house_edge_bp * REBATE_BPS / 10000^2
+/// to the player's rebate balance, held in the vault until claimed through claim_rebate
+pub const REBATE_BPS: u16 = 0;
+
+#[allow(clippy::absurd_extreme_comparisons)] // REBATE_BPS may be 0
+const _: () = assert!(REBATE_BPS <= 10000, "REBATE_BPS must not exceed 10000 bp");
+
+/// Minimum winning payout: 5,000 lamports (one signature fee)
+/// Winning payouts below this are economically meaningless to the player
+/// and are adjusted according to DUST_PAYOUT_POLICY
+pub const MIN_PAYOUT_LAMPORTS: u64 = 5_000;
+
+/// How winning payouts below MIN_PAYOUT_LAMPORTS are settled
+/// RoundUp pays the threshold from the vault, RefundStake returns the bet amount
+pub const DUST_PAYOUT_POLICY: DustPayoutPolicy = DustPayoutPolicy::RoundUp;
+
+/// Scaled payout accounting for native SOL bets: disabled
+/// When true, winning payouts are computed exactly in PAYOUT_SCALE units instead of
+/// rounding per the config's payout_rounding. Whole lamports are paid and the
+/// sub-lamport remainder is banked on the player's PlayerStats, to be added to
+/// their next win, so truncation never accumulates against the player
+pub const SCALED_PAYOUT_ACCOUNTING: bool = false;
+
+/// Internal payout units per lamport under SCALED_PAYOUT_ACCOUNTING: 1,000,000 (micro-lamports)
+pub const PAYOUT_SCALE: u64 = 1_000_000;
+
+const _: () = assert!(PAYOUT_SCALE >= 1, "PAYOUT_SCALE must be at least 1");
+
+/// Vault reserve floor: 0 lamports on top of rent exemption
+/// No outflow (payouts, refunds, finalization, withdrawals) may take the vault
+/// below rent exemption plus this reserve; enforced by vault_available_lamports
+pub const MIN_VAULT_RESERVE_LAMPORTS: u64 = 0;
+
+// RANDOMNESS
+// ==========
+
+/// Additional signers whose Ed25519 signatures are mixed into the roll entropy
+/// Lets operators split trust so no single key controls the outcome
+pub const ENTROPY_SIGNERS: &[Pubkey] = &[];
+
+/// Number of distinct ENTROPY_SIGNERS that must co-sign every resolution
+/// 0 means the house signature alone provides the entropy
+pub const ENTROPY_SIGNER_THRESHOLD: usize = 0;
+
+/// Oracle allowed to sign final rolls directly via resolve_bet_oracle
+/// For operators who run their own VRF off-chain; None disables oracle resolution
+pub const ORACLE_AUTHORITY: Option<Pubkey> = None;
+
+/// Switchboard On-Demand program that must own randomness accounts used by resolve_bet_vrf
+pub const SWITCHBOARD_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv");
+
+/// Require the player to co-sign every resolution
+/// When true, resolve_bet rejects transactions the player did not sign,
+/// so funds never move without the player's awareness
+pub const REQUIRE_PLAYER_SIGNATURE: bool = false;
+
+/// Bind house signatures to the current vault state
+/// When true, the signed message is the bet message followed by a hash of the vault
+/// balance and open bet count, so a signature cannot be replayed after either changes
+/// Exposed in the IDL so signers know which message to sign
+#[constant]
+pub const BIND_VAULT_STATE: bool = false;
+
+/// Most bets resolve_bets_batch settles in one transaction
+/// Each bet needs its own Ed25519 instruction and four accounts, so the
+/// transaction size limit is reached long before the compute budget; a full
+/// batch fits only with the accounts loaded from an address lookup table
+pub const MAX_BATCH_RESOLVE: usize = 3;
+
+/// Resolutions between RollDistribution events: 1,000
+/// HouseStats counts every roll by decile of its range, and the counts are
+/// published each time their total reaches a multiple of this (0 = never published)
+pub const ROLL_DISTRIBUTION_INTERVAL: u64 = 1_000;
+
+/// Largest deviation of a decile from an even split before RollDistribution flags
+/// the counts as diverged: 2000 basis points (20%) of the expected count
+/// Monitoring only; a diverged distribution does not stop the game
+pub const MAX_ROLL_DIVERGENCE_BPS: u16 = 2000;
+
 // TIMEOUT SETTINGS
 // ================
 
-/// Refund timeout: 150 slots (approximately 1 minute on Solana)
+/// Default refund timeout: 150 slots (approximately 1 minute on Solana)
 /// After this time passes without resolution, players can claim refunds
 /// Protects players from stuck bets due to house inactivity
+/// Stored in GameConfig at initialize and adjustable through update_config
 pub const REFUND_TIMEOUT_SLOTS: u64 = 150;
+
+/// Minimum game age before update_config may run: 0 slots (no minimum)
+/// When non-zero, the economics a game launched with are locked for this many
+/// slots after initialize, protecting early players from a bait-and-switch
+pub const MIN_GAME_AGE_SLOTS: u64 = 0;
+
+/// Shortest refund timeout update_config accepts: 30 slots
+/// Gives the house a reasonable window to resolve before refunds open
+pub const MIN_REFUND_TIMEOUT_SLOTS: u64 = 30;
+
+/// Slots that must pass after the commit slot before a bet can be resolved: 1
+/// Keeps the house from placing and resolving in the same slot, which would let
+/// it co-schedule both and grind the outcome
+/// Also the window in which cancel_bet is open, before the outcome can be signed
+pub const MIN_RESOLVE_DELAY_SLOTS: u64 = 1;
+
+const _: () = assert!(
+    MIN_RESOLVE_DELAY_SLOTS >= 1 && MIN_RESOLVE_DELAY_SLOTS < MIN_REFUND_TIMEOUT_SLOTS,
+    "MIN_RESOLVE_DELAY_SLOTS must be at least 1 and below MIN_REFUND_TIMEOUT_SLOTS"
+);
+
+/// Most refunds a single player may claim per refund window: 0 (no cap)
+/// Counted on the player's PlayerStats by refund_bet, keeper_refund and
+/// refund_bet_token; further refunds fail until the window rolls over, and a
+/// bet refundable for a whole window is no longer capped.
+/// House-initiated admin_refund is never capped
+pub const MAX_REFUNDS_PER_WINDOW: u16 = 0;
+
+/// Length of the refund cap window: 216,000 slots (about a day at 400ms per slot)
+pub const REFUND_WINDOW_SLOTS: u64 = 216_000;
+
+/// Default cancellation fee: 200 basis points (2%) of the wager
+/// Kept in the vault when a player backs out of a pending bet through cancel_bet
+/// Stored in GameConfig at initialize and adjustable through update_config
+pub const CANCEL_FEE_BPS: u16 = 200;
+
+const _: () = assert!(
+    CANCEL_FEE_BPS <= 10000,
+    "CANCEL_FEE_BPS must not exceed 10000 bp"
+);
+
+/// Tip paid from the vault to whoever calls keeper_refund: 0 lamports (no tip)
+/// The refunded wager always goes to the player; only this tip reaches the keeper
+pub const KEEPER_TIP_LAMPORTS: u64 = 0;
+
+const _: () = assert!(
+    REFUND_TIMEOUT_SLOTS >= MIN_REFUND_TIMEOUT_SLOTS,
+    "REFUND_TIMEOUT_SLOTS must be at least MIN_REFUND_TIMEOUT_SLOTS"
+);
+
+/// Slow resolution threshold: 0 slots (latency bonus disabled)
+/// When non-zero, a native SOL bet resolved more than this many slots after its
+/// commit slot earns the player SLOW_RESOLUTION_BONUS_BPS of the wager from the vault
+pub const SLOW_RESOLUTION_SLOTS: u64 = 0;
+
+/// Bonus paid on slow resolutions: 0 basis points of the wager
+/// Comes out of house profit, giving the house a reason to settle promptly
+pub const SLOW_RESOLUTION_BONUS_BPS: u16 = 0;
+
+/// Lifetime payout circuit breaker: 0 (disabled)
+/// When non-zero, the resolution that takes HouseStats::total_payout to this amount
+/// or beyond pauses the game for operator review. Bets already placed still settle;
+/// the operator resumes with set_pause, and the breaker does not trip again
+/// In the game's betting currency (lamports for native SOL games)
+pub const LIFETIME_PAYOUT_CAP: u64 = 0;
+
+/// Dispute window: 0 slots (settle immediately)
+/// When non-zero, resolve_bet only records the outcome and the payout moves in
+/// finalize_resolution once this many slots have passed, giving the player time
+/// to re-verify the resolution
+pub const DISPUTE_WINDOW_SLOTS: u64 = 0;
+
+/// Keep resolved bets open instead of closing them
+/// When true, resolve_bet records the rolled value and outcome on the bet and
+/// leaves it open for later inspection; the player reclaims rent with close_bet
+pub const KEEP_RESOLVED_BETS: bool = false;
+
+/// Most a single player is paid out of the vault per payout window: 0 lamports (no ceiling)
+/// When non-zero, the part of a native SOL payout above the player's remaining
+/// allowance stays in the vault, owed to the player, who collects it in later
+/// windows through claim_deferred_payout
+pub const DAILY_PAYOUT_CEILING_LAMPORTS: u64 = 0;
+
+/// Length of the payout ceiling window: 216,000 slots (about a day at 400ms per slot)
+pub const PAYOUT_WINDOW_SLOTS: u64 = 216_000;
+
+/// Escrow native SOL payouts for the player to pull instead of pushing them
+/// When true, resolution (or finalize_resolution) moves a winning payout into
+/// the player's Winnings PDA and the player withdraws it with claim_winnings
+pub const PULL_PAYOUTS: bool = false;
@@ -40,4 +40,112 @@ pub enum DiceError {
     Ed25519Signature,
     #[msg("Arithmetic overflow")]
     Overflow,
+    #[msg("The vault cannot be the recipient of a split")]
+    VaultAsRecipient,
+    #[msg("Bet seed does not have enough entropy")]
+    WeakSeed,
+    #[msg("Expected the instructions sysvar - check the account order")]
+    InvalidInstructionSysvar,
+    #[msg("Missing or invalid charity account")]
+    InvalidCharity,
+    #[msg("Bet is not awaiting finalization")]
+    BetNotPendingFinalization,
+    #[msg("Dispute window has not passed yet")]
+    DisputeWindowOpen,
+    #[msg("Vault unexpectedly holds account data")]
+    VaultHasData,
+    #[msg("House edge is out of the allowed range")]
+    InvalidHouseEdge,
+    #[msg("Player must co-sign the resolution")]
+    PlayerSignatureRequired,
+    #[msg("Randomness account was closed or reallocated")]
+    RandomnessAccountClosed,
+    #[msg("Oracle resolution is not configured")]
+    OracleNotConfigured,
+    #[msg("Oracle roll must be within the bet's dice range")]
+    InvalidOracleRoll,
+    #[msg("Bet has not been settled yet")]
+    BetNotSettled,
+    #[msg("Minimum bet must not exceed maximum bet")]
+    InvalidBetLimits,
+    #[msg("Roll limits must satisfy 2 <= min_roll <= max_roll <= roll_range <= MAX_ROLL_RANGE")]
+    InvalidRollLimits,
+    #[msg("Bet has no commit slot")]
+    BetNotCommitted,
+    #[msg("Mint does not match the game's betting currency")]
+    InvalidMint,
+    #[msg("Randomness account does not match the one committed at placement")]
+    RandomnessMismatch,
+    #[msg("Too many open bets")]
+    TooManyOpenBets,
+    #[msg("Game is paused")]
+    GamePaused,
+    #[msg("Refund timeout is below the minimum")]
+    InvalidRefundTimeout,
+    #[msg("Game still has open bets")]
+    BetsOutstanding,
+    #[msg("Batch size is empty, above the limit, or does not match the accounts")]
+    InvalidBatchSize,
+    #[msg("Signer is not the house or one of its authorities")]
+    UnauthorizedAuthority,
+    #[msg("Authority set is full")]
+    TooManyAuthorities,
+    #[msg("Key is already an authority")]
+    AuthorityAlreadyAdded,
+    #[msg("Key is not an additional authority")]
+    AuthorityNotFound,
+    #[msg("New authority must be a non-default key different from the current one")]
+    InvalidNewAuthority,
+    #[msg("Revealed secret does not match the bet's committed hash")]
+    RevealMismatch,
+    #[msg("Bet is committed to a house secret and must be resolved by reveal")]
+    RevealRequired,
+    #[msg("Players cannot refer themselves")]
+    SelfReferral,
+    #[msg("Referral account is missing or does not match the bet's referrer")]
+    InvalidReferral,
+    #[msg("Pull payouts require the player's winnings account")]
+    InvalidWinningsAccount,
+    #[msg("Vault funding does not cover the worst-case payout")]
+    UnderfundedVault,
+    #[msg("Player account cannot be the vault or the house")]
+    InvalidPlayer,
+    #[msg("Bet commit slot is ahead of the current slot")]
+    InvalidSlot,
+    #[msg("Share amount is zero, exceeds the holding, or cannot be priced")]
+    InvalidShares,
+    #[msg("Liquidity providers still hold shares in the vault")]
+    LpSharesOutstanding,
+    #[msg("Treasury is missing, does not match the config, or its share exceeds 10000 bps")]
+    InvalidTreasury,
+    #[msg("Bet randomness is already revealed")]
+    RandomnessRevealed,
+    #[msg("Cancellation fee must not exceed 10000 bps")]
+    InvalidCancelFee,
+    #[msg("Vault bump does not match the canonical bump recorded at initialize")]
+    InvalidVaultBump,
+    #[msg("Edge tiers must be at most MAX_EDGE_TIERS, strictly increasing, with bounded edges")]
+    InvalidEdgeTiers,
+    #[msg("Bet cannot be resolved in the slot it was placed in")]
+    TooEarly,
+    #[msg("The same bet account was passed more than once")]
+    DuplicateBet,
+    #[msg("Game is closing and accepts no new bets or deposits")]
+    GameClosing,
+    #[msg("Game must be put into its closing state with begin_close_game first")]
+    GameNotClosing,
+    #[msg("Players are still owed deferred winnings or rebates")]
+    PayoutsOutstanding,
+    #[msg("Player has claimed the maximum number of refunds for this window")]
+    TooManyRefunds,
+    #[msg("Bet was placed in a different game")]
+    WrongHouse,
+    #[msg("Game has not run for MIN_GAME_AGE_SLOTS yet")]
+    GameTooYoung,
+    #[msg("Bet can no longer be cancelled: it may already be resolved")]
+    CancelWindowClosed,
+    #[msg("Bet must be resolved through the method chosen at placement")]
+    ResolutionMethodMismatch,
+    #[msg("Insured bets and jackpot payouts require the pool account")]
+    PoolAccountRequired,
 }
@@ -0,0 +1,264 @@
+use anchor_lang::prelude::*;
+
+use crate::BetDirection;
+
+/// Game Initialized Event - Emitted once when a house sets up its game
+///
+/// Publishes a commitment to the randomness scheme so players can check the
+/// fairness setup before playing. Recompute it with `utils::rng_commitment`;
+/// a mismatch means the program's randomness parameters have changed.
+#[event]
+pub struct GameInitialized {
+    /// House authority that owns the game
+    pub house: Pubkey,
+
+    /// Address of the game config PDA
+    pub config: Pubkey,
+
+    /// Betting mint, or `Pubkey::default()` for a native SOL game
+    pub mint: Pubkey,
+
+    /// House edge in basis points at initialization
+    pub house_edge_bp: u16,
+
+    /// Hash of the randomness scheme parameters
+    pub rng_commitment: [u8; 32],
+}
+
+/// Vault Funded Event - Emitted whenever lamports are deposited into a vault
+///
+/// Emitted by initialize and fund_vault, so treasury monitoring sees every inflow
+/// that is not a wager.
+#[event]
+pub struct VaultFunded {
+    /// House key of the game whose vault was funded
+    pub house: Pubkey,
+
+    /// Signer who provided the lamports
+    pub actor: Pubkey,
+
+    /// Lamports deposited
+    pub amount: u64,
+
+    /// Vault balance after the deposit, in lamports
+    pub vault_balance: u64,
+}
+
+/// Vault Withdrawn Event - Emitted whenever lamports are withdrawn from a vault
+///
+/// Emitted by withdraw and withdraw_lp, so operators can alert on large outflows.
+#[event]
+pub struct VaultWithdrawn {
+    /// House key of the game whose vault was withdrawn from
+    pub house: Pubkey,
+
+    /// Signer who requested the withdrawal
+    pub actor: Pubkey,
+
+    /// Account that received the lamports
+    pub recipient: Pubkey,
+
+    /// Lamports withdrawn
+    pub amount: u64,
+
+    /// Vault balance after the withdrawal, in lamports
+    pub vault_balance: u64,
+}
+
+/// Treasury Paid Event - Emitted when a lost wager is split with the treasury
+///
+/// Emitted on resolution (or finalization, with a dispute window) of a losing bet
+/// while the config names a treasury.
+#[event]
+pub struct TreasuryPaid {
+    /// House key of the game whose vault paid the cut
+    pub house: Pubkey,
+
+    /// The losing bet the cut was taken from
+    pub bet: Pubkey,
+
+    /// Account that received the cut (the token account for token games)
+    pub treasury: Pubkey,
+
+    /// Cut paid, in lamports or the mint's base units
+    pub amount: u64,
+}
+
+/// Bet Placed Event - Emitted when a player places a new bet
+///
+/// Lets indexers and frontends track open bets without polling account state.
+#[event]
+pub struct BetPlaced {
+    /// Address of the bet PDA, so clients can subscribe to it directly
+    pub bet: Pubkey,
+
+    /// Public key of the player who placed the bet
+    pub player: Pubkey,
+
+    /// Seed distinguishing this bet from the player's other bets
+    pub seed: u128,
+
+    /// Player's roll prediction
+    pub roll: u16,
+
+    /// Whether the player wins under or over the prediction
+    pub direction: BetDirection,
+
+    /// Amount wagered in lamports
+    pub amount: u64,
+
+    /// Slot when the bet was placed
+    pub slot: u64,
+}
+
+/// Bet Resolved Event - Emitted when a bet is resolved
+///
+/// The bet account is closed on resolution, so this is the permanent record
+/// of the rolled number used for provable-fairness audits.
+#[event]
+pub struct BetResolved {
+    /// Address of the resolved bet PDA
+    pub bet: Pubkey,
+
+    /// Public key of the player who placed the bet
+    pub player: Pubkey,
+
+    /// Seed distinguishing this bet from the player's other bets
+    pub seed: u128,
+
+    /// Player's roll prediction
+    pub prediction: u16,
+
+    /// Actual rolled value (1-roll_range)
+    pub roll: u16,
+
+    /// Lamports paid to the player (0 on an uninsured loss)
+    pub payout: u64,
+
+    /// Whether the rolled value was below the prediction
+    pub won: bool,
+
+    /// Bet account rent returned to the player when the bet is closed
+    /// Separate from `payout`; 0 while the bet stays open after resolution
+    pub rent_refunded: u64,
+}
+
+/// Jackpot Won Event - Emitted when a resolution rolls JACKPOT_ROLL
+///
+/// The jackpot is included in the bet's `payout` in BetResolved; this event
+/// reports the jackpot share on its own.
+#[event]
+pub struct JackpotWon {
+    /// Address of the bet PDA that hit the jackpot
+    pub bet: Pubkey,
+
+    /// Public key of the player receiving the jackpot
+    pub player: Pubkey,
+
+    /// Lamports paid from the jackpot pool
+    pub amount: u64,
+}
+
+/// Fairness Receipt Event - Self-contained record of a signature-based resolution
+///
+/// Holds every input and output of the roll derivation and payout formula, so
+/// anyone can recompute the resolution offline:
+/// 1. Check `signature` is `authority`'s Ed25519 signature over the bet message
+/// 2. Check `entropy` is the hash of `signature` (followed by any co-signatures)
+/// 3. Derive the roll in `roll_range` from `entropy` and compare with `roll`
+/// 4. Recompute the payout from `amount`, `prediction`, `direction`, `roll_range`,
+///    `house_edge_bp`, `tie_is_push` and the config's payout rounding
+#[event]
+pub struct FairnessReceipt {
+    /// Address of the resolved bet PDA
+    pub bet: Pubkey,
+
+    /// Public key of the player who placed the bet
+    pub player: Pubkey,
+
+    /// Seed distinguishing this bet from the player's other bets
+    pub seed: u128,
+
+    /// Amount wagered in lamports
+    pub amount: u64,
+
+    /// Player's roll prediction
+    pub prediction: u16,
+
+    /// Whether the player wins under or over the prediction
+    pub direction: BetDirection,
+
+    /// Dice range the roll was drawn from (1-roll_range)
+    pub roll_range: u16,
+
+    /// House Ed25519 signature over the hash of the bet address and serialized bet data
+    pub signature: Vec<u8>,
+
+    /// Key that produced `signature`: the primary or one of the additional authorities
+    pub authority: Pubkey,
+
+    /// Entropy hashed from the verified signature(s)
+    pub entropy: [u8; 32],
+
+    /// Rolled value (1-roll_range) derived from the entropy
+    pub roll: u16,
+
+    /// Lamports paid to the player
+    pub payout: u64,
+
+    /// House edge in basis points applied to the payout
+    pub house_edge_bp: u16,
+
+    /// Whether a tie returned the stake for this bet
+    pub tie_is_push: bool,
+}
+
+/// Payout Cap Reached Event - Emitted when the lifetime payout circuit breaker pauses a game
+///
+/// The game rejects new bets until the operator reviews it and calls set_pause.
+#[event]
+pub struct PayoutCapReached {
+    /// House key of the paused game
+    pub house: Pubkey,
+
+    /// Total paid out by the game, including the resolution that tripped the breaker
+    pub total_payout: u64,
+
+    /// LIFETIME_PAYOUT_CAP the total reached
+    pub cap: u64,
+}
+
+/// Roll Distribution Event - Periodic snapshot of the game's rolls by decile
+///
+/// Emitted every ROLL_DISTRIBUTION_INTERVAL resolutions so operators can spot
+/// RNG anomalies without indexing every resolution.
+#[event]
+pub struct RollDistribution {
+    /// House key of the game
+    pub house: Pubkey,
+
+    /// Rolls counted in each tenth of the dice range, lowest first
+    pub deciles: [u64; 10],
+
+    /// Largest deviation of a decile from an even split, in basis points of the
+    /// expected count (see `utils::decile_divergence_bps`)
+    pub divergence_bps: u16,
+
+    /// Whether `divergence_bps` exceeds MAX_ROLL_DIVERGENCE_BPS
+    pub diverged: bool,
+}
+
+/// Player Stats Reconciled Event - Emitted when an authority repairs a player's open bet count
+///
+/// Reports both counts so operators can audit every manual correction.
+#[event]
+pub struct PlayerStatsReconciled {
+    /// Player whose statistics were reconciled
+    pub player: Pubkey,
+
+    /// Open bet count before the reconciliation
+    pub previous_open_bets: u16,
+
+    /// Open bet count recomputed from the player's bet accounts
+    pub open_bets: u16,
+}
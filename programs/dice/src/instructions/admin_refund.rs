@@ -0,0 +1,145 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    error::DiceError, utils::vault_available_lamports, Bet, BetTracker, GameConfig, PlayerStats,
+};
+
+/// Admin Refund Instruction - Lets the house refund a bet it cannot resolve
+///
+/// For dispute resolution, e.g. when no valid signature can be produced for a
+/// bet. Unlike refund_bet there is no timeout: an authority refunds the full
+/// wager to the player immediately. The funds can only ever go to the player.
+#[derive(Accounts)]
+pub struct AdminRefund<'info> {
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for PDA seed derivation
+    ///CHECK: This check is safe - the config constraint ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The primary authority or one of the additional authorities
+    /// Must sign the transaction
+    pub authority: Signer<'info>,
+
+    /// The player who placed the bet, receiving the refund (unchecked for efficiency)
+    ///CHECK: This check is safe - the bet seeds and constraint tie the bet to this player
+    #[account(mut)]
+    pub player: UncheckedAccount<'info>,
+
+    /// House vault funding the refund
+    /// Must hold no data - a system-owned vault is never allocated by the program
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref()],
+        bump,
+        constraint = vault.data_is_empty() @ DiceError::VaultHasData
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// The bet account to be refunded
+    /// - Must belong to `player` (enforced by constraint)
+    /// - Must have been placed in this game (enforced by constraint)
+    /// - After refund, the bet is marked as resolved like any refunded bet
+    /// - Must be a native SOL bet
+    #[account(
+        mut,
+        seeds = [b"bet", bet.player.as_ref(), bet.seed.to_le_bytes().as_ref()],
+        bump = bet.bump,
+        constraint = bet.player == player.key() @ DiceError::NotPlayerBet,
+        constraint = bet.house == house.key() @ DiceError::WrongHouse,
+        constraint = bet.mint == Pubkey::default() @ DiceError::InvalidMint
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// The game config authorizing the signer
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        constraint = config.is_authority(&authority.key()) @ DiceError::UnauthorizedAuthority
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The tracker counting the game's open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// The player's betting statistics, counting their open bets
+    ///
+    /// Seeds: ["player_stats", house_pubkey, player_pubkey]
+    #[account(
+        mut,
+        seeds = [b"player_stats", house.key().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// System program required for SOL transfers
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AdminRefund<'info> {
+    /// Refund an unresolved bet to its player right away
+    ///
+    /// # Arguments
+    /// * `bumps` - PDA bumps needed for vault signing authority
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or validation error
+    ///
+    /// # Notes
+    /// The bet must not be resolved, refunded or cancelled yet. The full wager
+    /// is returned; as with refund_bet, a donation or insurance premium is not.
+    pub fn admin_refund(&mut self, bumps: &AdminRefundBumps) -> Result<()> {
+        // SECURITY: The vault signs with the canonical bump recorded at initialize
+        require_eq!(
+            bumps.vault,
+            self.config.vault_bump,
+            DiceError::InvalidVaultBump
+        );
+
+        let bet = &mut self.bet;
+
+        // VALIDATION: Settled bets cannot be refunded again
+        require!(!bet.is_resolved, DiceError::BetAlreadyResolved);
+
+        // VALIDATION: The vault must cover the refund above its balance floor
+        if bet.amount > vault_available_lamports(&self.vault.to_account_info())? {
+            return Err(DiceError::InsufficientFunds.into());
+        }
+
+        // TRANSFER: Return the bet amount from vault to the player
+        let house_key = self.house.key();
+        let seeds = &[b"vault", house_key.as_ref(), &[bumps.vault]];
+        let signer = &[&seeds[..]];
+
+        let accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.player.to_account_info(),
+        };
+
+        let ctx =
+            CpiContext::new_with_signer(self.system_program.to_account_info(), accounts, signer);
+
+        transfer(ctx, bet.amount)?;
+
+        // TRACKER: The bet is no longer open
+        self.tracker.open_bets = self.tracker.open_bets.saturating_sub(1);
+        self.tracker.open_stakes = self.tracker.open_stakes.saturating_sub(bet.amount);
+        self.player_stats.open_bets = self.player_stats.open_bets.saturating_sub(1);
+
+        // FINALIZATION: Mark the bet as resolved so it can be neither resolved nor refunded again
+        bet.is_resolved = true;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::DiceError, GameConfig};
+
+/// Begin Close Game Instruction - Puts a game into its closing state
+///
+/// The first step of shutting a game down. A closing game takes no new bets or
+/// deposits, but every bet already placed can still be resolved, refunded or
+/// cancelled as usual, so players are never stranded. close_game only
+/// completes once the game is closing and its last open bet has settled.
+#[derive(Accounts)]
+pub struct BeginCloseGame<'info> {
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for config PDA seed derivation
+    ///CHECK: This check is safe - the config's has_one ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The game's primary authority
+    /// Must sign the transaction
+    pub authority: Signer<'info>,
+
+    /// The game config holding the closing flag
+    ///
+    /// Seeds: ["config", house_pubkey]
+    /// - Only the primary authority can start closing the game
+    #[account(
+        mut,
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, GameConfig>,
+}
+
+impl<'info> BeginCloseGame<'info> {
+    /// Mark the game as closing
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or `GameClosing` if the game is already closing
+    ///
+    /// # Notes
+    /// There is no way back: players who saw the game closing must be able to
+    /// rely on it not reopening under them.
+    pub fn begin_close_game(&mut self) -> Result<()> {
+        require!(!self.config.is_closing, DiceError::GameClosing);
+        self.config.is_closing = true;
+        Ok(())
+    }
+}
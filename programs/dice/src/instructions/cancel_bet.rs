@@ -0,0 +1,195 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use switchboard_on_demand::RandomnessAccountData;
+
+use crate::{
+    error::DiceError, utils::vault_available_lamports, Bet, BetTracker, GameConfig, PlayerStats,
+    MIN_RESOLVE_DELAY_SLOTS, SWITCHBOARD_PROGRAM_ID,
+};
+
+/// Cancel Bet Instruction - Lets players back out of a pending bet for a fee
+///
+/// Unlike refund_bet, this needs no timeout: the player can cancel as soon as
+/// the bet is placed, until it becomes resolvable MIN_RESOLVE_DELAY_SLOTS later.
+/// The config's cancel_fee_bps of the wager stays in the vault and the rest
+/// goes back to the player.
+#[derive(Accounts)]
+pub struct CancelBet<'info> {
+    /// The player cancelling the bet
+    /// Must be the same player who originally placed the bet
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// House authority (unchecked for efficiency)
+    /// Used only for vault PDA seed derivation
+    ///CHECK: This check is safe - house authority for vault seeds
+    pub house: UncheckedAccount<'info>,
+
+    /// House vault returning the wager minus the fee
+    /// Must hold no data - a system-owned vault is never allocated by the program
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref()],
+        bump,
+        constraint = vault.data_is_empty() @ DiceError::VaultHasData
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// The bet account to be cancelled
+    /// - Must belong to the requesting player (enforced by constraint)
+    /// - Must have been placed in this game (enforced by constraint)
+    /// - After cancellation, the bet is marked as resolved like a refunded bet
+    ///   and can be closed with close_bet
+    /// - Must be a native SOL bet
+    #[account(
+        mut,
+        seeds = [b"bet", bet.player.as_ref(), bet.seed.to_le_bytes().as_ref()],
+        bump = bet.bump,
+        constraint = bet.player == player.key() @ DiceError::NotPlayerBet,
+        constraint = bet.house == house.key() @ DiceError::WrongHouse,
+        constraint = bet.mint == Pubkey::default() @ DiceError::InvalidMint
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// Randomness account linked to the bet at placement
+    /// Checked so a bet cannot be cancelled once its VRF value is public
+    #[account(address = bet.randomness_account @ DiceError::RandomnessMismatch)]
+    /// CHECK: The address is matched against the bet; the data is parsed in the handler
+    pub randomness_account: AccountInfo<'info>,
+
+    /// The game config providing the cancellation fee
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The tracker counting the game's open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// The player's betting statistics, counting their open bets
+    ///
+    /// Seeds: ["player_stats", house_pubkey, player_pubkey]
+    #[account(
+        mut,
+        seeds = [b"player_stats", house.key().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// System program required for SOL transfers
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelBet<'info> {
+    /// Cancel an unresolved bet and return the wager minus the cancellation fee
+    ///
+    /// # Arguments
+    /// * `bumps` - PDA bumps needed for vault signing authority
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or validation error
+    ///
+    /// # Cancellation Requirements
+    /// 1. Bet must not already be resolved, refunded or cancelled
+    /// 2. Fewer than MIN_RESOLVE_DELAY_SLOTS slots may have passed since the commit slot
+    /// 3. A Switchboard randomness value for the bet must not be revealed yet,
+    ///    otherwise the player could cancel exactly the bets about to lose
+    /// 4. Vault must cover the returned amount above its balance floor
+    ///
+    /// # Why the window
+    /// The house's Ed25519 signature over a bet is deterministic, and any resolve
+    /// transaction (even a failed one) publishes it, so the player can compute
+    /// the roll from it. Once a bet is resolvable the outcome may be known, and
+    /// cancelling then would let the player back out of exactly the losing bets.
+    /// The house resolver never signs a bet before it is resolvable.
+    ///
+    /// # Notes
+    /// fee = amount * cancel_fee_bps / 10000, rounded down. The fee stays in the
+    /// vault; a donation or insurance premium paid at placement is not returned,
+    /// just as with refund_bet.
+    pub fn cancel_bet(&mut self, bumps: &CancelBetBumps) -> Result<()> {
+        // SECURITY: The vault signs with the canonical bump recorded at initialize
+        require_eq!(
+            bumps.vault,
+            self.config.vault_bump,
+            DiceError::InvalidVaultBump
+        );
+
+        let bet = &mut self.bet;
+
+        // VALIDATION: Settled bets cannot be cancelled
+        if bet.is_resolved {
+            return Err(DiceError::BetAlreadyResolved.into());
+        }
+
+        // VALIDATION: Only before the house may sign, and so reveal, the outcome
+        let age = Clock::get()?.slot.saturating_sub(bet.commit_slot);
+        require!(age < MIN_RESOLVE_DELAY_SLOTS, DiceError::CancelWindowClosed);
+
+        // VALIDATION: Once the VRF value is public the outcome is known
+        if *self.randomness_account.owner == SWITCHBOARD_PROGRAM_ID {
+            if let Ok(randomness_data) =
+                RandomnessAccountData::parse(self.randomness_account.data.borrow())
+            {
+                require!(
+                    randomness_data.get_value(&Clock::get()?).is_err(),
+                    DiceError::RandomnessRevealed
+                );
+            }
+        }
+
+        // FEE: The cancellation fee stays in the vault
+        let fee = (bet.amount as u128)
+            .checked_mul(self.config.cancel_fee_bps as u128)
+            .ok_or(DiceError::Overflow)?
+            .checked_div(10000)
+            .ok_or(DiceError::Overflow)? as u64;
+        let amount = bet.amount.saturating_sub(fee);
+
+        // VALIDATION: The vault must cover the returned amount above its balance floor
+        if amount > vault_available_lamports(&self.vault.to_account_info())? {
+            return Err(DiceError::InsufficientFunds.into());
+        }
+
+        if amount > 0 {
+            // TRANSFER: Return the wager minus the fee from vault to player
+            let house_key = self.house.key();
+            let seeds = &[b"vault", house_key.as_ref(), &[bumps.vault]];
+            let signer = &[&seeds[..]];
+
+            let accounts = Transfer {
+                from: self.vault.to_account_info(),
+                to: self.player.to_account_info(),
+            };
+
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                signer,
+            );
+            transfer(ctx, amount)?;
+        }
+
+        // TRACKER: The bet is no longer open
+        self.tracker.open_bets = self.tracker.open_bets.saturating_sub(1);
+        self.tracker.open_stakes = self.tracker.open_stakes.saturating_sub(bet.amount);
+        self.player_stats.open_bets = self.player_stats.open_bets.saturating_sub(1);
+
+        // FINALIZATION: Mark the bet as resolved so it can be neither resolved nor refunded
+        bet.is_resolved = true;
+
+        Ok(())
+    }
+}
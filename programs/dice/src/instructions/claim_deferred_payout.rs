@@ -0,0 +1,124 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    error::DiceError, utils::vault_available_lamports, GameConfig, HouseStats, PlayerStats,
+    DAILY_PAYOUT_CEILING_LAMPORTS,
+};
+
+/// Claim Deferred Payout Instruction - Pays out winnings held back by the payout ceiling
+///
+/// With DAILY_PAYOUT_CEILING_LAMPORTS set, the part of a payout above the
+/// player's allowance for the window stays in the vault. The player collects it
+/// here, at most one window's allowance at a time.
+#[derive(Accounts)]
+pub struct ClaimDeferredPayout<'info> {
+    /// The player claiming their deferred winnings
+    /// Must sign the transaction and receives the lamports
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for PDA seed derivation
+    ///CHECK: This check is safe - house authority for vault seeds
+    pub house: UncheckedAccount<'info>,
+
+    /// House vault holding the deferred winnings
+    /// Must hold no data - a system-owned vault is never allocated by the program
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref()],
+        bump,
+        constraint = vault.data_is_empty() @ DiceError::VaultHasData
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// The game config providing the canonical vault bump
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// Aggregate statistics for the game, counting deferred payouts
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"stats", house.key().as_ref()],
+        bump = stats.bump
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// The player's betting statistics, holding the deferred amount and window
+    ///
+    /// Seeds: ["player_stats", house_pubkey, player_pubkey]
+    #[account(
+        mut,
+        seeds = [b"player_stats", house.key().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// System program required for SOL transfers
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimDeferredPayout<'info> {
+    /// Pay as much of the deferred winnings as the current window allows
+    ///
+    /// # Arguments
+    /// * `bumps` - PDA bumps needed for vault signing
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success (claiming nothing is a no-op) or `InsufficientFunds`
+    pub fn claim_deferred_payout(&mut self, bumps: &ClaimDeferredPayoutBumps) -> Result<()> {
+        // SECURITY: The vault signs with the canonical bump recorded at initialize
+        require_eq!(
+            bumps.vault,
+            self.config.vault_bump,
+            DiceError::InvalidVaultBump
+        );
+
+        // CEILING: The claim counts against the window like any other payout
+        let deferred = self.player_stats.deferred_payout;
+        let amount = self.player_stats.take_payout_allowance(
+            deferred,
+            Clock::get()?.slot,
+            DAILY_PAYOUT_CEILING_LAMPORTS,
+        );
+        if amount == 0 {
+            return Ok(());
+        }
+
+        // SOLVENCY: Never leave the vault below its balance floor
+        require!(
+            amount <= vault_available_lamports(&self.vault.to_account_info())?,
+            DiceError::InsufficientFunds
+        );
+
+        self.player_stats.deferred_payout -= amount;
+        self.stats.deferred_payouts = self.stats.deferred_payouts.saturating_sub(amount);
+
+        // TRANSFER: Pay the claimed winnings from the vault
+        let accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.player.to_account_info(),
+        };
+
+        let house_key = self.house.key();
+        let seeds = [b"vault", house_key.as_ref(), &[bumps.vault]];
+        let signer_seeds = &[&seeds[..]][..];
+
+        let ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            accounts,
+            signer_seeds,
+        );
+        transfer(ctx, amount)
+    }
+}
@@ -0,0 +1,117 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    error::DiceError, utils::vault_available_lamports, GameConfig, HouseStats, PlayerStats,
+};
+
+/// Claim Rebate Instruction - Pays a player their accrued house edge rebates
+///
+/// With REBATE_BPS set, every resolved bet credits the player a share of its
+/// house edge. The credits stay in the vault, counted in the HouseStats rebate
+/// pool, until the player claims them here.
+#[derive(Accounts)]
+pub struct ClaimRebate<'info> {
+    /// The player claiming their rebates
+    /// Must sign the transaction and receives the lamports
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for PDA seed derivation
+    ///CHECK: This check is safe - house authority for vault seeds
+    pub house: UncheckedAccount<'info>,
+
+    /// House vault paying the rebates
+    /// Must hold no data - a system-owned vault is never allocated by the program
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref()],
+        bump,
+        constraint = vault.data_is_empty() @ DiceError::VaultHasData
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// The game config providing the canonical vault bump
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// Aggregate statistics for the game, holding the rebate pool
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"stats", house.key().as_ref()],
+        bump = stats.bump
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// The player's betting statistics, holding their unclaimed rebates
+    ///
+    /// Seeds: ["player_stats", house_pubkey, player_pubkey]
+    #[account(
+        mut,
+        seeds = [b"player_stats", house.key().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// System program required for SOL transfers
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimRebate<'info> {
+    /// Transfer the player's whole rebate balance from the vault
+    ///
+    /// # Arguments
+    /// * `bumps` - PDA bumps needed for vault signing
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success (claiming nothing is a no-op) or `InsufficientFunds`
+    pub fn claim_rebate(&mut self, bumps: &ClaimRebateBumps) -> Result<()> {
+        // SECURITY: The vault signs with the canonical bump recorded at initialize
+        require_eq!(
+            bumps.vault,
+            self.config.vault_bump,
+            DiceError::InvalidVaultBump
+        );
+
+        let amount = self.player_stats.rebate_unclaimed;
+        if amount == 0 {
+            return Ok(());
+        }
+
+        // SOLVENCY: Never leave the vault below its balance floor
+        require!(
+            amount <= vault_available_lamports(&self.vault.to_account_info())?,
+            DiceError::InsufficientFunds
+        );
+
+        self.player_stats.rebate_unclaimed = 0;
+        self.stats.rebate_pool = self.stats.rebate_pool.saturating_sub(amount);
+
+        // TRANSFER: Pay the rebates from the vault
+        let accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.player.to_account_info(),
+        };
+
+        let house_key = self.house.key();
+        let seeds = [b"vault", house_key.as_ref(), &[bumps.vault]];
+        let signer_seeds = &[&seeds[..]][..];
+
+        let ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            accounts,
+            signer_seeds,
+        );
+        transfer(ctx, amount)
+    }
+}
@@ -0,0 +1,105 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    error::DiceError,
+    utils::{require_not_vault, vault_available_lamports},
+    HouseStats, ReferralEarnings,
+};
+
+/// Claim Referral Instruction - Pays a referrer their accrued rewards
+///
+/// Rewards are credited at resolution but stay in the house vault until the
+/// referrer claims them here.
+#[derive(Accounts)]
+pub struct ClaimReferral<'info> {
+    /// The referrer claiming their rewards
+    /// Must sign the transaction and receives the lamports
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    /// House authority (unchecked for efficiency)
+    /// Used only for PDA seed derivation
+    ///CHECK: This check is safe
+    pub house: UncheckedAccount<'info>,
+
+    /// House vault paying the rewards
+    /// Must hold no data - a system-owned vault is never allocated by the program
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref()],
+        bump,
+        constraint = vault.data_is_empty() @ DiceError::VaultHasData
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// The referrer's earnings account for this game
+    ///
+    /// Seeds: ["referral", house_pubkey, referrer_pubkey]
+    #[account(
+        mut,
+        seeds = [b"referral", house.key().as_ref(), referrer.key().as_ref()],
+        bump = referral.bump
+    )]
+    pub referral: Account<'info, ReferralEarnings>,
+
+    /// Aggregate statistics for the game, counting unclaimed referral credits
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"stats", house.key().as_ref()],
+        bump = stats.bump
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// System program required for SOL transfers
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimReferral<'info> {
+    /// Transfer all unclaimed rewards from the vault to the referrer
+    ///
+    /// # Arguments
+    /// * `bumps` - PDA bumps needed for vault signing
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or `InsufficientFunds` if the vault cannot cover the claim
+    pub fn claim_referral(&mut self, bumps: &ClaimReferralBumps) -> Result<()> {
+        let amount = self.referral.unclaimed;
+        if amount == 0 {
+            return Ok(());
+        }
+
+        // SOLVENCY: Never leave the vault below its balance floor
+        require!(
+            amount <= vault_available_lamports(&self.vault.to_account_info())?,
+            DiceError::InsufficientFunds
+        );
+
+        // VALIDATION: The reward must leave the vault
+        require_not_vault(&self.referrer.key(), &self.vault.key())?;
+
+        self.referral.unclaimed = 0;
+        self.stats.referral_credits = self.stats.referral_credits.saturating_sub(amount);
+
+        // TRANSFER: Pay the rewards from the vault
+        let accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.referrer.to_account_info(),
+        };
+
+        let house_key = self.house.key();
+        let seeds = [b"vault", house_key.as_ref(), &[bumps.vault]];
+        let signer_seeds = &[&seeds[..]][..];
+
+        let ctx = CpiContext::new_with_signer(
+            self.system_program.to_account_info(),
+            accounts,
+            signer_seeds,
+        );
+        transfer(ctx, amount)
+    }
+}
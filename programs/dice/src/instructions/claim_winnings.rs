@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::Winnings;
+
+/// Claim Winnings Instruction - Lets a player pull escrowed payouts
+///
+/// With PULL_PAYOUTS set, resolution moves winning payouts from the vault into
+/// the player's Winnings PDA instead of the player's wallet. The player (or a
+/// smart wallet acting as the player) withdraws them here whenever convenient.
+#[derive(Accounts)]
+pub struct ClaimWinnings<'info> {
+    /// The player claiming their winnings
+    /// Must sign the transaction and receives the lamports
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The player's winnings escrow
+    ///
+    /// Seeds: ["winnings", player_pubkey]
+    #[account(
+        mut,
+        seeds = [b"winnings", player.key().as_ref()],
+        bump = winnings.bump
+    )]
+    pub winnings: Account<'info, Winnings>,
+}
+
+impl<'info> ClaimWinnings<'info> {
+    /// Move every unclaimed lamport from the escrow to the player
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success (claiming nothing is a no-op)
+    ///
+    /// # Notes
+    /// The escrow is program-owned, so the lamports are moved directly rather
+    /// than through a system transfer; its rent-exempt balance stays behind.
+    pub fn claim_winnings(&mut self) -> Result<()> {
+        let amount = self.winnings.take_unclaimed();
+        if amount == 0 {
+            return Ok(());
+        }
+
+        // TRANSFER: Release the escrowed lamports to the player
+        self.winnings.sub_lamports(amount)?;
+        self.player.add_lamports(amount)?;
+
+        Ok(())
+    }
+}
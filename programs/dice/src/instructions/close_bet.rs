@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::DiceError, Bet};
+
+/// Close Bet Instruction - Lets players reclaim rent from a settled bet
+///
+/// Bets stay open after settlement when KEEP_RESOLVED_BETS is set (and after
+/// a refund), so the outcome can be inspected on-chain. Once the player no
+/// longer needs the record, this closes the account and returns its rent.
+#[derive(Accounts)]
+pub struct CloseBet<'info> {
+    /// The player who placed the bet
+    /// Must sign the transaction and receives the bet account rent
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// The settled bet account to close
+    /// - Must belong to the requesting player (enforced by constraint)
+    /// - Closed and rent returned to player
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"bet", bet.player.as_ref(), bet.seed.to_le_bytes().as_ref()],
+        bump = bet.bump,
+        constraint = bet.player == player.key() @ DiceError::NotPlayerBet
+    )]
+    pub bet: Account<'info, Bet>,
+}
+
+impl<'info> CloseBet<'info> {
+    /// Check the bet is fully settled before Anchor closes it
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or `BetNotSettled` error
+    ///
+    /// # Requirements
+    /// 1. The bet must be resolved or refunded
+    /// 2. The bet must not still be waiting out its dispute window
+    pub fn close_bet(&mut self) -> Result<()> {
+        require!(
+            self.bet.is_resolved && self.bet.resolved_slot == 0,
+            DiceError::BetNotSettled
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{error::DiceError, BetTracker, GameConfig, HouseStats};
+
+/// Close Game Instruction - Tears down a house's native SOL game
+///
+/// Returns everything left in the vault and the insurance and jackpot pools to
+/// the primary authority and closes the config, tracker and stats accounts to reclaim their rent.
+/// Only completes for a game put into its closing state by begin_close_game.
+/// Refused while any bet is still open, so no player can be left unpaid, and
+/// while liquidity providers hold shares, so their deposits are never swept.
+#[derive(Accounts)]
+pub struct CloseGame<'info> {
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for PDA seed derivation
+    ///CHECK: This check is safe - the config's has_one ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The game's primary authority shutting it down
+    /// Must sign the transaction and receives all remaining funds and rent
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The house vault, emptied completely
+    ///
+    /// Seeds: ["vault", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref()],
+        bump,
+        constraint = vault.data_is_empty() @ DiceError::VaultHasData
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Insurance pool, emptied completely
+    ///
+    /// Seeds: ["insurance", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"insurance", house.key().as_ref()],
+        bump
+    )]
+    pub insurance_pool: SystemAccount<'info>,
+
+    /// Jackpot pool, emptied completely
+    ///
+    /// Seeds: ["jackpot", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"jackpot", house.key().as_ref()],
+        bump
+    )]
+    pub jackpot_pool: SystemAccount<'info>,
+
+    /// The game config, closed
+    /// Must belong to a native SOL game already in its closing state
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        has_one = authority,
+        constraint = config.mint == Pubkey::default() @ DiceError::InvalidMint,
+        constraint = config.is_closing @ DiceError::GameNotClosing
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The open bet tracker, closed
+    /// No bet may be awaiting resolution or refund
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump,
+        constraint = tracker.open_bets == 0 @ DiceError::BetsOutstanding
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// Aggregate statistics for the game, closed
+    /// Every outstanding liquidity share must be the house's, and no winnings
+    /// deferred by the payout ceiling or rebates may still be owed to players
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"stats", house.key().as_ref()],
+        bump = stats.bump,
+        constraint = stats.lp_shares == stats.house_shares @ DiceError::LpSharesOutstanding,
+        constraint = stats.deferred_payouts == 0 && stats.rebate_pool == 0
+            @ DiceError::PayoutsOutstanding
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// System program required for SOL transfers
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CloseGame<'info> {
+    /// Return the vault, insurance pool and jackpot pool balances to the primary authority
+    ///
+    /// # Arguments
+    /// * `bumps` - PDA bumps needed for vault and pool signing
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or transfer error
+    ///
+    /// # Notes
+    /// The config, tracker and stats accounts are closed by their `close`
+    /// constraints once this returns. Bets already settled but left open
+    /// (KEEP_RESOLVED_BETS, refunds) remain closable by their players.
+    pub fn close_game(&mut self, bumps: &CloseGameBumps) -> Result<()> {
+        let house_key = self.house.key();
+
+        // TRANSFER: Empty the vault to the primary authority
+        let vault_balance = self.vault.lamports();
+        if vault_balance > 0 {
+            let accounts = Transfer {
+                from: self.vault.to_account_info(),
+                to: self.authority.to_account_info(),
+            };
+
+            let seeds = [b"vault", house_key.as_ref(), &[bumps.vault]];
+            let signer_seeds = &[&seeds[..]][..];
+
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                signer_seeds,
+            );
+            transfer(ctx, vault_balance)?;
+        }
+
+        // TRANSFER: Empty the insurance pool to the primary authority
+        let pool_balance = self.insurance_pool.lamports();
+        if pool_balance > 0 {
+            let accounts = Transfer {
+                from: self.insurance_pool.to_account_info(),
+                to: self.authority.to_account_info(),
+            };
+
+            let seeds = [b"insurance", house_key.as_ref(), &[bumps.insurance_pool]];
+            let signer_seeds = &[&seeds[..]][..];
+
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                signer_seeds,
+            );
+            transfer(ctx, pool_balance)?;
+        }
+
+        // TRANSFER: Empty the jackpot pool to the primary authority
+        let jackpot_balance = self.jackpot_pool.lamports();
+        if jackpot_balance > 0 {
+            let accounts = Transfer {
+                from: self.jackpot_pool.to_account_info(),
+                to: self.authority.to_account_info(),
+            };
+
+            let seeds = [b"jackpot", house_key.as_ref(), &[bumps.jackpot_pool]];
+            let signer_seeds = &[&seeds[..]][..];
+
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                signer_seeds,
+            );
+            transfer(ctx, jackpot_balance)?;
+        }
+
+        Ok(())
+    }
+}
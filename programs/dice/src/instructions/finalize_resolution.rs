@@ -0,0 +1,475 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    error::DiceError,
+    utils::{
+        decile_divergence_bps, edge_rebate, require_not_vault, slow_resolution_bonus,
+        vault_available_lamports,
+    },
+    Bet, BetResolved, BetTracker, GameConfig, HouseStats, JackpotWon, PayoutCapReached,
+    PlayerStats, ReferralEarnings, RollDistribution, TreasuryPaid, Winnings,
+    DAILY_PAYOUT_CEILING_LAMPORTS, DISPUTE_WINDOW_SLOTS, INSURANCE_COVER_BPS, JACKPOT_ROLL,
+    LIFETIME_PAYOUT_CAP, MAX_ROLL_DIVERGENCE_BPS, PULL_PAYOUTS, REBATE_BPS, REFERRAL_BPS,
+    SLOW_RESOLUTION_BONUS_BPS, SLOW_RESOLUTION_SLOTS,
+};
+
+/// Finalize Resolution Instruction - Settles a resolved bet after the dispute window
+///
+/// When DISPUTE_WINDOW_SLOTS is configured, resolve_bet only records the roll,
+/// outcome and base payout. Once the window has passed, this instruction applies
+/// everything resolve_bet would otherwise have applied (insurance, latency bonus,
+/// jackpot, referral and rebate credits, statistics), moves the payout from the
+/// vault to the player and closes the bet. It is permissionless because funds
+/// can only ever go to the original player.
+#[derive(Accounts)]
+pub struct FinalizeResolution<'info> {
+    /// House authority (unchecked for efficiency)
+    /// Used only for vault PDA seed derivation
+    ///CHECK: This check is safe - house authority for vault seeds
+    pub house: UncheckedAccount<'info>,
+
+    /// Player who placed the bet
+    /// Receives the recorded payout and the bet account rent
+    #[account(mut)]
+    ///CHECK: This is safe - must match the player stored on the bet
+    pub player: UncheckedAccount<'info>,
+
+    /// House vault containing funds for payouts
+    /// Must hold no data - a system-owned vault is never allocated by the program
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref()],
+        bump,
+        constraint = vault.data_is_empty() @ DiceError::VaultHasData
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// The resolved bet awaiting finalization
+    /// Closed and rent returned to player once settled
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"bet", bet.player.as_ref(), bet.seed.to_le_bytes().as_ref()],
+        bump = bet.bump,
+        constraint = bet.player == player.key() @ DiceError::NotPlayerBet,
+        constraint = bet.house == house.key() @ DiceError::WrongHouse,
+        constraint = bet.mint == Pubkey::default() @ DiceError::InvalidMint
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// The game config naming the treasury
+    /// Mutable so the lifetime payout circuit breaker can pause the game
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The tracker counting the game's open bets
+    /// A bet in its dispute window still counts as open until finalized here
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// Aggregate statistics for the game, counting deferred payouts
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"stats", house.key().as_ref()],
+        bump = stats.bump
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// The player's betting statistics, counting their open bets
+    ///
+    /// Seeds: ["player_stats", house_pubkey, player_pubkey]
+    #[account(
+        mut,
+        seeds = [b"player_stats", house.key().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// Insurance pool funding the cover on insured losses
+    ///
+    /// Seeds: ["insurance", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"insurance", house.key().as_ref()],
+        bump
+    )]
+    pub insurance_pool: SystemAccount<'info>,
+
+    /// Jackpot pool paid out when the recorded roll is JACKPOT_ROLL
+    ///
+    /// Seeds: ["jackpot", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"jackpot", house.key().as_ref()],
+        bump
+    )]
+    pub jackpot_pool: SystemAccount<'info>,
+
+    /// Earnings account of the bet's referrer
+    /// Required only when the bet names a referrer; must be that referrer's PDA for this game
+    #[account(mut)]
+    pub referral: Option<Account<'info, ReferralEarnings>>,
+
+    /// The player's winnings escrow
+    /// Required only when PULL_PAYOUTS is set; must belong to the player
+    #[account(mut)]
+    pub winnings: Option<Account<'info, Winnings>>,
+
+    /// Treasury receiving its share of a lost wager
+    /// Required only when the config names a treasury; must be that account
+    #[account(mut)]
+    /// CHECK: Only receives lamports; the handler matches it against the config
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// System program required for transferring payouts
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FinalizeResolution<'info> {
+    /// Pay out a resolved bet once its dispute window has passed
+    ///
+    /// # Arguments
+    /// * `bumps` - PDA bumps needed for vault signing
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or validation error
+    ///
+    /// # Requirements
+    /// 1. The bet must have been resolved by resolve_bet (refunded bets are rejected)
+    /// 2. DISPUTE_WINDOW_SLOTS must have passed since resolution
+    ///
+    /// # Settlement
+    /// Insurance cover, the jackpot and the treasury split are taken from the pools
+    /// and config as they stand at finalization. The latency bonus is measured to
+    /// the resolution slot, so waiting out the window never earns it.
+    pub fn finalize_resolution(&mut self, bumps: &FinalizeResolutionBumps) -> Result<()> {
+        // SECURITY: The vault signs with the canonical bump recorded at initialize
+        require_eq!(
+            bumps.vault,
+            self.config.vault_bump,
+            DiceError::InvalidVaultBump
+        );
+
+        let slot = Clock::get()?.slot;
+
+        // VALIDATION: Only bets resolved by resolve_bet carry a resolution slot
+        // Refunded bets are also marked resolved but never have one
+        require!(
+            self.bet.is_resolved && self.bet.resolved_slot != 0,
+            DiceError::BetNotPendingFinalization
+        );
+
+        // VALIDATION: The dispute window must have fully elapsed
+        let finalize_slot = self.bet.resolved_slot.saturating_add(DISPUTE_WINDOW_SLOTS);
+        require!(slot >= finalize_slot, DiceError::DisputeWindowOpen);
+
+        let roll = self.bet.rolled_value;
+        let won = self.bet.won;
+        // A push (tie returning the stake) is not a loss
+        let push = !won && self.bet.tie_is_push && roll == self.bet.roll;
+        let house_key = self.house.key();
+
+        // INSURANCE: An insured loss returns a fraction of the wager
+        // The pool reimburses the vault, which then pays out like a win
+        let payout = if !won && !push && self.bet.insured {
+            let cover = (self.bet.amount as u128)
+                .checked_mul(INSURANCE_COVER_BPS as u128)
+                .ok_or(DiceError::Overflow)?
+                .checked_div(10000)
+                .ok_or(DiceError::Overflow)? as u64;
+
+            // Never draw the pool below its rent-exempt minimum
+            let available = self
+                .insurance_pool
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(0));
+            let cover = cover.min(available);
+
+            if cover > 0 {
+                let accounts = Transfer {
+                    from: self.insurance_pool.to_account_info(),
+                    to: self.vault.to_account_info(),
+                };
+
+                let seeds = [b"insurance", house_key.as_ref(), &[bumps.insurance_pool]];
+                let signer_seeds = &[&seeds[..]][..];
+
+                let ctx = CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    accounts,
+                    signer_seeds,
+                );
+                transfer(ctx, cover)?;
+            }
+
+            cover
+        } else {
+            self.bet.payout
+        };
+
+        // LATENCY: Compensate the player when resolution was slow
+        // Latency is measured from the commit slot to the resolving slot, not to finalization
+        let latency = self.bet.resolved_slot.saturating_sub(self.bet.commit_slot);
+        let bonus = slow_resolution_bonus(
+            self.bet.amount,
+            latency,
+            SLOW_RESOLUTION_SLOTS,
+            SLOW_RESOLUTION_BONUS_BPS,
+        )?;
+        let payout = payout.checked_add(bonus).ok_or(DiceError::Overflow)?;
+
+        // JACKPOT: A recorded JACKPOT_ROLL wins the whole pool as it stands now
+        let jackpot = if roll == JACKPOT_ROLL {
+            // Never draw the pool below its rent-exempt minimum
+            let available = self
+                .jackpot_pool
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(0));
+            self.stats.jackpot_pool.min(available)
+        } else {
+            0
+        };
+
+        let payout = if jackpot > 0 {
+            let accounts = Transfer {
+                from: self.jackpot_pool.to_account_info(),
+                to: self.vault.to_account_info(),
+            };
+
+            let seeds = [b"jackpot", house_key.as_ref(), &[bumps.jackpot_pool]];
+            let signer_seeds = &[&seeds[..]][..];
+
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                signer_seeds,
+            );
+            transfer(ctx, jackpot)?;
+
+            self.stats.jackpot_pool = 0;
+
+            emit!(JackpotWon {
+                bet: self.bet.key(),
+                player: self.bet.player,
+                amount: jackpot,
+            });
+
+            payout.checked_add(jackpot).ok_or(DiceError::Overflow)?
+        } else {
+            payout
+        };
+
+        // TREASURY: A loss routes the configured share of the wager out of the vault
+        let treasury_cut = if won || push {
+            0
+        } else {
+            self.config.treasury_cut(self.bet.amount)
+        };
+
+        // SOLVENCY: The vault must cover the payout and treasury cut without dropping
+        // below its balance floor
+        require!(
+            payout
+                .checked_add(treasury_cut)
+                .ok_or(DiceError::Overflow)?
+                <= vault_available_lamports(&self.vault.to_account_info())?,
+            DiceError::InsufficientFunds
+        );
+
+        // EVENT: The bet closes here, so this is its permanent record
+        emit!(BetResolved {
+            bet: self.bet.key(),
+            player: self.bet.player,
+            seed: self.bet.seed,
+            prediction: self.bet.roll,
+            roll,
+            payout,
+            won,
+            rent_refunded: self.bet.to_account_info().lamports(),
+        });
+
+        // PLAYER STATS: Winnings and streak; a loss resets the streak
+        self.player_stats.record_outcome(won, push, payout)?;
+
+        // REFERRAL: Credit the referrer from the house's share, capped at the edge
+        // The reward stays in the vault until the referrer claims it
+        if let Some(referrer) = self.bet.referrer {
+            let referral = self.referral.as_mut().ok_or(DiceError::InvalidReferral)?;
+            require!(
+                referral.referrer == referrer && referral.house == house_key,
+                DiceError::InvalidReferral
+            );
+
+            let reward = (self.bet.amount as u128)
+                .checked_mul(REFERRAL_BPS.min(self.bet.house_edge_bp) as u128)
+                .ok_or(DiceError::Overflow)?
+                .checked_div(10000)
+                .ok_or(DiceError::Overflow)? as u64;
+            referral.unclaimed = referral
+                .unclaimed
+                .checked_add(reward)
+                .ok_or(DiceError::Overflow)?;
+            referral.total_earned = referral
+                .total_earned
+                .checked_add(reward)
+                .ok_or(DiceError::Overflow)?;
+            self.stats.referral_credits = self
+                .stats
+                .referral_credits
+                .checked_add(reward)
+                .ok_or(DiceError::Overflow)?;
+        }
+
+        // REBATE: Credit the player their share of the house edge on the bet
+        let rebate = edge_rebate(self.bet.amount, self.bet.house_edge_bp, REBATE_BPS);
+        if rebate > 0 {
+            self.player_stats.rebate_unclaimed = self
+                .player_stats
+                .rebate_unclaimed
+                .checked_add(rebate)
+                .ok_or(DiceError::Overflow)?;
+            self.stats.rebate_pool = self
+                .stats
+                .rebate_pool
+                .checked_add(rebate)
+                .ok_or(DiceError::Overflow)?;
+        }
+
+        // CIRCUIT BREAKER: Pause new bets for review once lifetime payouts reach the cap
+        // This bet still settles; only new bets are rejected
+        let tripped = self.stats.crosses_payout_cap(payout, LIFETIME_PAYOUT_CAP);
+
+        // STATS: Count the outcome and the amount paid out
+        self.stats.record_outcome(won, push, payout)?;
+        if tripped {
+            self.config.is_paused = true;
+            emit!(PayoutCapReached {
+                house: self.house.key(),
+                total_payout: self.stats.total_payout,
+                cap: LIFETIME_PAYOUT_CAP,
+            });
+        }
+
+        // DISTRIBUTION: Count the roll by decile, publishing the counts when due
+        if self.stats.record_roll(roll, self.bet.roll_range) {
+            let divergence_bps = decile_divergence_bps(&self.stats.roll_deciles);
+            emit!(RollDistribution {
+                house: self.house.key(),
+                deciles: self.stats.roll_deciles,
+                divergence_bps,
+                diverged: divergence_bps > MAX_ROLL_DIVERGENCE_BPS,
+            });
+        }
+        self.bet.payout = payout;
+
+        // CEILING: Pay out at most the player's remaining allowance for the window
+        // The rest stays in the vault, owed to the player through claim_deferred_payout
+        let paid_now =
+            self.player_stats
+                .take_payout_allowance(payout, slot, DAILY_PAYOUT_CEILING_LAMPORTS);
+        let deferred = payout - paid_now;
+        if deferred > 0 {
+            self.player_stats.deferred_payout = self
+                .player_stats
+                .deferred_payout
+                .checked_add(deferred)
+                .ok_or(DiceError::Overflow)?;
+            self.stats.deferred_payouts = self
+                .stats
+                .deferred_payouts
+                .checked_add(deferred)
+                .ok_or(DiceError::Overflow)?;
+        }
+
+        if paid_now > 0 {
+            // TRANSFER: Pay the recorded winnings from the house vault
+            // In pull mode the payout goes into the player's escrow instead
+            let recipient = if PULL_PAYOUTS {
+                let winnings = self
+                    .winnings
+                    .as_mut()
+                    .ok_or(DiceError::InvalidWinningsAccount)?;
+                require_keys_eq!(
+                    winnings.player,
+                    self.player.key(),
+                    DiceError::InvalidWinningsAccount
+                );
+                winnings.credit(paid_now)?;
+                winnings.to_account_info()
+            } else {
+                self.player.to_account_info()
+            };
+
+            let accounts = Transfer {
+                from: self.vault.to_account_info(),
+                to: recipient,
+            };
+
+            let seeds = [b"vault", house_key.as_ref(), &[bumps.vault]];
+            let signer_seeds = &[&seeds[..]][..];
+
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                signer_seeds,
+            );
+            transfer(ctx, paid_now)?;
+        }
+
+        if treasury_cut > 0 {
+            // TRANSFER: Send the treasury its cut of the lost wager
+            let treasury = self.treasury.as_ref().ok_or(DiceError::InvalidTreasury)?;
+            require!(
+                self.config.treasury == Some(treasury.key()),
+                DiceError::InvalidTreasury
+            );
+            require_not_vault(&treasury.key(), &self.vault.key())?;
+
+            let accounts = Transfer {
+                from: self.vault.to_account_info(),
+                to: treasury.to_account_info(),
+            };
+
+            let seeds = [b"vault", house_key.as_ref(), &[bumps.vault]];
+            let signer_seeds = &[&seeds[..]][..];
+
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                signer_seeds,
+            );
+            transfer(ctx, treasury_cut)?;
+
+            emit!(TreasuryPaid {
+                house: self.house.key(),
+                bet: self.bet.key(),
+                treasury: treasury.key(),
+                amount: treasury_cut,
+            });
+        }
+
+        // TRACKER: The bet is settled and no longer open
+        self.tracker.open_bets = self.tracker.open_bets.saturating_sub(1);
+        self.tracker.open_stakes = self.tracker.open_stakes.saturating_sub(self.bet.amount);
+        self.player_stats.open_bets = self.player_stats.open_bets.saturating_sub(1);
+
+        Ok(())
+    }
+}
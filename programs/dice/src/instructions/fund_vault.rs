@@ -0,0 +1,153 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    error::DiceError,
+    utils::{shares_for_deposit, vault_available_lamports, vault_liabilities},
+    BetTracker, GameConfig, HouseStats, VaultFunded, VaultLp,
+};
+
+/// Fund Vault Instruction - Adds liquidity to an existing native SOL game
+///
+/// initialize funds the vault once; this lets the house, or anyone else,
+/// top it up afterwards. Each deposit mints the funder liquidity shares
+/// priced against the vault's net value, redeemable through withdraw_lp.
+#[derive(Accounts)]
+pub struct FundVault<'info> {
+    /// Whoever provides the lamports
+    /// Must sign the transaction and pays for their LP account on the first deposit
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for PDA seed derivation
+    ///CHECK: This check is safe - the config seeds tie the game to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The game config, proving the game exists
+    /// Must belong to a native SOL game that is not closing
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        constraint = config.mint == Pubkey::default() @ DiceError::InvalidMint,
+        constraint = !config.is_closing @ DiceError::GameClosing
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// Aggregate statistics holding the share totals
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"stats", house.key().as_ref()],
+        bump = stats.bump
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// The tracker summing the stakes of open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// The funder's shares in this vault, created with their first deposit
+    ///
+    /// Seeds: ["lp", house_pubkey, funder_pubkey]
+    #[account(
+        init_if_needed,
+        payer = funder,
+        space = 8 + VaultLp::INIT_SPACE,
+        seeds = [b"lp", house.key().as_ref(), funder.key().as_ref()],
+        bump
+    )]
+    pub lp: Account<'info, VaultLp>,
+
+    /// The house vault receiving the lamports
+    ///
+    /// Seeds: ["vault", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref()],
+        bump,
+        constraint = vault.data_is_empty() @ DiceError::VaultHasData
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// System program required for SOL transfers
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundVault<'info> {
+    /// Transfer lamports from the funder into the vault and mint their shares
+    ///
+    /// # Arguments
+    /// * `amount` - Amount in lamports to add to the vault
+    /// * `bumps` - PDA bumps generated by Anchor
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or `InvalidShares` if the deposit would mint no shares
+    ///
+    /// # Share Pricing
+    /// shares = amount * lp_shares / net vault value, rounded down so rounding
+    /// never dilutes existing holders. The net value is the available balance
+    /// less the vault's liabilities (see `utils::vault_liabilities`), so lamports
+    /// owed back to players and referrers are never bought into.
+    /// The first deposit credits the house one share per lamport already in the
+    /// vault, so the house keeps what it funded and earned before LPs joined.
+    /// Into an empty vault the first deposit mints one share per lamport.
+    pub fn fund_vault(&mut self, amount: u64, bumps: &FundVaultBumps) -> Result<()> {
+        let value = vault_available_lamports(&self.vault.to_account_info())?
+            .saturating_sub(vault_liabilities(&self.tracker, &self.stats));
+        let stats = &mut self.stats;
+
+        // FIRST DEPOSITOR: Everything in the vault so far belongs to the house
+        if stats.lp_shares == 0 {
+            stats.house_shares = value;
+            stats.lp_shares = value;
+        }
+
+        // SHARES: Price the deposit against the current vault value
+        let shares = shares_for_deposit(amount, stats.lp_shares, value)?;
+        require!(shares > 0, DiceError::InvalidShares);
+
+        // TRANSFER: Move the deposit from the funder to the vault
+        let accounts = Transfer {
+            from: self.funder.to_account_info(),
+            to: self.vault.to_account_info(),
+        };
+
+        let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+
+        transfer(ctx, amount)?;
+
+        // LP: Created on the first deposit, then credited on every deposit
+        let lp = &mut self.lp;
+        lp.house = self.house.key();
+        lp.provider = self.funder.key();
+        lp.bump = bumps.lp;
+        lp.shares = lp.shares.checked_add(shares).ok_or(DiceError::Overflow)?;
+
+        self.stats.lp_shares = self
+            .stats
+            .lp_shares
+            .checked_add(shares)
+            .ok_or(DiceError::Overflow)?;
+
+        // EVENT: Report the deposit for treasury monitoring
+        emit!(VaultFunded {
+            house: self.house.key(),
+            actor: self.funder.key(),
+            amount,
+            vault_balance: self.vault.lamports(),
+        });
+
+        Ok(())
+    }
+}
@@ -3,11 +3,18 @@ use anchor_lang::{
     system_program::{transfer, Transfer},
 };
 
-/// Initialize Instruction - Sets up the house vault for the dice game
+use crate::{
+    error::DiceError, utils::rng_commitment, BetTracker, GameConfig, GameInitialized, HouseStats,
+    PayoutRounding, VaultFunded, CANCEL_FEE_BPS, HOUSE_EDGE, MAX_BET_LAMPORTS, MAX_ROLL,
+    MIN_BET_LAMPORTS, MIN_ROLL, MIN_VAULT_FUNDING_PAYOUTS, REFUND_TIMEOUT_SLOTS, ROLL_RANGE,
+};
+
+/// Initialize Instruction - Sets up the house vault and game config for the dice game
 ///
 /// This instruction must be called once by the house to fund the initial vault
 /// that will be used to pay out winning bets. The vault is a Program Derived Account (PDA)
-/// that can only be controlled by the program itself.
+/// that can only be controlled by the program itself. The game config PDA is
+/// created alongside it with the default economics.
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     /// The house authority that will fund the vault
@@ -28,6 +35,55 @@ pub struct Initialize<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
+    /// The game config holding the adjustable economics
+    ///
+    /// Seeds: ["config", house_pubkey]
+    /// - Created here with the defaults from constants.rs
+    /// - Changed later through update_config
+    #[account(
+        init,
+        payer = house,
+        space = 8 + GameConfig::INIT_SPACE,
+        seeds = [b"config", house.key().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The tracker counting the game's open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        init,
+        payer = house,
+        space = 8 + BetTracker::INIT_SPACE,
+        seeds = [b"tracker", house.key().as_ref()],
+        bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// Aggregate statistics for the game
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        init,
+        payer = house,
+        space = 8 + HouseStats::INIT_SPACE,
+        seeds = [b"stats", house.key().as_ref()],
+        bump
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// Insurance pool collecting the premiums of insured bets
+    /// Seeded here with its rent-exempt minimum, so the first premium can be any size
+    ///
+    /// Seeds: ["insurance", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"insurance", house.key().as_ref()],
+        bump
+    )]
+    pub insurance_pool: SystemAccount<'info>,
+
     /// System program required for SOL transfers between accounts
     pub system_program: Program<'info, System>,
 }
@@ -37,6 +93,8 @@ impl<'info> Initialize<'info> {
     ///
     /// # Arguments
     /// * `amount` - Amount in lamports to initially fund the vault
+    /// * `max_bet` - Maximum bet the game starts with, at most MAX_BET_LAMPORTS
+    /// * `bumps` - PDA bumps generated by Anchor
     ///
     /// # Returns
     /// * `Result<()>` - Success or anchor/system program error
@@ -44,8 +102,93 @@ impl<'info> Initialize<'info> {
     /// # Security Notes
     /// - Only the house can call this function (enforced by signer requirement)
     /// - The vault PDA ensures funds can only be withdrawn through program logic
-    /// - Initial funding ensures the vault can pay out early winning bets
-    pub fn init(&mut self, amount: u64) -> Result<()> {
+    /// - Initial funding ensures the vault can pay out early winning bets: together
+    ///   with any lamports already in the vault it must cover MIN_VAULT_FUNDING_PAYOUTS
+    ///   worst-case payouts of this game (`GameConfig::worst_case_payout`), or
+    ///   initialization fails with `UnderfundedVault`
+    /// - The config starts from the defaults in constants.rs, with the given max bet
+    /// - The insurance pool is topped up to its rent-exempt minimum by the house
+    /// - A GameInitialized event commits to the randomness scheme
+    pub fn init(&mut self, amount: u64, max_bet: u64, bumps: &InitializeBumps) -> Result<()> {
+        // VALIDATION: The starting max bet must fit the default limits
+        require!(
+            (MIN_BET_LAMPORTS..=MAX_BET_LAMPORTS).contains(&max_bet),
+            DiceError::InvalidBetLimits
+        );
+
+        // CONFIG: Start the game with the default economics
+        self.config.set_inner(GameConfig {
+            house: self.house.key(),
+            authority: self.house.key(),
+            house_edge_bp: HOUSE_EDGE,
+            min_bet: MIN_BET_LAMPORTS,
+            max_bet,
+            roll_range: ROLL_RANGE,
+            min_roll: MIN_ROLL,
+            max_roll: MAX_ROLL,
+            mint: Pubkey::default(),
+            refund_timeout_slots: REFUND_TIMEOUT_SLOTS,
+            cancel_fee_bps: CANCEL_FEE_BPS,
+            is_paused: false,
+            is_closing: false,
+            payout_rounding: PayoutRounding::Floor,
+            tie_is_push: false,
+            treasury: None,
+            treasury_bps: 0,
+            authorities: Vec::new(),
+            edge_tiers: Vec::new(),
+            created_slot: Clock::get()?.slot,
+            vault_bump: bumps.vault,
+            bump: bumps.config,
+        });
+
+        // VALIDATION: The vault must be able to pay the largest win this game allows
+        // Otherwise the first max bet on the longest shot could not be paid
+        let required = self
+            .config
+            .worst_case_payout()?
+            .checked_mul(MIN_VAULT_FUNDING_PAYOUTS)
+            .ok_or(DiceError::Overflow)?;
+        require!(
+            self.vault.lamports().saturating_add(amount) >= required,
+            DiceError::UnderfundedVault
+        );
+
+        // TRACKER: No bets are open yet
+        self.tracker.set_inner(BetTracker {
+            house: self.house.key(),
+            open_bets: 0,
+            open_stakes: 0,
+            bump: bumps.tracker,
+        });
+
+        // STATS: Start all counters at zero
+        self.stats.set_inner(HouseStats {
+            house: self.house.key(),
+            total_bets: 0,
+            total_volume: 0,
+            total_payout: 0,
+            wins: 0,
+            losses: 0,
+            jackpot_pool: 0,
+            lp_shares: 0,
+            house_shares: 0,
+            deferred_payouts: 0,
+            rebate_pool: 0,
+            referral_credits: 0,
+            roll_deciles: [0; 10],
+            bump: bumps.stats,
+        });
+
+        // EVENT: Publish the randomness scheme commitment for this game
+        emit!(GameInitialized {
+            house: self.house.key(),
+            config: self.config.key(),
+            mint: self.config.mint,
+            house_edge_bp: self.config.house_edge_bp,
+            rng_commitment: rng_commitment(&self.house.key()),
+        });
+
         // Prepare the Cross-Program Invocation (CPI) accounts for the transfer
         let cpi_accounts = Transfer {
             from: self.house.to_account_info(),
@@ -59,6 +202,28 @@ impl<'info> Initialize<'info> {
         // This funds the vault so it can pay out winning bets
         transfer(ctx, amount)?;
 
+        // INSURANCE: Seed the pool so premiums below rent exemption can be accepted
+        let shortfall = Rent::get()?
+            .minimum_balance(0)
+            .saturating_sub(self.insurance_pool.lamports());
+        if shortfall > 0 {
+            let accounts = Transfer {
+                from: self.house.to_account_info(),
+                to: self.insurance_pool.to_account_info(),
+            };
+
+            let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+            transfer(ctx, shortfall)?;
+        }
+
+        // EVENT: Report the initial funding for treasury monitoring
+        emit!(VaultFunded {
+            house: self.house.key(),
+            actor: self.house.key(),
+            amount,
+            vault_balance: self.vault.lamports(),
+        });
+
         Ok(())
     }
 }
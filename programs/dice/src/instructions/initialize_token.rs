@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+use crate::{
+    utils::rng_commitment, BetTracker, GameConfig, GameInitialized, HouseStats, PayoutRounding,
+    CANCEL_FEE_BPS, HOUSE_EDGE, MAX_BET_LAMPORTS, MAX_ROLL, MIN_BET_LAMPORTS, MIN_ROLL,
+    REFUND_TIMEOUT_SLOTS, ROLL_RANGE,
+};
+
+/// Initialize Token Instruction - Sets up an SPL token vault and game config
+///
+/// The token counterpart of initialize. The vault PDA holds no funds itself;
+/// it is the authority of an associated token account that holds the house's
+/// tokens for paying out winning bets. The config records the betting mint so
+/// every token instruction can check it.
+#[derive(Accounts)]
+pub struct InitializeToken<'info> {
+    /// The house authority that will fund the vault
+    /// Must sign the transaction and pay for the new accounts
+    #[account(mut)]
+    pub house: Signer<'info>,
+
+    /// Mint of the token the game is played in
+    pub mint: Account<'info, Mint>,
+
+    /// The house's token account funding the vault
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = house
+    )]
+    pub house_token_account: Account<'info, TokenAccount>,
+
+    /// The house vault PDA, used only as the vault token account authority
+    ///
+    /// Seeds: ["vault", house_pubkey]
+    /// CHECK: Only the address is used; the seeds guarantee it is the program's PDA
+    #[account(
+        seeds = [b"vault", house.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Associated token account of the vault PDA holding the house's tokens
+    #[account(
+        init,
+        payer = house,
+        associated_token::mint = mint,
+        associated_token::authority = vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// The game config holding the adjustable economics and the betting mint
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        init,
+        payer = house,
+        space = 8 + GameConfig::INIT_SPACE,
+        seeds = [b"config", house.key().as_ref()],
+        bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The tracker counting the game's open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        init,
+        payer = house,
+        space = 8 + BetTracker::INIT_SPACE,
+        seeds = [b"tracker", house.key().as_ref()],
+        bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// Aggregate statistics for the game
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        init,
+        payer = house,
+        space = 8 + HouseStats::INIT_SPACE,
+        seeds = [b"stats", house.key().as_ref()],
+        bump
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// Token program used for the funding transfer
+    pub token_program: Program<'info, Token>,
+
+    /// Associated token program creating the vault token account
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// System program required for account creation
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeToken<'info> {
+    /// Create the token game config and fund the vault token account
+    ///
+    /// # Arguments
+    /// * `amount` - Amount in the mint's base units to initially fund the vault
+    /// * `bumps` - PDA bumps generated by Anchor
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or anchor/token program error
+    ///
+    /// # Notes
+    /// - The default bet limits are interpreted in the mint's base units;
+    ///   the house should tune them with update_config for its token
+    /// - A house runs either a SOL game or a token game, as both share the config PDA
+    pub fn init_token(&mut self, amount: u64, bumps: &InitializeTokenBumps) -> Result<()> {
+        // CONFIG: Start the game with the default economics and the betting mint
+        self.config.set_inner(GameConfig {
+            house: self.house.key(),
+            authority: self.house.key(),
+            house_edge_bp: HOUSE_EDGE,
+            min_bet: MIN_BET_LAMPORTS,
+            max_bet: MAX_BET_LAMPORTS,
+            roll_range: ROLL_RANGE,
+            min_roll: MIN_ROLL,
+            max_roll: MAX_ROLL,
+            mint: self.mint.key(),
+            refund_timeout_slots: REFUND_TIMEOUT_SLOTS,
+            cancel_fee_bps: CANCEL_FEE_BPS,
+            is_paused: false,
+            is_closing: false,
+            payout_rounding: PayoutRounding::Floor,
+            tie_is_push: false,
+            treasury: None,
+            treasury_bps: 0,
+            authorities: Vec::new(),
+            edge_tiers: Vec::new(),
+            created_slot: Clock::get()?.slot,
+            vault_bump: bumps.vault,
+            bump: bumps.config,
+        });
+
+        // TRACKER: No bets are open yet
+        self.tracker.set_inner(BetTracker {
+            house: self.house.key(),
+            open_bets: 0,
+            open_stakes: 0,
+            bump: bumps.tracker,
+        });
+
+        // STATS: Start all counters at zero
+        self.stats.set_inner(HouseStats {
+            house: self.house.key(),
+            total_bets: 0,
+            total_volume: 0,
+            total_payout: 0,
+            wins: 0,
+            losses: 0,
+            jackpot_pool: 0,
+            lp_shares: 0,
+            house_shares: 0,
+            deferred_payouts: 0,
+            rebate_pool: 0,
+            referral_credits: 0,
+            roll_deciles: [0; 10],
+            bump: bumps.stats,
+        });
+
+        // EVENT: Publish the randomness scheme commitment for this game
+        emit!(GameInitialized {
+            house: self.house.key(),
+            config: self.config.key(),
+            mint: self.config.mint,
+            house_edge_bp: self.config.house_edge_bp,
+            rng_commitment: rng_commitment(&self.house.key()),
+        });
+
+        // TRANSFER: Fund the vault token account from the house
+        let accounts = TransferChecked {
+            from: self.house_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.vault_token_account.to_account_info(),
+            authority: self.house.to_account_info(),
+        };
+
+        let ctx = CpiContext::new(self.token_program.to_account_info(), accounts);
+        transfer_checked(ctx, amount, self.mint.decimals)
+    }
+}
@@ -0,0 +1,187 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    error::DiceError,
+    utils::{require_not_vault, vault_available_lamports},
+    Bet, BetTracker, GameConfig, PlayerStats, KEEPER_TIP_LAMPORTS, MAX_REFUNDS_PER_WINDOW,
+};
+
+/// Keeper Refund Instruction - Lets anyone refund a timed-out bet to its player
+///
+/// Performs the same refund as refund_bet, but without the player's signature,
+/// so keeper bots can clean up stuck bets. The wager always goes back to the
+/// player who placed the bet; the keeper can only receive KEEPER_TIP_LAMPORTS
+/// from the vault for the service.
+#[derive(Accounts)]
+pub struct KeeperRefund<'info> {
+    /// Whoever submits the refund
+    /// Pays the transaction fee and receives the keeper tip, if any
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    /// The player who placed the bet, receiving the refund (unchecked for efficiency)
+    ///CHECK: This check is safe - the bet seeds and constraint tie the bet to this player
+    #[account(mut)]
+    pub player: UncheckedAccount<'info>,
+
+    /// House authority (unchecked for efficiency)
+    /// Used only for vault PDA seed derivation
+    ///CHECK: This check is safe - house authority for vault seeds
+    pub house: UncheckedAccount<'info>,
+
+    /// House vault funding the refund and the keeper tip
+    /// Must hold no data - a system-owned vault is never allocated by the program
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref()],
+        bump,
+        constraint = vault.data_is_empty() @ DiceError::VaultHasData
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// The bet account to be refunded
+    /// - Must belong to `player` (enforced by constraint)
+    /// - Must have been placed in this game (enforced by constraint)
+    /// - After refund, the bet is marked as resolved to prevent double-spending
+    /// - Must be a native SOL bet
+    #[account(
+        mut,
+        seeds = [b"bet", bet.player.as_ref(), bet.seed.to_le_bytes().as_ref()],
+        bump = bet.bump,
+        constraint = bet.player == player.key() @ DiceError::NotPlayerBet,
+        constraint = bet.house == house.key() @ DiceError::WrongHouse,
+        constraint = bet.mint == Pubkey::default() @ DiceError::InvalidMint
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// The game config providing the refund timeout
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The tracker counting the game's open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// The player's betting statistics, counting their open bets
+    ///
+    /// Seeds: ["player_stats", house_pubkey, player_pubkey]
+    #[account(
+        mut,
+        seeds = [b"player_stats", house.key().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// System program required for SOL transfers
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> KeeperRefund<'info> {
+    /// Refund a timed-out bet to its player and tip the keeper
+    ///
+    /// # Arguments
+    /// * `bumps` - PDA bumps needed for vault signing authority
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or validation error
+    ///
+    /// # Notes
+    /// Eligibility is identical to refund_bet. The tip is only paid when the
+    /// vault can cover both the refund and the tip; the refund never depends on it.
+    pub fn keeper_refund(&mut self, bumps: &KeeperRefundBumps) -> Result<()> {
+        // SECURITY: The vault signs with the canonical bump recorded at initialize
+        require_eq!(
+            bumps.vault,
+            self.config.vault_bump,
+            DiceError::InvalidVaultBump
+        );
+
+        let bet = &mut self.bet;
+        let clock = Clock::get()?;
+
+        // VALIDATION: Settled bets cannot be refunded again
+        if bet.is_resolved {
+            return Err(DiceError::BetAlreadyResolved.into());
+        }
+
+        // VALIDATION: Same timeout and slot checks as a player-initiated refund
+        let slots_passed = bet.slots_since_commit(clock.slot)?;
+        if slots_passed < self.config.refund_timeout_slots {
+            return Err(DiceError::RefundNotEligible.into());
+        }
+
+        // VALIDATION: At most MAX_REFUNDS_PER_WINDOW refunds per player per window
+        // A refund the cap held back for a whole window is no longer counted
+        self.player_stats.record_refund(
+            clock.slot,
+            slots_passed - self.config.refund_timeout_slots,
+            MAX_REFUNDS_PER_WINDOW,
+        )?;
+
+        // VALIDATION: The vault must cover the refund above its balance floor
+        let available = vault_available_lamports(&self.vault.to_account_info())?;
+        if bet.amount > available {
+            return Err(DiceError::InsufficientFunds.into());
+        }
+
+        // SETUP: Prepare vault PDA signing authority
+        let house_key = self.house.key();
+        let seeds = &[b"vault", house_key.as_ref(), &[bumps.vault]];
+        let signer = &[&seeds[..]];
+
+        // TRANSFER: Return the bet amount from vault to the player, never the keeper
+        let accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.player.to_account_info(),
+        };
+        let ctx =
+            CpiContext::new_with_signer(self.system_program.to_account_info(), accounts, signer);
+        transfer(ctx, bet.amount)?;
+
+        // TRANSFER: Tip the keeper if enabled and the vault can spare it
+        #[allow(clippy::absurd_extreme_comparisons)] // KEEPER_TIP_LAMPORTS may be 0
+        let tip = if available - bet.amount >= KEEPER_TIP_LAMPORTS {
+            KEEPER_TIP_LAMPORTS
+        } else {
+            0
+        };
+        if tip > 0 {
+            require_not_vault(&self.keeper.key(), &self.vault.key())?;
+
+            let accounts = Transfer {
+                from: self.vault.to_account_info(),
+                to: self.keeper.to_account_info(),
+            };
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                signer,
+            );
+            transfer(ctx, tip)?;
+        }
+
+        // TRACKER: The bet is no longer open
+        self.tracker.open_bets = self.tracker.open_bets.saturating_sub(1);
+        self.tracker.open_stakes = self.tracker.open_stakes.saturating_sub(bet.amount);
+        self.player_stats.open_bets = self.player_stats.open_bets.saturating_sub(1);
+
+        // FINALIZATION: Mark the bet as resolved to prevent double-spending
+        bet.is_resolved = true;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::DiceError, GameConfig, MAX_AUTHORITIES};
+
+/// Manage Authorities Instruction - Lets the house share resolution and withdrawals
+///
+/// Backs add_authority and remove_authority. The primary authority stays the
+/// only key that manages the set; the additional authorities may resolve bets
+/// and withdraw profits (which always go to the primary authority).
+#[derive(Accounts)]
+pub struct ManageAuthorities<'info> {
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for config PDA seed derivation
+    ///CHECK: This check is safe - the config's has_one ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The game's primary authority
+    /// Must sign the transaction
+    pub authority: Signer<'info>,
+
+    /// The game config holding the authority set
+    ///
+    /// Seeds: ["config", house_pubkey]
+    /// - Only the primary authority can change the set
+    #[account(
+        mut,
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, GameConfig>,
+}
+
+impl<'info> ManageAuthorities<'info> {
+    /// Add a key to the game's additional authorities
+    ///
+    /// # Arguments
+    /// * `authority` - Key allowed to resolve bets and withdraw for the game
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, `AuthorityAlreadyAdded` or `TooManyAuthorities`
+    pub fn add_authority(&mut self, authority: Pubkey) -> Result<()> {
+        // VALIDATION: The primary and existing authorities are already authorized
+        require!(
+            !self.config.is_authority(&authority),
+            DiceError::AuthorityAlreadyAdded
+        );
+        require!(
+            self.config.authorities.len() < MAX_AUTHORITIES,
+            DiceError::TooManyAuthorities
+        );
+
+        self.config.authorities.push(authority);
+        Ok(())
+    }
+
+    /// Remove a key from the game's additional authorities
+    ///
+    /// # Arguments
+    /// * `authority` - Previously added key to revoke
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or `AuthorityNotFound`
+    pub fn remove_authority(&mut self, authority: Pubkey) -> Result<()> {
+        let index = self
+            .config
+            .authorities
+            .iter()
+            .position(|key| *key == authority)
+            .ok_or(DiceError::AuthorityNotFound)?;
+
+        self.config.authorities.remove(index);
+        Ok(())
+    }
+}
@@ -8,18 +8,106 @@
 // 2. place_bet   - Players place new bets with their predictions
 // 3. resolve_bet - House resolves bets using Ed25519 signatures for randomness
 // 4. refund_bet  - Players can claim refunds for unresolved bets after timeout
+// 5. finalize_resolution - Pays out a resolved bet once its dispute window passes
+// 6. withdraw    - House withdraws profits from the vault
+// 7. close_bet   - Players reclaim rent from a settled bet left open
+// 8. update_config - House adjusts the game economics stored in GameConfig
+// 9. set_pause   - House stops or resumes new bets
+// 10. begin_close_game, close_game - House winds down its game, then reclaims all funds and rent
+// 11. resolve_bets_batch - House resolves several bets in one transaction
+// 12. quote_payout, win_probability - Read-only payout and odds quotes for frontends
+// 13. add_authority, remove_authority - House manages who may resolve and withdraw
+// 14. transfer_authority - Hands control of the game to a new primary authority
+// 15. set_payout_rounding - House picks floor or nearest rounding for payouts
+// 16. register_referrer, claim_referral - Referrers sign up and collect rewards
+// 17. claim_winnings - Players pull escrowed payouts (PULL_PAYOUTS)
+// 18. keeper_refund - Anyone refunds a timed-out bet to its player
+// 19. fund_vault, withdraw_lp - Liquidity providers deposit for shares and redeem them
+// 20. verify_fairness - Read-only recomputation of a past resolution for auditors
+// 21. set_treasury - House routes a share of every lost wager to a treasury
+// 22. cancel_bet - Players back out of a pending bet for a fee
+// 23. set_tie_is_push - House chooses whether a tied roll returns the stake
+// 24. set_edge_tiers - House lowers the edge for larger stakes
+// 25. admin_refund - House refunds a bet it cannot resolve, without the timeout
+// 26. reconcile_player_stats - House recomputes a player's open bet count from their bets
+// 27. claim_deferred_payout - Players collect winnings held back by the payout ceiling
+// 28. claim_rebate - Players collect their share of the house edge (REBATE_BPS)
+// 29. initialize_token, place_bet_token, resolve_bet_token, refund_bet_token
+//     - SPL token counterparts of the native SOL instructions above
 //
 // The instructions follow Solana's Account-based programming model where
 // each instruction specifies exactly which accounts it needs and how they
 // should be validated (seeds, constraints, mutability, etc.).
 
+pub mod admin_refund;
+pub mod begin_close_game;
+pub mod cancel_bet;
+pub mod claim_deferred_payout;
+pub mod claim_rebate;
+pub mod claim_referral;
+pub mod claim_winnings;
+pub mod close_bet;
+pub mod close_game;
+pub mod finalize_resolution;
+pub mod fund_vault;
 pub mod initialize;
+pub mod initialize_token;
+pub mod keeper_refund;
+pub mod manage_authorities;
 pub mod place_bet;
+pub mod place_bet_token;
+pub mod quote_payout;
+pub mod reconcile_player_stats;
 pub mod refund_bet;
+pub mod refund_bet_token;
+pub mod register_referrer;
 pub mod resolve_bet;
+pub mod resolve_bet_token;
+pub mod resolve_bets_batch;
+pub mod set_edge_tiers;
+pub mod set_pause;
+pub mod set_payout_rounding;
+pub mod set_tie_is_push;
+pub mod set_treasury;
+pub mod transfer_authority;
+pub mod update_config;
+pub mod verify_fairness;
+pub mod withdraw;
+pub mod withdraw_lp;
 
 // Re-export all instruction types for easy access from the main program
+pub use admin_refund::*;
+pub use begin_close_game::*;
+pub use cancel_bet::*;
+pub use claim_deferred_payout::*;
+pub use claim_rebate::*;
+pub use claim_referral::*;
+pub use claim_winnings::*;
+pub use close_bet::*;
+pub use close_game::*;
+pub use finalize_resolution::*;
+pub use fund_vault::*;
 pub use initialize::*;
+pub use initialize_token::*;
+pub use keeper_refund::*;
+pub use manage_authorities::*;
 pub use place_bet::*;
+pub use place_bet_token::*;
+pub use quote_payout::*;
+pub use reconcile_player_stats::*;
 pub use refund_bet::*;
+pub use refund_bet_token::*;
+pub use register_referrer::*;
 pub use resolve_bet::*;
+pub use resolve_bet_token::*;
+pub use resolve_bets_batch::*;
+pub use set_edge_tiers::*;
+pub use set_pause::*;
+pub use set_payout_rounding::*;
+pub use set_tie_is_push::*;
+pub use set_treasury::*;
+pub use transfer_authority::*;
+pub use update_config::*;
+pub use verify_fairness::*;
+pub use withdraw::*;
+pub use withdraw_lp::*;
@@ -3,7 +3,16 @@ use anchor_lang::{
     system_program::{transfer, Transfer},
 };
 
-use crate::{error::DiceError, Bet, MAX_BET_LAMPORTS, MAX_ROLL, MIN_BET_LAMPORTS, MIN_ROLL};
+use crate::{
+    error::DiceError,
+    utils::{
+        calculate_payout, max_payout_for_vault, require_not_vault, seed_distinct_bytes,
+        stake_share, vault_available_lamports,
+    },
+    Bet, BetDirection, BetPlaced, BetTracker, GameConfig, HouseStats, PlayerStats,
+    ReferralEarnings, Winnings, CHARITY_ADDRESS, CHARITY_BPS, INSURANCE_PREMIUM_BPS, JACKPOT_BPS,
+    MAX_OPEN_BETS, MAX_OPEN_BETS_PER_PLAYER, MIN_SEED_DISTINCT_BYTES, PULL_PAYOUTS,
+};
 
 /// Place Bet Instruction - Allows players to place new dice bets
 ///
@@ -35,6 +44,84 @@ pub struct PlaceBet<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
+    /// Insurance pool collecting premiums from insured bets
+    /// Required only when the bet is insured
+    ///
+    /// Seeds: ["insurance", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"insurance", house.key().as_ref()],
+        bump
+    )]
+    pub insurance_pool: Option<SystemAccount<'info>>,
+
+    /// Jackpot pool funded by a JACKPOT_BPS share of every native SOL stake
+    /// Required only when JACKPOT_BPS is non-zero
+    ///
+    /// Seeds: ["jackpot", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"jackpot", house.key().as_ref()],
+        bump
+    )]
+    pub jackpot_pool: Option<SystemAccount<'info>>,
+
+    /// The game config providing the current betting limits
+    /// Must belong to a native SOL game; token games use place_bet_token
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        constraint = config.mint == Pubkey::default() @ DiceError::InvalidMint
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The tracker counting the game's open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// Aggregate statistics for the game
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"stats", house.key().as_ref()],
+        bump = stats.bump
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// The player's betting statistics, created with their first bet
+    ///
+    /// Seeds: ["player_stats", house_pubkey, player_pubkey]
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", house.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// The player's winnings escrow, created with their first bet in pull mode
+    /// Required only when PULL_PAYOUTS is set, so push mode never pays its rent
+    ///
+    /// Seeds: ["winnings", player_pubkey]
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + Winnings::INIT_SPACE,
+        seeds = [b"winnings", player.key().as_ref()],
+        bump
+    )]
+    pub winnings: Option<Account<'info, Winnings>>,
+
     /// The bet account to be created for this specific bet
     /// Each bet gets its own PDA to store bet details
     ///
@@ -42,8 +129,10 @@ pub struct PlaceBet<'info> {
     /// - The seed parameter allows players to have multiple concurrent bets
     /// - Each combination of player + seed creates a unique bet account
     /// - Space is calculated using Anchor's InitSpace derive macro
+    /// - init_if_needed so a reused seed fails with `BetAlreadyPlaced` in the handler
+    ///   instead of the system program's opaque "account already in use"
     #[account(
-        init,
+        init_if_needed,
         payer = player,
         space = 8 + Bet::INIT_SPACE,
         seeds = [b"bet", player.key().as_ref(), seed.to_le_bytes().as_ref()],
@@ -54,9 +143,20 @@ pub struct PlaceBet<'info> {
     /// Randomness account provided by an oracle service
     /// Used to link this bet to a specific randomness source
     /// The account data is validated manually in the handler
+    /// Must exist so it can be re-checked at resolution
     /// CHECK: The account's data is validated manually within the handler.
     pub randomness_account: AccountInfo<'info>,
 
+    /// Charity receiving the donated share of the stake
+    /// Required only when CHARITY_BPS is non-zero; must equal CHARITY_ADDRESS
+    /// CHECK: The address is validated against CHARITY_ADDRESS in the handler.
+    #[account(mut)]
+    pub charity: Option<UncheckedAccount<'info>>,
+
+    /// Earnings account of the referrer named by the bet
+    /// Required only when `referrer` is set; must be that referrer's PDA for this game
+    pub referral: Option<Account<'info, ReferralEarnings>>,
+
     /// System program required for account creation and SOL transfers
     pub system_program: Program<'info, System>,
 }
@@ -65,73 +165,305 @@ impl<'info> PlaceBet<'info> {
     /// Create a new bet with the specified parameters
     ///
     /// # Arguments
-    /// * `amount` - Bet amount in lamports (must be within MIN/MAX limits)
-    /// * `roll` - Player's roll prediction (2-96, player wins if random < roll)
+    /// * `amount` - Bet amount in lamports (must be within the config's min/max limits)
+    /// * `roll` - Player's roll prediction (config range, player wins if random < roll)
+    /// * `direction` - Roll under (random < roll wins) or roll over (random > roll wins)
     /// * `seed` - Unique seed to allow multiple bets from the same player
     /// * `randomness_account` - Public key of the randomness oracle account
+    /// * `insured` - Whether the player buys loss insurance for this bet
+    /// * `min_acceptable_payout` - Smallest winning payout the player accepts (0 = any)
+    /// * `committed_hash` - Hash of the house secret to resolve against (zeroes = none)
+    /// * `referrer` - Registered referrer credited at resolution, if any
+    /// * `vrf` - Whether the bet resolves through Switchboard VRF instead of the house
     /// * `bumps` - PDA bumps generated by Anchor
     ///
     /// # Returns
     /// * `Result<()>` - Success or validation error
     ///
     /// # Game Mechanics
-    /// - Player wins if the random roll (1-100) is LESS than their prediction
-    /// - Higher predictions = higher win chance but lower payout multiplier
-    /// - Payout formula: (bet_amount * house_edge_factor) / (prediction - 1)
+    /// - Player wins if the random roll (1-roll_range) is LESS than their prediction,
+    ///   or GREATER when rolling over
+    /// - More winning rolls = higher win chance but lower payout multiplier
+    /// - Payout formula: (bet_amount * house_edge_factor * roll_range) / winning_rolls
+    /// - CHARITY_BPS of the stake is donated to CHARITY_ADDRESS and not wagered
+    /// - JACKPOT_BPS of the rest goes to the jackpot pool and is not wagered either
+    /// - Insured bets pay an extra INSURANCE_PREMIUM_BPS of the wager into the insurance pool
+    /// - The bet is rejected unless the vault's available balance covers its winning payout
+    /// - The winning payout may not exceed MAX_PAYOUT_VAULT_BPS of the vault (`MaximumBet`)
+    #[allow(clippy::too_many_arguments)] // One argument per bet parameter
     pub fn create_bet(
         &mut self,
         amount: u64,
-        roll: u8,
+        roll: u16,
+        direction: BetDirection,
         seed: u128,
         randomness_account: Pubkey,
+        insured: bool,
+        min_acceptable_payout: u64,
+        committed_hash: [u8; 32],
+        referrer: Option<Pubkey>,
+        vrf: bool,
         bumps: &PlaceBetBumps,
     ) -> Result<()> {
-        // VALIDATION: Check bet amount is within allowed limits
-        if amount < MIN_BET_LAMPORTS {
-            return Err(DiceError::MinimumBet.into());
-        }
-        if amount > MAX_BET_LAMPORTS {
-            return Err(DiceError::MaximumBet.into());
-        }
+        // LOG: Placement inputs, so a failed transaction shows what was attempted
+        #[cfg(feature = "verbose-logs")]
+        msg!("create_bet: amount={} roll={} seed={}", amount, roll, seed);
+
+        // VALIDATION: A paused game accepts no new bets
+        require!(!self.config.is_paused, DiceError::GamePaused);
+
+        // VALIDATION: A closing game only settles the bets it already has
+        require!(!self.config.is_closing, DiceError::GameClosing);
+
+        // VALIDATION: Check bet amount is within the configured limits (inclusive)
+        // The maximum caps the house's exposure on any single bet
+        require!(amount >= self.config.min_bet, DiceError::MinimumBet);
+        require!(amount <= self.config.max_bet, DiceError::MaximumBet);
+
+        // VALIDATION: Check roll prediction is within the configured range
+        // Roll must be 2-96 by default to ensure both winning and losing outcomes are possible:
+        // a roll of 1 can never win and a roll near the range would pay impossible multipliers
+        require!(roll >= self.config.min_roll, DiceError::MinimumRoll);
+        require!(roll <= self.config.max_roll, DiceError::MaximumRoll);
 
-        // VALIDATION: Check roll prediction is within valid range
-        // Roll must be 2-96 to ensure both winning and losing outcomes are possible
-        if roll < MIN_ROLL {
-            return Err(DiceError::MinimumRoll.into());
+        // VALIDATION: Rolling over the top of the range can never win
+        require!(
+            direction.winning_outcomes(roll, self.config.roll_range) > 0,
+            DiceError::MaximumRoll
+        );
+
+        // VALIDATION: Reject trivially patterned seeds when seed entropy is enforced
+        // The seed is part of the signed bet data, so it should not be a constant pattern
+        require!(
+            seed_distinct_bytes(seed) >= MIN_SEED_DISTINCT_BYTES,
+            DiceError::WeakSeed
+        );
+
+        // VALIDATION: A bet has a single resolution method: VRF or the house secret it commits to
+        require!(
+            !vrf || committed_hash == [0; 32],
+            DiceError::ResolutionMethodMismatch
+        );
+
+        // VALIDATION: The seed must not belong to an existing bet of this player
+        // A freshly created account has no player yet; any existing bet, open or
+        // settled but not yet closed, keeps its account and must not be overwritten
+        // Once a settled bet is closed (resolution or close_bet), the seed is free again
+        require!(
+            self.bet.player == Pubkey::default(),
+            DiceError::BetAlreadyPlaced
+        );
+
+        // VALIDATION: Enforce the global limit on open bets
+        require!(
+            self.tracker.open_bets < MAX_OPEN_BETS,
+            DiceError::TooManyOpenBets
+        );
+
+        // VALIDATION: Enforce the per-player limit on open bets
+        require!(
+            self.player_stats.open_bets < MAX_OPEN_BETS_PER_PLAYER,
+            DiceError::TooManyOpenBets
+        );
+
+        // VALIDATION: A referrer must be someone else, registered for this game
+        if let Some(referrer) = referrer {
+            require_keys_neq!(referrer, self.player.key(), DiceError::SelfReferral);
+            let referral = self.referral.as_ref().ok_or(DiceError::InvalidReferral)?;
+            require!(
+                referral.referrer == referrer && referral.house == self.house.key(),
+                DiceError::InvalidReferral
+            );
         }
-        if roll > MAX_ROLL {
-            return Err(DiceError::MaximumRoll.into());
+
+        // VALIDATION: The randomness account must be live so resolution can detect
+        // it being closed or reallocated before the bet settles
+        require!(
+            self.randomness_account.lamports() > 0,
+            DiceError::RandomnessAccountClosed
+        );
+
+        // DONATION: Carve the charity share out of the stake
+        // Limits above apply to the full stake; only the remainder is wagered
+        let donation = stake_share(amount, CHARITY_BPS)?;
+        let wager = amount.checked_sub(donation).ok_or(DiceError::Overflow)?;
+
+        // JACKPOT: Carve the jackpot contribution out of the remaining stake
+        let jackpot_cut = stake_share(wager, JACKPOT_BPS)?;
+        let wager = wager.checked_sub(jackpot_cut).ok_or(DiceError::Overflow)?;
+
+        // EDGE: The stake's tier edge, locked in on the bet below
+        let house_edge_bp = self.config.house_edge_for(amount);
+
+        // SOLVENCY: Only accept a bet the vault could pay if it wins
+        let max_payout = calculate_payout(
+            wager,
+            roll,
+            self.config.roll_range,
+            direction,
+            house_edge_bp,
+            self.config.payout_rounding,
+        )?;
+        let available = vault_available_lamports(&self.vault.to_account_info())?;
+        require!(max_payout <= available, DiceError::InsufficientFunds);
+
+        // EXPOSURE: No single bet may risk more than MAX_PAYOUT_VAULT_BPS of the vault
+        // The payout depends on the roll, so long shots are capped at smaller stakes
+        require!(
+            max_payout <= max_payout_for_vault(available),
+            DiceError::MaximumBet
+        );
+
+        if donation > 0 {
+            let charity = self.charity.as_ref().ok_or(DiceError::InvalidCharity)?;
+            require_keys_eq!(
+                charity.key(),
+                CHARITY_ADDRESS.ok_or(DiceError::InvalidCharity)?,
+                DiceError::InvalidCharity
+            );
+            require_not_vault(&charity.key(), &self.vault.key())?;
+
+            // TRANSFER: Route the donation from player to charity
+            let accounts = Transfer {
+                from: self.player.to_account_info(),
+                to: charity.to_account_info(),
+            };
+
+            let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+            transfer(ctx, donation)?;
         }
 
-        // VALIDATION: Check if this bet account already has an unresolved bet
-        // This prevents overwriting existing active bets
-        if self.bet.is_resolved == false
-            && self.bet.commit_slot != 0
-            && self.bet.key() != Pubkey::default()
-        {
-            return Err(DiceError::BetAlreadyPlaced.into());
+        if jackpot_cut > 0 {
+            // TRANSFER: Route the jackpot contribution from player to the jackpot pool
+            let jackpot_pool = self
+                .jackpot_pool
+                .as_ref()
+                .ok_or(DiceError::PoolAccountRequired)?;
+            let accounts = Transfer {
+                from: self.player.to_account_info(),
+                to: jackpot_pool.to_account_info(),
+            };
+
+            let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+            transfer(ctx, jackpot_cut)?;
         }
 
-        // TRANSFER: Move bet amount from player to house vault
+        // TRANSFER: Move the wager from player to house vault
         let accounts = Transfer {
             from: self.player.to_account_info(),
             to: self.vault.to_account_info(),
         };
 
         let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
-        transfer(ctx, amount)?;
+        transfer(ctx, wager)?;
+
+        // INSURANCE: Premium is paid on top of the wager into the insurance pool
+        if insured {
+            let insurance_pool = self
+                .insurance_pool
+                .as_ref()
+                .ok_or(DiceError::PoolAccountRequired)?;
+            let premium = stake_share(wager, INSURANCE_PREMIUM_BPS)?;
+
+            let accounts = Transfer {
+                from: self.player.to_account_info(),
+                to: insurance_pool.to_account_info(),
+            };
+
+            let ctx = CpiContext::new(self.system_program.to_account_info(), accounts);
+            transfer(ctx, premium)?;
+        }
 
         // INITIALIZE: Set up the bet account with all relevant data
+        let randomness_data_len = self.randomness_account.data_len() as u64;
+        let roll_range = self.config.roll_range;
+        let tie_is_push = self.config.tie_is_push;
         self.bet.set_inner(Bet {
-            amount,                          // Bet amount in lamports
+            amount: wager,                   // Wagered amount in lamports
+            donation,                        // Charity share of the stake
             player: self.player.key(),       // Player's public key
+            house: self.house.key(),         // Game the bet belongs to
             slot: Clock::get()?.slot,        // Current slot when bet was placed
             seed,                            // Unique seed for this bet
             roll,                            // Player's roll prediction
+            roll_range,                      // Dice range fixed for this bet
+            direction,                       // Roll under or over the prediction
+            house_edge_bp,                   // Edge locked in for this bet
+            tie_is_push,                     // Tie handling locked in for this bet
             bump: bumps.bet,                 // PDA bump for this bet account
             randomness_account,              // Oracle account for randomness
-            commit_slot: Clock::get()?.slot, // Slot when bet was committed
+            randomness_data_len,             // Randomness account size at placement
+            commit_slot: Clock::get()?.slot, // Slot when bet was committed (refund timer)
             is_resolved: false,              // Bet is pending resolution
+            payout: 0,                       // Set at resolution
+            resolved_slot: 0,                // Set at resolution
+            rolled_value: 0,                 // Set at resolution
+            won: false,                      // Set at resolution
+            insured,                         // Loss insurance opt-in
+            min_acceptable_payout,           // Refund instead of paying less
+            mint: Pubkey::default(),         // Native SOL bet
+            committed_hash,                  // House secret commitment, if any
+            vrf,                             // Resolution method chosen by the player
+            referrer,                        // Credited with REFERRAL_BPS at resolution
+        });
+
+        // TRACKER: Count the new open bet
+        self.tracker.open_bets = self
+            .tracker
+            .open_bets
+            .checked_add(1)
+            .ok_or(DiceError::Overflow)?;
+        self.tracker.open_stakes = self
+            .tracker
+            .open_stakes
+            .checked_add(wager)
+            .ok_or(DiceError::Overflow)?;
+
+        // STATS: Count the bet and its wager
+        let stats = &mut self.stats;
+        stats.total_bets = stats.total_bets.checked_add(1).ok_or(DiceError::Overflow)?;
+        stats.total_volume = stats
+            .total_volume
+            .checked_add(wager)
+            .ok_or(DiceError::Overflow)?;
+        stats.fund_jackpot(jackpot_cut)?;
+
+        // WINNINGS: Created on the first bet in pull mode; only ever written here
+        if PULL_PAYOUTS {
+            let winnings = self
+                .winnings
+                .as_mut()
+                .ok_or(DiceError::InvalidWinningsAccount)?;
+            winnings.player = self.player.key();
+            winnings.bump = bumps.winnings.ok_or(DiceError::InvalidWinningsAccount)?;
+        }
+
+        // PLAYER STATS: Created on the first bet, then updated on every bet
+        let player_stats = &mut self.player_stats;
+        player_stats.player = self.player.key();
+        player_stats.bump = bumps.player_stats;
+        player_stats.bets_placed = player_stats
+            .bets_placed
+            .checked_add(1)
+            .ok_or(DiceError::Overflow)?;
+        player_stats.total_wagered = player_stats
+            .total_wagered
+            .checked_add(wager)
+            .ok_or(DiceError::Overflow)?;
+        player_stats.open_bets = player_stats
+            .open_bets
+            .checked_add(1)
+            .ok_or(DiceError::Overflow)?;
+
+        // EVENT: Announce the new bet to off-chain listeners
+        emit!(BetPlaced {
+            bet: self.bet.key(),
+            player: self.bet.player,
+            seed,
+            roll,
+            direction,
+            amount: self.bet.amount,
+            slot: self.bet.slot,
         });
 
         Ok(())
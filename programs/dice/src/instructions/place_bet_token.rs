@@ -0,0 +1,308 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::{
+    error::DiceError,
+    utils::{calculate_payout, max_payout_for_vault, seed_distinct_bytes},
+    Bet, BetDirection, BetPlaced, BetTracker, GameConfig, HouseStats, PlayerStats, MAX_OPEN_BETS,
+    MAX_OPEN_BETS_PER_PLAYER, MIN_SEED_DISTINCT_BYTES,
+};
+
+/// Place Bet Token Instruction - Places a dice bet in the game's SPL token
+///
+/// The token counterpart of place_bet: the wager moves from the player's token
+/// account into the vault token account. Charity donations and insurance are
+/// only offered for native SOL bets.
+#[derive(Accounts)]
+#[instruction(seed:u128)]
+pub struct PlaceBetToken<'info> {
+    /// The player placing the bet
+    /// Must sign the transaction and pay for the bet account
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// House authority (unchecked for efficiency)
+    /// Used only for PDA seed derivation
+    ///CHECK: This check is safe
+    pub house: UncheckedAccount<'info>,
+
+    /// The game config providing the betting limits and mint
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        has_one = mint @ DiceError::InvalidMint
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The tracker counting the game's open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// Aggregate statistics for the game
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"stats", house.key().as_ref()],
+        bump = stats.bump
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// The player's betting statistics, created with their first bet
+    ///
+    /// Seeds: ["player_stats", house_pubkey, player_pubkey]
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + PlayerStats::INIT_SPACE,
+        seeds = [b"player_stats", house.key().as_ref(), player.key().as_ref()],
+        bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// Mint of the token the game is played in
+    pub mint: Account<'info, Mint>,
+
+    /// The player's token account paying the wager
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = player
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// The house vault PDA, authority of the vault token account
+    ///
+    /// Seeds: ["vault", house_pubkey]
+    /// CHECK: Only the address is used; the seeds guarantee it is the program's PDA
+    #[account(
+        seeds = [b"vault", house.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Vault token account receiving the wager
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// The bet account to be created for this specific bet
+    ///
+    /// Seeds: ["bet", player_pubkey, seed_bytes]
+    /// - init_if_needed so a reused seed fails with `BetAlreadyPlaced`
+    #[account(
+        init_if_needed,
+        payer = player,
+        space = 8 + Bet::INIT_SPACE,
+        seeds = [b"bet", player.key().as_ref(), seed.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// Randomness account provided by an oracle service
+    /// Must exist so it can be re-checked at resolution
+    /// CHECK: The account's data is validated manually within the handler.
+    pub randomness_account: AccountInfo<'info>,
+
+    /// Token program used for the wager transfer
+    pub token_program: Program<'info, Token>,
+
+    /// System program required for account creation
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PlaceBetToken<'info> {
+    /// Create a new token bet with the specified parameters
+    ///
+    /// # Arguments
+    /// * `amount` - Bet amount in the mint's base units (within the config's min/max limits)
+    /// * `roll` - Player's roll prediction (config range, player wins if random < roll)
+    /// * `direction` - Roll under (random < roll wins) or roll over (random > roll wins)
+    /// * `seed` - Unique seed to allow multiple bets from the same player
+    /// * `min_acceptable_payout` - Smallest winning payout the player accepts (0 = any)
+    /// * `bumps` - PDA bumps generated by Anchor
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or validation error
+    pub fn create_bet_token(
+        &mut self,
+        amount: u64,
+        roll: u16,
+        direction: BetDirection,
+        seed: u128,
+        min_acceptable_payout: u64,
+        bumps: &PlaceBetTokenBumps,
+    ) -> Result<()> {
+        // VALIDATION: A paused game accepts no new bets
+        require!(!self.config.is_paused, DiceError::GamePaused);
+
+        // VALIDATION: A closing game only settles the bets it already has
+        require!(!self.config.is_closing, DiceError::GameClosing);
+
+        // VALIDATION: Same limits as native SOL bets, in the mint's base units
+        require!(amount >= self.config.min_bet, DiceError::MinimumBet);
+        require!(amount <= self.config.max_bet, DiceError::MaximumBet);
+        require!(roll >= self.config.min_roll, DiceError::MinimumRoll);
+        require!(roll <= self.config.max_roll, DiceError::MaximumRoll);
+        require!(
+            direction.winning_outcomes(roll, self.config.roll_range) > 0,
+            DiceError::MaximumRoll
+        );
+
+        // VALIDATION: Reject trivially patterned seeds when seed entropy is enforced
+        require!(
+            seed_distinct_bytes(seed) >= MIN_SEED_DISTINCT_BYTES,
+            DiceError::WeakSeed
+        );
+
+        // VALIDATION: The seed must not belong to an existing, unclosed bet
+        require!(
+            self.bet.player == Pubkey::default(),
+            DiceError::BetAlreadyPlaced
+        );
+
+        // VALIDATION: Enforce the global limit on open bets
+        require!(
+            self.tracker.open_bets < MAX_OPEN_BETS,
+            DiceError::TooManyOpenBets
+        );
+
+        // VALIDATION: Enforce the per-player limit on open bets
+        require!(
+            self.player_stats.open_bets < MAX_OPEN_BETS_PER_PLAYER,
+            DiceError::TooManyOpenBets
+        );
+
+        // VALIDATION: The randomness account must be live so resolution can re-check it
+        require!(
+            self.randomness_account.lamports() > 0,
+            DiceError::RandomnessAccountClosed
+        );
+
+        // EDGE: The stake's tier edge, locked in on the bet below
+        let house_edge_bp = self.config.house_edge_for(amount);
+
+        // SOLVENCY: Only accept a bet the vault token account could pay if it wins
+        let max_payout = calculate_payout(
+            amount,
+            roll,
+            self.config.roll_range,
+            direction,
+            house_edge_bp,
+            self.config.payout_rounding,
+        )?;
+        require!(
+            max_payout <= self.vault_token_account.amount,
+            DiceError::InsufficientFunds
+        );
+
+        // EXPOSURE: Same per-bet cap as native SOL bets
+        require!(
+            max_payout <= max_payout_for_vault(self.vault_token_account.amount),
+            DiceError::MaximumBet
+        );
+
+        // TRANSFER: Move the wager from the player's token account to the vault
+        let accounts = TransferChecked {
+            from: self.player_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.vault_token_account.to_account_info(),
+            authority: self.player.to_account_info(),
+        };
+
+        let ctx = CpiContext::new(self.token_program.to_account_info(), accounts);
+        transfer_checked(ctx, amount, self.mint.decimals)?;
+
+        // INITIALIZE: Set up the bet account, tagged with the betting mint
+        let slot = Clock::get()?.slot;
+        self.bet.set_inner(Bet {
+            amount,
+            donation: 0,
+            player: self.player.key(),
+            house: self.house.key(),
+            slot,
+            seed,
+            roll,
+            roll_range: self.config.roll_range,
+            direction,
+            house_edge_bp,
+            tie_is_push: self.config.tie_is_push,
+            bump: bumps.bet,
+            randomness_account: self.randomness_account.key(),
+            randomness_data_len: self.randomness_account.data_len() as u64,
+            commit_slot: slot,
+            is_resolved: false,
+            payout: 0,
+            resolved_slot: 0,
+            rolled_value: 0,
+            won: false,
+            insured: false,
+            min_acceptable_payout,
+            mint: self.mint.key(),
+            committed_hash: [0; 32],
+            vrf: false,
+            referrer: None,
+        });
+
+        // TRACKER: Count the new open bet
+        self.tracker.open_bets = self
+            .tracker
+            .open_bets
+            .checked_add(1)
+            .ok_or(DiceError::Overflow)?;
+        self.tracker.open_stakes = self
+            .tracker
+            .open_stakes
+            .checked_add(amount)
+            .ok_or(DiceError::Overflow)?;
+
+        // STATS: Count the bet and its wager
+        let stats = &mut self.stats;
+        stats.total_bets = stats.total_bets.checked_add(1).ok_or(DiceError::Overflow)?;
+        stats.total_volume = stats
+            .total_volume
+            .checked_add(amount)
+            .ok_or(DiceError::Overflow)?;
+
+        // PLAYER STATS: Created on the first bet, then updated on every bet
+        let player_stats = &mut self.player_stats;
+        player_stats.player = self.player.key();
+        player_stats.bump = bumps.player_stats;
+        player_stats.bets_placed = player_stats
+            .bets_placed
+            .checked_add(1)
+            .ok_or(DiceError::Overflow)?;
+        player_stats.total_wagered = player_stats
+            .total_wagered
+            .checked_add(amount)
+            .ok_or(DiceError::Overflow)?;
+        player_stats.open_bets = player_stats
+            .open_bets
+            .checked_add(1)
+            .ok_or(DiceError::Overflow)?;
+
+        // EVENT: Announce the new bet to off-chain listeners
+        emit!(BetPlaced {
+            bet: self.bet.key(),
+            player: self.bet.player,
+            seed,
+            roll,
+            direction,
+            amount,
+            slot,
+        });
+
+        Ok(())
+    }
+}
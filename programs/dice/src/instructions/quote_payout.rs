@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DiceError,
+    utils::{calculate_payout, win_probability_bp},
+    BetDirection, GameConfig, PayoutQuote, DUST_PAYOUT_POLICY,
+};
+
+/// Quote Payout Instruction - Read-only payout and odds for a prospective bet
+///
+/// Changes no state. The quote is returned through the transaction return
+/// data, so clients simulate it instead of sending it.
+#[derive(Accounts)]
+pub struct QuotePayout<'info> {
+    /// House authority (unchecked for efficiency)
+    /// Used only for config PDA seed derivation
+    ///CHECK: This check is safe
+    pub house: UncheckedAccount<'info>,
+
+    /// The game config providing the current house edge and roll limits
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GameConfig>,
+}
+
+impl<'info> QuotePayout<'info> {
+    /// Quote what a winning bet would pay under the current config
+    ///
+    /// # Arguments
+    /// * `amount` - Bet amount in lamports
+    /// * `roll` - Player's roll prediction (within the config's roll limits)
+    /// * `direction` - Roll under or roll over the prediction
+    ///
+    /// # Returns
+    /// * `Result<PayoutQuote>` - Winning payout and win probability
+    ///
+    /// # Consistency
+    /// Uses the stake's tier edge, `calculate_payout` and the dust policy exactly
+    /// like placement and resolve_bet, so the quote matches the payout of a win
+    /// resolved under the same config.
+    pub fn quote_payout(
+        &self,
+        amount: u64,
+        roll: u16,
+        direction: BetDirection,
+    ) -> Result<PayoutQuote> {
+        // VALIDATION: Only rolls a bet could be placed with are quoted
+        require!(roll >= self.config.min_roll, DiceError::MinimumRoll);
+        require!(roll <= self.config.max_roll, DiceError::MaximumRoll);
+
+        let payout = calculate_payout(
+            amount,
+            roll,
+            self.config.roll_range,
+            direction,
+            self.config.house_edge_for(amount),
+            self.config.payout_rounding,
+        )?;
+        let payout = DUST_PAYOUT_POLICY.settle(payout, amount);
+
+        // ODDS: The player wins on rolls 1..roll-1 (or roll+1..roll_range) out of roll_range
+        Ok(PayoutQuote {
+            payout,
+            win_probability_bp: win_probability_bp(roll, self.config.roll_range, direction),
+        })
+    }
+
+    /// Win probability of a prediction under the current config
+    ///
+    /// # Arguments
+    /// * `roll` - Player's roll prediction (within the config's roll limits)
+    /// * `direction` - Roll under or roll over the prediction
+    ///
+    /// # Returns
+    /// * `Result<u16>` - Win probability in basis points (4900 = 49%)
+    ///
+    /// # Consistency
+    /// Same value as the quote's `win_probability_bp`, for clients that only
+    /// display the odds.
+    pub fn win_probability(&self, roll: u16, direction: BetDirection) -> Result<u16> {
+        // VALIDATION: Only rolls a bet could be placed with are quoted
+        require!(roll >= self.config.min_roll, DiceError::MinimumRoll);
+        require!(roll <= self.config.max_roll, DiceError::MaximumRoll);
+
+        Ok(win_probability_bp(roll, self.config.roll_range, direction))
+    }
+}
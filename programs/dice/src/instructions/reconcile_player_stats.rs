@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::DiceError, Bet, GameConfig, PlayerStats, PlayerStatsReconciled};
+
+/// Reconcile Player Stats Instruction - Lets the house repair a drifted open bet counter
+///
+/// PlayerStats::open_bets is maintained incrementally on every placement and
+/// settlement. If it ever drifts from the bets actually open (e.g. after a bug
+/// fix or a migration), the player could be locked out by
+/// MAX_OPEN_BETS_PER_PLAYER. An authority recomputes it here from the player's
+/// bet accounts, passed as remaining accounts.
+#[derive(Accounts)]
+pub struct ReconcilePlayerStats<'info> {
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for PDA seed derivation
+    ///CHECK: This check is safe - the config constraint ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The primary authority or one of the additional authorities
+    /// Must sign the transaction
+    pub authority: Signer<'info>,
+
+    /// The game config authorizing the signer
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        constraint = config.is_authority(&authority.key()) @ DiceError::UnauthorizedAuthority
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// Player whose statistics are reconciled (unchecked for efficiency)
+    ///CHECK: This check is safe - only used for PDA seed derivation
+    pub player: UncheckedAccount<'info>,
+
+    /// The player's betting statistics, counting their open bets
+    ///
+    /// Seeds: ["player_stats", house_pubkey, player_pubkey]
+    #[account(
+        mut,
+        seeds = [b"player_stats", house.key().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+}
+
+impl<'info> ReconcilePlayerStats<'info> {
+    /// Recompute the player's open bet count from their bet accounts
+    ///
+    /// # Arguments
+    /// * `bets` - Every open bet account of the player, as remaining accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or validation error
+    ///
+    /// # Security Requirements
+    /// 1. Every account must be a bet PDA of this player in this game, listed once
+    /// 2. Only bets still awaiting settlement are counted: pending bets and
+    ///    bets waiting out their dispute window
+    ///
+    /// # Notes
+    /// The program cannot enumerate a player's bets, so the count is only as
+    /// complete as the list the authority passes; bets that are already
+    /// settled may be included and are simply not counted.
+    pub fn reconcile_player_stats(&mut self, bets: &'info [AccountInfo<'info>]) -> Result<()> {
+        let player = self.player.key();
+        let mut seen: Vec<Pubkey> = Vec::with_capacity(bets.len());
+        let mut open_bets: u16 = 0;
+
+        for bet_info in bets {
+            // SECURITY: Each bet must be this program's PDA for the player
+            let bet: Account<'info, Bet> = Account::try_from(bet_info)?;
+            let bet_address = Pubkey::create_program_address(
+                &[
+                    b"bet",
+                    player.as_ref(),
+                    bet.seed.to_le_bytes().as_ref(),
+                    &[bet.bump],
+                ],
+                &crate::ID,
+            )
+            .map_err(|_| DiceError::NotPlayerBet)?;
+            require_keys_eq!(bet_address, bet_info.key(), DiceError::NotPlayerBet);
+            require_keys_eq!(bet.player, player, DiceError::NotPlayerBet);
+            require_keys_eq!(bet.house, self.house.key(), DiceError::WrongHouse);
+
+            // VALIDATION: A bet listed twice would be counted twice
+            require!(!seen.contains(bet_info.key), DiceError::DuplicateBet);
+            seen.push(bet_info.key());
+
+            // COUNT: Pending bets and bets waiting out the dispute window are still open
+            if !bet.is_resolved || bet.resolved_slot != 0 {
+                open_bets = open_bets.checked_add(1).ok_or(DiceError::Overflow)?;
+            }
+        }
+
+        let previous_open_bets = self.player_stats.open_bets;
+        self.player_stats.open_bets = open_bets;
+
+        emit!(PlayerStatsReconciled {
+            player,
+            previous_open_bets,
+            open_bets,
+        });
+
+        Ok(())
+    }
+}
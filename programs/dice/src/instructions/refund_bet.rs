@@ -3,7 +3,10 @@ use anchor_lang::{
     system_program::{transfer, Transfer},
 };
 
-use crate::{error::DiceError, Bet, REFUND_TIMEOUT_SLOTS};
+use crate::{
+    error::DiceError, utils::vault_available_lamports, Bet, BetTracker, GameConfig, PlayerStats,
+    MAX_REFUNDS_PER_WINDOW,
+};
 
 /// Refund Bet Instruction - Allows players to recover funds from unresolved bets
 ///
@@ -25,25 +28,60 @@ pub struct RefundBet<'info> {
 
     /// House vault containing the funds to be refunded
     /// Must have sufficient balance to cover the refund amount
+    /// Must hold no data - a system-owned vault is never allocated by the program
     #[account(
         mut,
         seeds = [b"vault", house.key().as_ref()],
-        bump
+        bump,
+        constraint = vault.data_is_empty() @ DiceError::VaultHasData
     )]
     pub vault: SystemAccount<'info>,
 
     /// The bet account to be refunded
     /// - Must belong to the requesting player (enforced by constraint)
-    /// - Seeds ensure only the original player can access their bet
+    /// - Must have been placed in this game (enforced by constraint)
+    /// - Seeds (from the stored player) ensure it is a genuine bet PDA
     /// - After refund, the bet is marked as resolved to prevent double-spending
+    /// - Must be a native SOL bet; token bets use refund_bet_token
     #[account(
         mut,
-        seeds = [b"bet", player.key().as_ref(), bet.seed.to_le_bytes().as_ref()],
+        seeds = [b"bet", bet.player.as_ref(), bet.seed.to_le_bytes().as_ref()],
         bump = bet.bump,
-        constraint = bet.player == player.key() @ DiceError::NotPlayerBet
+        constraint = bet.player == player.key() @ DiceError::NotPlayerBet,
+        constraint = bet.house == house.key() @ DiceError::WrongHouse,
+        constraint = bet.mint == Pubkey::default() @ DiceError::InvalidMint
     )]
     pub bet: Account<'info, Bet>,
 
+    /// The game config providing the refund timeout
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The tracker counting the game's open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// The player's betting statistics, counting their open bets
+    ///
+    /// Seeds: ["player_stats", house_pubkey, player_pubkey]
+    #[account(
+        mut,
+        seeds = [b"player_stats", house.key().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
     /// System program required for SOL transfers
     pub system_program: Program<'info, System>,
 }
@@ -58,36 +96,75 @@ impl<'info> RefundBet<'info> {
     /// * `Result<()>` - Success or validation error
     ///
     /// # Refund Eligibility Requirements
-    /// 1. Bet must not already be resolved
-    /// 2. Sufficient time (REFUND_TIMEOUT_SLOTS) must have passed since bet placement
-    /// 3. Vault must have sufficient funds for the refund
+    /// 1. Bet must not already be resolved and must have a commit slot
+    /// 2. Sufficient time (the config's refund_timeout_slots) must have passed since bet placement
+    /// 3. Vault must have sufficient funds above its balance floor for the refund
     /// 4. Only the original player can request refund (enforced by account constraints)
+    /// 5. The player must be below MAX_REFUNDS_PER_WINDOW refunds in the current window,
+    ///    unless the bet has been refundable for a whole REFUND_WINDOW_SLOTS
     ///
     /// # Safety Mechanisms
     /// - Bet is marked as resolved after refund to prevent double-spending
     /// - Timeout prevents immediate refunds that could disrupt normal game flow
     /// - Vault balance check ensures refund won't fail due to insufficient funds
+    ///
+    /// # Already-Resolved Bets
+    /// A bet that has been settled can be in one of two states:
+    /// - Resolved and closed: resolve_bet (or finalize_resolution) closed the account,
+    ///   so Anchor rejects the missing `bet` with `AccountNotInitialized` before this runs
+    /// - Resolved but open: a refunded bet, or a bet awaiting its dispute window, keeps
+    ///   its account with `is_resolved = true` and fails here with `BetAlreadyResolved`
     pub fn refund_bet(&mut self, bumps: &RefundBetBumps) -> Result<()> {
+        // SECURITY: The vault signs with the canonical bump recorded at initialize
+        require_eq!(
+            bumps.vault,
+            self.config.vault_bump,
+            DiceError::InvalidVaultBump
+        );
+
         let bet = &mut self.bet;
         let clock = Clock::get()?;
 
         // VALIDATION: Check if bet is already resolved
         // Resolved bets (win/loss/previous refund) cannot be refunded again
-        if bet.is_resolved == true {
+        // This is also where a refund racing a resolve that left the bet open loses
+        // Closed bets never reach this point: their account no longer deserializes
+        if bet.is_resolved {
             return Err(DiceError::BetAlreadyResolved.into());
         }
 
         // VALIDATION: Check if enough time has passed for refund eligibility
         // This prevents immediate refunds and gives the house reasonable time to resolve bets
-        // REFUND_TIMEOUT_SLOTS is typically 150 slots (~1 minute on Solana)
-        let slots_passed = clock.slot.saturating_sub(bet.commit_slot);
-        if slots_passed < REFUND_TIMEOUT_SLOTS {
+        // The timeout is read from the config, 150 slots (~1 minute on Solana) by default
+        // A commit slot ahead of the clock (e.g. after a fork rollback) fails with InvalidSlot
+        // and a bet without a commit slot fails with BetNotCommitted
+        let slots_passed = bet.slots_since_commit(clock.slot)?;
+
+        // LOG: Eligibility inputs, so a rejected refund shows how long remains
+        #[cfg(feature = "verbose-logs")]
+        msg!(
+            "refund_bet: slots_passed={} timeout={} eligible={}",
+            slots_passed,
+            self.config.refund_timeout_slots,
+            slots_passed >= self.config.refund_timeout_slots
+        );
+
+        if slots_passed < self.config.refund_timeout_slots {
             return Err(DiceError::RefundNotEligible.into());
         }
 
+        // VALIDATION: At most MAX_REFUNDS_PER_WINDOW refunds per player per window
+        // A refund the cap held back for a whole window is no longer counted
+        self.player_stats.record_refund(
+            clock.slot,
+            slots_passed - self.config.refund_timeout_slots,
+            MAX_REFUNDS_PER_WINDOW,
+        )?;
+
         // VALIDATION: Check if vault has sufficient funds for the refund
         // This prevents runtime errors during the transfer operation
-        if bet.amount > self.vault.to_account_info().lamports() {
+        // Refunds respect the same balance floor as every other vault outflow
+        if bet.amount > vault_available_lamports(&self.vault.to_account_info())? {
             return Err(DiceError::InsufficientFunds.into());
         }
 
@@ -108,6 +185,11 @@ impl<'info> RefundBet<'info> {
 
         transfer(ctx, bet.amount)?;
 
+        // TRACKER: The bet is no longer open
+        self.tracker.open_bets = self.tracker.open_bets.saturating_sub(1);
+        self.tracker.open_stakes = self.tracker.open_stakes.saturating_sub(bet.amount);
+        self.player_stats.open_bets = self.player_stats.open_bets.saturating_sub(1);
+
         // FINALIZATION: Mark the bet as resolved to prevent double-spending
         // This ensures the bet cannot be refunded again or resolved normally
         bet.is_resolved = true;
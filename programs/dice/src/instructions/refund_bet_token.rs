@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::{error::DiceError, Bet, BetTracker, GameConfig, PlayerStats, MAX_REFUNDS_PER_WINDOW};
+
+/// Refund Bet Token Instruction - Recovers the wager of an unresolved token bet
+///
+/// The token counterpart of refund_bet, with the same timeout rules. The
+/// wager is returned from the vault token account with the vault PDA signing.
+#[derive(Accounts)]
+pub struct RefundBetToken<'info> {
+    /// The player requesting the refund
+    /// Must be the same player who originally placed the bet
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// House authority (unchecked for efficiency)
+    /// Used only for vault PDA seed derivation
+    ///CHECK: This check is safe - house authority for vault seeds
+    pub house: UncheckedAccount<'info>,
+
+    /// Mint of the token the bet was placed in
+    pub mint: Account<'info, Mint>,
+
+    /// The player's token account receiving the refund
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = player
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// The house vault PDA, signing authority of the vault token account
+    ///
+    /// Seeds: ["vault", house_pubkey]
+    /// CHECK: Only the address and bump are used; the seeds guarantee it is the program's PDA
+    #[account(
+        seeds = [b"vault", house.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Vault token account holding the wager
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// The token bet to be refunded
+    /// Marked as resolved after the refund to prevent double-spending
+    /// Must have been placed in this game (enforced by constraint)
+    #[account(
+        mut,
+        seeds = [b"bet", bet.player.as_ref(), bet.seed.to_le_bytes().as_ref()],
+        bump = bet.bump,
+        constraint = bet.player == player.key() @ DiceError::NotPlayerBet,
+        constraint = bet.house == house.key() @ DiceError::WrongHouse,
+        constraint = bet.mint == mint.key() @ DiceError::InvalidMint
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// The game config providing the refund timeout
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The tracker counting the game's open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// The player's betting statistics, counting their open bets
+    ///
+    /// Seeds: ["player_stats", house_pubkey, player_pubkey]
+    #[account(
+        mut,
+        seeds = [b"player_stats", house.key().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// Token program used for the refund transfer
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> RefundBetToken<'info> {
+    /// Process a refund request for an unresolved token bet
+    ///
+    /// # Arguments
+    /// * `bumps` - PDA bumps needed for vault signing authority
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or validation error
+    ///
+    /// # Refund Eligibility Requirements
+    /// Same as refund_bet: unresolved, committed, and older than the config's refund timeout
+    pub fn refund_bet_token(&mut self, bumps: &RefundBetTokenBumps) -> Result<()> {
+        // SECURITY: The vault signs with the canonical bump recorded at initialize
+        require_eq!(
+            bumps.vault,
+            self.config.vault_bump,
+            DiceError::InvalidVaultBump
+        );
+
+        let bet = &mut self.bet;
+
+        // VALIDATION: Resolved bets (win/loss/previous refund) cannot be refunded again
+        require!(!bet.is_resolved, DiceError::BetAlreadyResolved);
+
+        let clock = Clock::get()?;
+
+        // VALIDATION: Check if enough time has passed for refund eligibility
+        // A commit slot ahead of the clock (e.g. after a fork rollback) fails with InvalidSlot
+        // and a bet without a commit slot fails with BetNotCommitted
+        let slots_passed = bet.slots_since_commit(clock.slot)?;
+        require!(
+            slots_passed >= self.config.refund_timeout_slots,
+            DiceError::RefundNotEligible
+        );
+
+        // VALIDATION: At most MAX_REFUNDS_PER_WINDOW refunds per player per window
+        // A refund the cap held back for a whole window is no longer counted
+        self.player_stats.record_refund(
+            clock.slot,
+            slots_passed - self.config.refund_timeout_slots,
+            MAX_REFUNDS_PER_WINDOW,
+        )?;
+
+        // VALIDATION: The vault token account must hold the wager
+        require!(
+            bet.amount <= self.vault_token_account.amount,
+            DiceError::InsufficientFunds
+        );
+
+        // TRANSFER: Return the wager from the vault token account to the player
+        let accounts = TransferChecked {
+            from: self.vault_token_account.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.player_token_account.to_account_info(),
+            authority: self.vault.to_account_info(),
+        };
+
+        let house_key = self.house.key();
+        let seeds = &[b"vault", house_key.as_ref(), &[bumps.vault]];
+        let signer = &[&seeds[..]];
+
+        let ctx =
+            CpiContext::new_with_signer(self.token_program.to_account_info(), accounts, signer);
+        transfer_checked(ctx, bet.amount, self.mint.decimals)?;
+
+        // TRACKER: The bet is no longer open
+        self.tracker.open_bets = self.tracker.open_bets.saturating_sub(1);
+        self.tracker.open_stakes = self.tracker.open_stakes.saturating_sub(bet.amount);
+        self.player_stats.open_bets = self.player_stats.open_bets.saturating_sub(1);
+
+        // FINALIZATION: Mark the bet as resolved to prevent double-spending
+        bet.is_resolved = true;
+
+        Ok(())
+    }
+}
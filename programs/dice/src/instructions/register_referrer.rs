@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::ReferralEarnings;
+
+/// Register Referrer Instruction - Creates a referrer's earnings account for a game
+///
+/// A referrer must be registered before bets can name them: place_bet and
+/// resolve_bet credit referral rewards to this PDA, and claim_referral pays
+/// them out.
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    /// The referrer registering for rewards
+    /// Must sign the transaction and pay for the earnings account
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    /// House authority (unchecked for efficiency)
+    /// Used only for PDA seed derivation
+    ///CHECK: This check is safe
+    pub house: UncheckedAccount<'info>,
+
+    /// The referrer's earnings account for this game
+    ///
+    /// Seeds: ["referral", house_pubkey, referrer_pubkey]
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + ReferralEarnings::INIT_SPACE,
+        seeds = [b"referral", house.key().as_ref(), referrer.key().as_ref()],
+        bump
+    )]
+    pub referral: Account<'info, ReferralEarnings>,
+
+    /// System program required for account creation
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterReferrer<'info> {
+    /// Initialize the referrer's earnings account
+    ///
+    /// # Arguments
+    /// * `bumps` - PDA bumps generated by Anchor
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success
+    pub fn register_referrer(&mut self, bumps: &RegisterReferrerBumps) -> Result<()> {
+        self.referral.set_inner(ReferralEarnings {
+            house: self.house.key(),
+            referrer: self.referrer.key(),
+            unclaimed: 0,
+            total_earned: 0,
+            bump: bumps.referral,
+        });
+        Ok(())
+    }
+}
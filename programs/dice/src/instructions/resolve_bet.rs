@@ -3,11 +3,30 @@ use anchor_lang::{
     prelude::*,
     system_program::{transfer, Transfer},
 };
-use solana_program::{
-    ed25519_program, hash::hash, sysvar::instructions::load_instruction_at_checked,
-};
+use solana_program::hash::hashv;
+use switchboard_on_demand::RandomnessAccountData;
 
-use crate::{error::DiceError, state::Bet, HOUSE_EDGE};
+use crate::{
+    error::DiceError,
+    events::{
+        BetResolved, FairnessReceipt, JackpotWon, PayoutCapReached, RollDistribution, TreasuryPaid,
+    },
+    state::{
+        Bet, BetTracker, GameConfig, HouseStats, PlayerStats, ReferralEarnings, ResolutionOutcome,
+        Winnings,
+    },
+    utils::{
+        bet_message, calculate_payout, calculate_payout_scaled, decile_divergence_bps, edge_rebate,
+        insurance_cover, load_preceding_ed25519_instruction, oracle_message, require_not_vault,
+        roll_from_entropy, slow_resolution_bonus, split_scaled_payout, vault_available_lamports,
+        vault_state_hash, verify_house_signature,
+    },
+    BIND_VAULT_STATE, DAILY_PAYOUT_CEILING_LAMPORTS, DISPUTE_WINDOW_SLOTS, DUST_PAYOUT_POLICY,
+    KEEP_RESOLVED_BETS, LIFETIME_PAYOUT_CAP, MAX_ROLL_DIVERGENCE_BPS, MIN_PAYOUT_LAMPORTS,
+    ORACLE_AUTHORITY, PAYOUT_SCALE, PULL_PAYOUTS, REBATE_BPS, REFERRAL_BPS,
+    SCALED_PAYOUT_ACCOUNTING, SLOW_RESOLUTION_BONUS_BPS, SLOW_RESOLUTION_SLOTS,
+    SWITCHBOARD_PROGRAM_ID,
+};
 
 /// Resolve Bet Instruction - Resolves a placed bet using Ed25519 signature for randomness
 ///
@@ -16,13 +35,18 @@ use crate::{error::DiceError, state::Bet, HOUSE_EDGE};
 /// verified, then used to generate a fair random number for the dice roll.
 #[derive(Accounts)]
 pub struct ResolveBet<'info> {
-    /// House authority that provides the Ed25519 signature for randomness
-    /// Must sign this transaction to authorize the bet resolution
-    #[account(mut)]
-    pub house: Signer<'info>,
+    /// House authority (unchecked for efficiency)
+    /// Used only for PDA seed derivation
+    ///CHECK: This check is safe - the config constraint ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The primary authority or one of the additional authorities
+    /// Provides the Ed25519 signature for randomness and must sign this transaction
+    pub authority: Signer<'info>,
 
     /// Player who placed the bet (unchecked for efficiency)
     /// Will receive payout if they win the bet
+    /// Must also sign when resolving through resolve_bet_cosigned
     /// The bet account itself enforces that this matches the original player
     #[account(mut)]
     ///CHECK: This is safe
@@ -30,29 +54,129 @@ pub struct ResolveBet<'info> {
 
     /// House vault containing funds for payouts
     /// Must match the PDA derived from house authority
+    /// Must hold no data - a system-owned vault is never allocated by the program
     #[account(
         mut,
         seeds = [b"vault", house.key().as_ref()],
-        bump
+        bump,
+        constraint = vault.data_is_empty() @ DiceError::VaultHasData
     )]
     pub vault: SystemAccount<'info>,
 
+    /// The game config providing the payout rounding and refund timeout
+    /// Mutable so the lifetime payout circuit breaker can pause the game
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        constraint = config.is_authority(&authority.key()) @ DiceError::UnauthorizedAuthority
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The tracker counting the game's open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// Aggregate statistics for the game
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"stats", house.key().as_ref()],
+        bump = stats.bump
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// The player's betting statistics
+    ///
+    /// Seeds: ["player_stats", house_pubkey, player_pubkey]
+    #[account(
+        mut,
+        seeds = [b"player_stats", house.key().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// Insurance pool funding the cover on insured losses
+    /// Required only when the bet is insured
+    ///
+    /// Seeds: ["insurance", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"insurance", house.key().as_ref()],
+        bump
+    )]
+    pub insurance_pool: Option<SystemAccount<'info>>,
+
+    /// Jackpot pool funded by a JACKPOT_BPS share of every native SOL stake
+    /// Required only when the jackpot holds funds, so never while JACKPOT_BPS is 0
+    ///
+    /// Seeds: ["jackpot", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"jackpot", house.key().as_ref()],
+        bump
+    )]
+    pub jackpot_pool: Option<SystemAccount<'info>>,
+
     /// The bet account to be resolved
-    /// - Will be closed and rent returned to player after resolution
-    /// - Must belong to the specified player (enforced by PDA seeds)
+    /// - Closed and rent returned to player after resolution, unless a
+    ///   dispute window is configured (then closed by finalize_resolution)
+    ///   or KEEP_RESOLVED_BETS is set (then closed by the player via close_bet)
+    /// - Must belong to the specified player, so the rent always goes back to
+    ///   the bettor; the PDA seeds use the stored player, so a mismatched
+    ///   player account fails with `NotPlayerBet`
     /// - Bump must match the original bet creation
+    /// - Must be a native SOL bet; token bets use resolve_bet_token
+    /// - Must still be pending: a bet refunded (or resolved) first loses the race
+    ///   with `BetAlreadyResolved`; a bet closed first fails with `AccountNotInitialized`
     #[account(
         mut,
-        close = player,  // Close account and return rent to player
-        seeds = [b"bet", player.key().as_ref(), bet.seed.to_le_bytes().as_ref()],
-        bump = bet.bump
+        seeds = [b"bet", bet.player.as_ref(), bet.seed.to_le_bytes().as_ref()],
+        bump = bet.bump,
+        constraint = bet.player == player.key() @ DiceError::NotPlayerBet,
+        constraint = bet.house == house.key() @ DiceError::WrongHouse,
+        constraint = bet.mint == Pubkey::default() @ DiceError::InvalidMint,
+        constraint = !bet.is_resolved @ DiceError::BetAlreadyResolved
     )]
     pub bet: Account<'info, Bet>,
 
+    /// Randomness account linked to the bet at placement
+    /// Must be the committed account (else `RandomnessMismatch`) and must not have
+    /// been closed or reallocated
+    #[account(address = bet.randomness_account @ DiceError::RandomnessMismatch)]
+    /// CHECK: The address is matched against the bet; liveness is checked in the handler
+    pub randomness_account: AccountInfo<'info>,
+
+    /// Earnings account of the bet's referrer
+    /// Required only when the bet names a referrer; must be that referrer's PDA for this game
+    #[account(mut)]
+    pub referral: Option<Account<'info, ReferralEarnings>>,
+
+    /// The player's winnings escrow
+    /// Required only when PULL_PAYOUTS is set; must belong to the player
+    #[account(mut)]
+    pub winnings: Option<Account<'info, Winnings>>,
+
+    /// Treasury receiving its share of a lost wager
+    /// Required only when the config names a treasury; must be that account
+    #[account(mut)]
+    /// CHECK: Only receives lamports; the handler matches it against the config
+    pub treasury: Option<UncheckedAccount<'info>>,
+
     /// Instruction sysvar account containing Ed25519 signature data
     /// Required for accessing the Ed25519 instruction that precedes this one
+    /// A wrong account in this slot (e.g. the randomness account) fails with InvalidInstructionSysvar
     #[account(
-        address = solana_program::sysvar::instructions::ID
+        address = solana_program::sysvar::instructions::ID @ DiceError::InvalidInstructionSysvar
     )]
     /// CHECK: This is safe
     pub instruction_sysvar: AccountInfo<'info>,
@@ -62,48 +186,159 @@ pub struct ResolveBet<'info> {
 }
 
 impl<'info> ResolveBet<'info> {
+    /// Verify that the player co-signed the resolution transaction
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success if the player signed, `PlayerSignatureRequired` otherwise
+    ///
+    /// # Usage
+    /// Always called by `resolve_bet_cosigned`, and by `resolve_bet` when
+    /// REQUIRE_PLAYER_SIGNATURE is set.
+    pub fn verify_player_signature(&self) -> Result<()> {
+        require!(self.player.is_signer, DiceError::PlayerSignatureRequired);
+        Ok(())
+    }
+
     /// Verify that the Ed25519 signature instruction is valid and properly formatted
     ///
     /// # Arguments
     /// * `sig` - The signature bytes that should match the Ed25519 instruction
     ///
     /// # Returns
-    /// * `Result<()>` - Success if signature is valid, error otherwise
+    /// * `Result<[u8; 32]>` - Roll entropy derived from the verified signatures
     ///
     /// # Security Requirements
-    /// 1. The preceding instruction must be an Ed25519 verification instruction
-    /// 2. The signature must be from the house authority
-    /// 3. The message being signed must be the serialized bet data
-    /// 4. No accounts should be present in the Ed25519 instruction
-    pub fn verify_ed25519_signature(&mut self, sig: &[u8]) -> Result<()> {
-        // Load the Ed25519 instruction that should precede this one
-        // Index 0 refers to the instruction immediately before this one
-        let ix = load_instruction_at_checked(0, &self.instruction_sysvar.to_account_info())?;
+    /// Delegates to `utils::verify_house_signature` with the signing authority's
+    /// key and the signable message every signature must cover.
+    pub fn verify_ed25519_signature(&mut self, sig: &[u8]) -> Result<[u8; 32]> {
+        let vault_state = self.vault_state();
+        self.verify_ed25519_signature_at(sig, 1, &vault_state)
+    }
 
-        // SECURITY: Ensure the instruction is addressed to the Ed25519 program
-        require_keys_eq!(
-            ix.program_id,
-            ed25519_program::ID,
-            DiceError::Ed25519Program
+    /// Verify the Ed25519 signature in the instruction `distance` positions back
+    ///
+    /// # Arguments
+    /// * `sig` - The signature bytes that should match the Ed25519 instruction
+    /// * `distance` - Position of the Ed25519 instruction before the current one
+    /// * `vault_state` - Vault state the signature is bound to (see `vault_state`)
+    ///
+    /// # Returns
+    /// * `Result<[u8; 32]>` - Roll entropy derived from the verified signatures
+    ///
+    /// # Usage
+    /// Used by resolve_bets_batch, where each bet has its own Ed25519 instruction
+    /// and every signature binds the vault state from before the batch.
+    ///
+    /// # Compute
+    /// With verbose-logs the remaining compute units are logged before and after
+    /// the verification, so its cost can be read from the transaction logs.
+    pub fn verify_ed25519_signature_at(
+        &mut self,
+        sig: &[u8],
+        distance: u16,
+        vault_state: &[u8; 32],
+    ) -> Result<[u8; 32]> {
+        // SECURITY: A bet committed to a house secret cannot fall back to signature entropy
+        self.bet.require_no_commitment()?;
+
+        // SECURITY: A bet placed for VRF cannot be resolved by a house signature
+        self.bet.require_resolution_method(false)?;
+
+        // LOG: Compute remaining before the message is built and the sysvar parsed
+        #[cfg(feature = "verbose-logs")]
+        solana_program::log::sol_log_compute_units();
+
+        let entropy = verify_house_signature(
+            &self.instruction_sysvar.to_account_info(),
+            &self.authority.key(),
+            &self.signable_message(vault_state)?,
+            sig,
+            distance,
+        )?;
+
+        // LOG: Compute remaining once the signature is verified
+        #[cfg(feature = "verbose-logs")]
+        solana_program::log::sol_log_compute_units();
+
+        Ok(entropy)
+    }
+
+    /// Hash of the current vault balance and open bet count
+    ///
+    /// # Returns
+    /// * `[u8; 32]` - The vault state a signature is bound to under BIND_VAULT_STATE
+    pub fn vault_state(&self) -> [u8; 32] {
+        vault_state_hash(self.vault.lamports(), self.tracker.open_bets)
+    }
+
+    /// Build the message the house must sign to resolve this bet
+    ///
+    /// # Arguments
+    /// * `vault_state` - Vault state the signature is bound to (see `vault_state`)
+    ///
+    /// # Returns
+    /// * `Result<Vec<u8>>` - The bet message (see `utils::bet_message`), followed by
+    ///   `vault_state` when BIND_VAULT_STATE is set
+    ///
+    /// # Vault State Binding
+    /// Binding the vault balance and open bet count means a signature produced
+    /// before the vault materially changed no longer verifies.
+    pub fn signable_message(&self, vault_state: &[u8; 32]) -> Result<Vec<u8>> {
+        let mut message = bet_message(&self.bet.key(), &self.bet)?;
+        if BIND_VAULT_STATE {
+            message.extend_from_slice(vault_state);
+        }
+        Ok(message)
+    }
+
+    /// Verify an oracle-signed roll for the bet
+    ///
+    /// # Arguments
+    /// * `roll` - The final roll (1-roll_range) chosen by the oracle's off-chain VRF
+    /// * `sig` - The oracle's signature over the bet data followed by the roll (u16 little-endian)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success if the oracle signed this roll for this bet
+    ///
+    /// # Security Requirements
+    /// 1. The bet must not be committed to a house secret, nor placed for VRF
+    /// 2. ORACLE_AUTHORITY must be configured
+    /// 3. The directly preceding instruction must be an Ed25519 verification
+    ///    instruction carrying exactly one signature from the oracle
+    /// 4. The signed message must be the bet message followed by `roll`
+    pub fn verify_oracle_signature(&self, roll: u16, sig: &[u8]) -> Result<()> {
+        // SECURITY: A bet committed to a house secret can only be resolved by the reveal
+        self.bet.require_no_commitment()?;
+
+        // SECURITY: A bet placed for VRF cannot be resolved by the oracle
+        self.bet.require_resolution_method(false)?;
+
+        let oracle = ORACLE_AUTHORITY.ok_or(DiceError::OracleNotConfigured)?;
+
+        // VALIDATION: The oracle reports the final roll directly
+        require!(
+            (1..=self.bet.roll_range).contains(&roll),
+            DiceError::InvalidOracleRoll
         );
 
-        // SECURITY: Ed25519 verify instructions should not have any accounts
-        require_eq!(ix.accounts.len(), 0, DiceError::Ed25519Accounts);
+        // SECURITY: Ed25519 signatures are exactly 64 bytes
+        require_eq!(sig.len(), 64, DiceError::Ed25519DataLength);
+
+        // Load the Ed25519 instruction that should precede this one
+        let ix = load_preceding_ed25519_instruction(&self.instruction_sysvar.to_account_info(), 1)?;
 
         // Parse the Ed25519 instruction data to extract signature information
         let signatures = Ed25519InstructionSignatures::unpack(&ix.data)?.0;
 
-        // SECURITY: Should contain exactly one signature
+        // SECURITY: Should contain exactly the oracle's signature
         require_eq!(signatures.len(), 1, DiceError::Ed25519DataLength);
         let signature = &signatures[0];
-
-        // SECURITY: All signature components must be verifiable (not None)
         require!(signature.is_verifiable, DiceError::Ed25519Header);
 
-        // SECURITY: Public key must match the house authority
+        // SECURITY: Public key must match the configured oracle
         require_keys_eq!(
             signature.public_key.ok_or(DiceError::Ed25519Pubkey)?,
-            self.house.key(),
+            oracle,
             DiceError::Ed25519Pubkey
         );
 
@@ -116,67 +351,594 @@ impl<'info> ResolveBet<'info> {
             DiceError::Ed25519Signature
         );
 
-        // SECURITY: Message must be the serialized bet data (prevents signature reuse)
+        // SECURITY: Message must be the bet message followed by the roll
+        let message = oracle_message(&self.bet.key(), &self.bet, roll)?;
         require!(
             &signature
                 .message
                 .as_ref()
                 .ok_or(DiceError::Ed25519Signature)?
-                .eq(&self.bet.try_to_vec()?),
+                .eq(&message),
             DiceError::Ed25519Signature
         );
 
         Ok(())
     }
 
+    /// Verify the house's revealed secret against the commitment on the bet
+    ///
+    /// # Arguments
+    /// * `secret` - The secret whose hash the house committed to before placement
+    ///
+    /// # Returns
+    /// * `Result<u16>` - The roll (1-roll_range) derived from the secret and the bet address
+    ///
+    /// # Security Requirements
+    /// 1. hash(secret) must equal the bet's `committed_hash`, so the house cannot
+    ///    pick a different secret after seeing the bet (a bet without a
+    ///    commitment never matches)
+    /// 2. The bet address is mixed into the entropy: it depends on the player's
+    ///    seed, chosen after the commitment was published
+    /// 3. The bet must not have been placed for VRF
+    pub fn verify_reveal(&self, secret: &[u8; 32]) -> Result<u16> {
+        // SECURITY: A bet placed for VRF cannot be resolved by a house secret
+        self.bet.require_resolution_method(false)?;
+
+        // SECURITY: The secret must be the one committed to
+        require!(
+            hashv(&[secret]).to_bytes() == self.bet.committed_hash,
+            DiceError::RevealMismatch
+        );
+
+        // RANDOMNESS: Bind the secret to this specific bet
+        let entropy = hashv(&[secret, self.bet.key().as_ref()]).to_bytes();
+        Ok(roll_from_entropy(&entropy, self.bet.roll_range))
+    }
+
+    /// Read the revealed Switchboard On-Demand randomness committed for the bet
+    ///
+    /// # Returns
+    /// * `Result<u16>` - The roll (1-roll_range) derived from the revealed VRF value
+    ///
+    /// # Security Requirements
+    /// 1. The bet must have been placed for VRF, without a house secret commitment
+    /// 2. The randomness account (already matched against the bet) must be owned
+    ///    by the Switchboard On-Demand program and parse as randomness data
+    /// 3. Its seed slot must fall in the slot range the bet was placed in, so the
+    ///    randomness was committed before its value could be known
+    /// 4. The value must have been revealed
+    pub fn verify_vrf_randomness(&self) -> Result<u16> {
+        // SECURITY: A bet committed to a house secret can only be resolved by the reveal
+        self.bet.require_no_commitment()?;
+
+        // SECURITY: Only a bet the player placed for VRF resolves through it
+        self.bet.require_resolution_method(true)?;
+
+        // SECURITY: Only Switchboard can write valid randomness data
+        require_keys_eq!(
+            *self.randomness_account.owner,
+            SWITCHBOARD_PROGRAM_ID,
+            DiceError::FailedToParseRandomness
+        );
+
+        let randomness_data = RandomnessAccountData::parse(self.randomness_account.data.borrow())
+            .map_err(|_| DiceError::FailedToParseRandomness)?;
+
+        // SECURITY: The commitment must belong to the slot range of the bet
+        // Switchboard seeds a commitment with the slot preceding the commit transaction
+        require!(
+            (self.bet.slot.saturating_sub(1)..=self.bet.slot).contains(&randomness_data.seed_slot),
+            DiceError::RandomnessExpired
+        );
+
+        // SECURITY: The value must already be revealed
+        let clock = Clock::get()?;
+        let value = randomness_data
+            .get_value(&clock)
+            .map_err(|_| DiceError::RandomnessNotResolved)?;
+
+        // RANDOMNESS: Same roll derivation as signature entropy
+        Ok(roll_from_entropy(&value, self.bet.roll_range))
+    }
+
     /// Resolve the bet by generating a random number and paying out winners
     ///
     /// # Arguments
     /// * `bumps` - PDA bumps needed for vault signing
-    /// * `sig` - Ed25519 signature bytes used as entropy source
+    /// * `sig` - The house's Ed25519 signature over the bet message
+    /// * `entropy` - Hash of the verified Ed25519 signature(s), see `verify_ed25519_signature`
     ///
     /// # Returns
-    /// * `Result<()>` - Success or payout error
+    /// * `Result<ResolutionOutcome>` - The roll, outcome and payout, or payout error
+    ///
+    /// # Fairness Receipt
+    /// Emits a `FairnessReceipt` with the bet parameters, signature, entropy,
+    /// roll, payout and house edge, so the player can verify the outcome offline.
     ///
     /// # Randomness Generation
-    /// 1. Hash the Ed25519 signature to get 32 bytes of entropy
-    /// 2. Split into two 16-byte chunks and convert to u128 integers
-    /// 3. Add them together and take modulo 100 to get roll (1-100)
+    /// 1. Take the 32 bytes of entropy hashed from the verified signature(s)
+    /// 2. Derive an unbiased roll (1-roll_range) by rejection sampling, see `utils::roll_from_entropy`
+    pub fn resolve_bet(
+        &mut self,
+        bumps: &ResolveBetBumps,
+        sig: &[u8],
+        entropy: &[u8; 32],
+    ) -> Result<ResolutionOutcome> {
+        // RANDOMNESS: Generate provably fair random number from signature entropy
+        let roll = roll_from_entropy(entropy, self.bet.roll_range);
+
+        let outcome = self.settle_bet(bumps, roll)?;
+
+        // RECEIPT: Everything needed to recompute this resolution offline
+        emit!(FairnessReceipt {
+            bet: self.bet.key(),
+            player: self.bet.player,
+            seed: self.bet.seed,
+            amount: self.bet.amount,
+            prediction: self.bet.roll,
+            direction: self.bet.direction,
+            roll_range: self.bet.roll_range,
+            signature: sig.to_vec(),
+            authority: self.authority.key(),
+            entropy: *entropy,
+            roll,
+            payout: self.bet.payout,
+            house_edge_bp: self.bet.house_edge_bp,
+            tie_is_push: self.bet.tie_is_push,
+        });
+
+        Ok(outcome)
+    }
+
+    /// Settle the bet against a rolled value, paying out winners
+    ///
+    /// # Arguments
+    /// * `bumps` - PDA bumps needed for vault signing
+    /// * `roll` - The rolled value (1-roll_range), derived from entropy or signed by the oracle
+    ///
+    /// # Returns
+    /// * `Result<ResolutionOutcome>` - The roll, outcome and payout, or payout error
     ///
     /// # Payout Calculation
-    /// If player wins: payout = (bet_amount * (100 - house_edge)) / (roll_prediction - 1) / 100
-    /// The house edge is subtracted before calculating the odds-based payout.
-    pub fn resolve_bet(&mut self, bumps: &ResolveBetBumps, sig: &[u8]) -> Result<()> {
-        // RANDOMNESS: Generate provably fair random number from signature
-        let hash = hash(sig).to_bytes();
-
-        // Split the 32-byte hash into two 16-byte chunks
-        let mut hash_16: [u8; 16] = [0; 16];
-        hash_16.copy_from_slice(&hash[0..16]);
-        let lower = u128::from_le_bytes(hash_16);
-        hash_16.copy_from_slice(&hash[16..32]);
-        let upper = u128::from_le_bytes(hash_16);
-
-        // Combine the two halves and generate a roll from 1-100
-        let roll = lower.wrapping_add(upper).wrapping_rem(100) as u8 + 1;
-
-        // GAME LOGIC: Player wins if their prediction is HIGHER than the random roll
-        if self.bet.roll > roll {
+    /// If player wins: payout = bet_amount * (100% - house_edge) * roll_range / (roll_prediction - 1)
+    /// The house edge is the one snapshotted on the bet at placement, so a later
+    /// config change cannot alter the payout; it is subtracted before
+    /// calculating the odds-based payout.
+    /// Payouts below MIN_PAYOUT_LAMPORTS are settled per DUST_PAYOUT_POLICY.
+    /// With SCALED_PAYOUT_ACCOUNTING set, the payout is exact to 1/PAYOUT_SCALE of a
+    /// lamport and the sub-lamport remainder is banked for the player's next win.
+    /// An insured loss pays INSURANCE_COVER_BPS of the wager, capped by the insurance pool.
+    /// A win paying less than the bet's `min_acceptable_payout` refunds the stake instead.
+    /// A tie (roll equal to the prediction) loses, or returns the stake if the bet
+    /// was placed with tie_is_push.
+    /// Resolving more than SLOW_RESOLUTION_SLOTS after the commit slot adds a
+    /// SLOW_RESOLUTION_BONUS_BPS bonus on top, whatever the outcome.
+    /// Rolling JACKPOT_ROLL adds the whole jackpot pool, whatever the outcome.
+    /// A referred bet credits REFERRAL_BPS of the wager (at most the house edge)
+    /// to the referrer's earnings account.
+    /// Every bet credits the player REBATE_BPS of its house edge, claimable through `claim_rebate`.
+    /// A loss sends the config's treasury_bps of the wager to its treasury, if one is set.
+    /// The roll is counted by decile in HouseStats, published through a
+    /// RollDistribution event every ROLL_DISTRIBUTION_INTERVAL resolutions.
+    ///
+    /// # Dispute Window
+    /// With DISPUTE_WINDOW_SLOTS set, only the roll, outcome and base payout are
+    /// recorded on the bet. No funds move and no insurance, bonus, jackpot, credit
+    /// or statistic is applied until `finalize_resolution` runs after the window.
+    /// With PULL_PAYOUTS set, the payout is escrowed in the player's Winnings
+    /// account for `claim_winnings` instead of sent to the player.
+    /// With DAILY_PAYOUT_CEILING_LAMPORTS set, only the player's remaining allowance
+    /// for the window leaves the vault; the rest is owed through `claim_deferred_payout`.
+    /// With KEEP_RESOLVED_BETS set, the bet is paid out but left open with its
+    /// rolled value and outcome recorded, until the player calls `close_bet`.
+    ///
+    /// # Betting Limits
+    /// Bet amount and roll limits are not re-checked here: they apply at placement
+    /// only, so an in-flight bet still resolves after the limits change. The one
+    /// exception is a bet without winning rolls, which the payout formula cannot divide by.
+    ///
+    /// # Expiry
+    /// Resolution fails with `RandomnessExpired` once the config's
+    /// refund_timeout_slots have passed since the commit slot; from that slot on
+    /// the bet can only be refunded.
+    pub fn settle_bet(&mut self, bumps: &ResolveBetBumps, roll: u16) -> Result<ResolutionOutcome> {
+        // VALIDATION: Payouts and the bet's rent go to the player, never back into
+        // the vault or to the house; the bet seeds already make this unreachable
+        require_keys_neq!(
+            self.player.key(),
+            self.vault.key(),
+            DiceError::InvalidPlayer
+        );
+        require_keys_neq!(
+            self.player.key(),
+            self.house.key(),
+            DiceError::InvalidPlayer
+        );
+
+        // SECURITY: The vault signs with the canonical bump recorded at initialize
+        require_eq!(
+            bumps.vault,
+            self.config.vault_bump,
+            DiceError::InvalidVaultBump
+        );
+
+        // VALIDATION: Defense in depth - the payout divides by the number of winning rolls
+        self.bet.require_winnable()?;
+
+        // VALIDATION: Not in the placement slot and not past the refund timeout
+        // Otherwise the house could co-schedule placement and resolution, or hold back
+        // bets and resolve only the favorable ones
+        self.bet
+            .require_resolvable_at(Clock::get()?.slot, self.config.refund_timeout_slots)?;
+
+        // VALIDATION: The randomness account must be in the state it was in at placement
+        // A closed or reallocated account means the randomness source is no longer trustworthy
+        require!(
+            self.randomness_account.lamports() > 0
+                && self.randomness_account.data_len() as u64 == self.bet.randomness_data_len,
+            DiceError::RandomnessAccountClosed
+        );
+
+        // VALIDATION: Defensive check that the edge leaves a non-zero payout factor
+        // update_config already rejects such an edge, and the bet keeps the edge it was placed under
+        let house_edge_bp = self.bet.house_edge_bp;
+        require!(house_edge_bp < 10000, DiceError::InvalidHouseEdge);
+
+        // GAME LOGIC: Player wins if the random roll is on their side of the prediction
+        // (below it rolling under, above it rolling over)
+        // If player loses, no payout is made unless insured
+        // The bet amount stays in the vault as house profit, less any treasury cut
+        let won = self.bet.direction.wins(self.bet.roll, roll);
+
+        // TIE: A roll equal to the prediction is on neither side, so it never wins
+        // (rolling under 50, a 50 loses); placed with tie_is_push it is a push instead
+        let push = !won && self.bet.tie_is_push && roll == self.bet.roll;
+
+        // DOUBLE RESOLVE: Mark the bet resolved before any CPI (insurance, jackpot,
+        // payout, treasury), as refund_bet does
+        // Closing the bet already prevents a second resolution; the flag also covers
+        // every path that leaves the account open
+        self.bet.is_resolved = true;
+
+        let payout = if won {
             // PAYOUT CALCULATION: Calculate winnings with house edge
-            // Formula: (bet_amount * (10000 - house_edge_bp)) / (roll_prediction - 1) / 100
-            // Example: 1 SOL bet on roll 50 = (1 * 9850) / 49 / 100 = ~2.01 SOL payout
-            let payout = (self.bet.amount as u128)
-                .checked_mul(10000 - HOUSE_EDGE as u128)
-                .ok_or(DiceError::Overflow)? // Apply house edge
-                .checked_div(self.bet.roll as u128 - 1)
-                .ok_or(DiceError::Overflow)? // Odds-based multiplier
-                .checked_div(100)
-                .ok_or(DiceError::Overflow)? as u64; // Convert basis points
+            // SCALED: Computed exactly in PAYOUT_SCALE units with the player's banked
+            // remainder added; whole lamports are paid and the rest stays banked
+            let (payout, remainder) = if SCALED_PAYOUT_ACCOUNTING {
+                let scaled = calculate_payout_scaled(
+                    self.bet.amount,
+                    self.bet.roll,
+                    self.bet.roll_range,
+                    self.bet.direction,
+                    house_edge_bp,
+                    PAYOUT_SCALE,
+                )?;
+                split_scaled_payout(scaled, self.player_stats.payout_remainder, PAYOUT_SCALE)?
+            } else {
+                let payout = calculate_payout(
+                    self.bet.amount,
+                    self.bet.roll,
+                    self.bet.roll_range,
+                    self.bet.direction,
+                    house_edge_bp,
+                    self.config.payout_rounding,
+                )?;
+                (payout, self.player_stats.payout_remainder)
+            };
+
+            // MINIMUM ACCEPTABLE: If the payout fell below what the player accepted at
+            // placement (e.g. the edge was raised since), refund the stake instead
+            // DUST: Never pay a winner less than MIN_PAYOUT_LAMPORTS
+            // The shortfall is either covered by the vault or the stake is returned
+            // The banked remainder only changes when the computed payout is paid
+            if payout < self.bet.min_acceptable_payout {
+                self.bet.amount
+            } else if payout < MIN_PAYOUT_LAMPORTS {
+                DUST_PAYOUT_POLICY.settle(payout, self.bet.amount)
+            } else {
+                self.player_stats.payout_remainder = remainder;
+                payout
+            }
+        } else if push {
+            // PUSH: The tie returns the stake, neither a win nor a loss
+            self.bet.amount
+        } else {
+            0
+        };
+
+        // TREASURY: A loss routes the configured share of the wager out of the vault
+        let treasury_cut = if won || push {
+            0
+        } else {
+            self.config.treasury_cut(self.bet.amount)
+        };
+
+        // DISPUTE WINDOW: Record the outcome and defer settlement to finalize_resolution
+        // The bet stays open so the player can re-verify the resolution. Insurance, the
+        // latency bonus, the jackpot, referral and rebate credits and the statistics are
+        // all applied at finalization, so nothing moves or accrues before the window passes
+        if DISPUTE_WINDOW_SLOTS != 0 {
+            // SOLVENCY: The vault must cover the base payout and treasury cut recorded now
+            require!(
+                payout
+                    .checked_add(treasury_cut)
+                    .ok_or(DiceError::Overflow)?
+                    <= vault_available_lamports(&self.vault.to_account_info())?,
+                DiceError::InsufficientFunds
+            );
+
+            self.bet.rolled_value = roll;
+            self.bet.won = won;
+            self.bet.payout = payout;
+            self.bet.resolved_slot = Clock::get()?.slot;
+            return Ok(ResolutionOutcome { roll, won, payout });
+        }
+
+        // INSURANCE: An insured loss returns a fraction of the wager
+        // The pool reimburses the vault, which then pays out like a win
+        let payout = if !won && !push && self.bet.insured {
+            let insurance_pool = self
+                .insurance_pool
+                .as_ref()
+                .ok_or(DiceError::PoolAccountRequired)?;
+            // Never draw the pool below its rent-exempt minimum
+            let cover = insurance_cover(
+                self.bet.amount,
+                insurance_pool.lamports(),
+                Rent::get()?.minimum_balance(0),
+            )?;
 
+            if cover > 0 {
+                let accounts = Transfer {
+                    from: insurance_pool.to_account_info(),
+                    to: self.vault.to_account_info(),
+                };
+
+                let bump = bumps.insurance_pool.ok_or(DiceError::PoolAccountRequired)?;
+                let house_key = self.house.key();
+                let seeds = [b"insurance", house_key.as_ref(), &[bump]];
+                let signer_seeds = &[&seeds[..]][..];
+
+                let ctx = CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    accounts,
+                    signer_seeds,
+                );
+                transfer(ctx, cover)?;
+            }
+
+            cover
+        } else {
+            payout
+        };
+
+        // LATENCY: Compensate the player from the vault when resolution was slow
+        // Latency is measured from the commit slot to the resolving slot
+        let latency = Clock::get()?.slot.saturating_sub(self.bet.commit_slot);
+        let bonus = slow_resolution_bonus(
+            self.bet.amount,
+            latency,
+            SLOW_RESOLUTION_SLOTS,
+            SLOW_RESOLUTION_BONUS_BPS,
+        )?;
+        let payout = payout.checked_add(bonus).ok_or(DiceError::Overflow)?;
+
+        // JACKPOT: Rolling JACKPOT_ROLL wins the whole pool, whatever the bet's outcome
+        // Like insurance, the pool moves into the vault, which then pays it out
+        // An empty jackpot (always, while JACKPOT_BPS is 0) needs no pool account
+        let jackpot = if self.stats.hits_jackpot(roll) {
+            let jackpot_pool = self
+                .jackpot_pool
+                .as_ref()
+                .ok_or(DiceError::PoolAccountRequired)?;
+
+            // Never draw the pool below its rent-exempt minimum
+            let available = jackpot_pool
+                .lamports()
+                .saturating_sub(Rent::get()?.minimum_balance(0));
+            self.stats.take_jackpot(available)
+        } else {
+            0
+        };
+
+        let payout = if jackpot > 0 {
+            let jackpot_pool = self
+                .jackpot_pool
+                .as_ref()
+                .ok_or(DiceError::PoolAccountRequired)?;
+            let accounts = Transfer {
+                from: jackpot_pool.to_account_info(),
+                to: self.vault.to_account_info(),
+            };
+
+            let bump = bumps.jackpot_pool.ok_or(DiceError::PoolAccountRequired)?;
+            let house_key = self.house.key();
+            let seeds = [b"jackpot", house_key.as_ref(), &[bump]];
+            let signer_seeds = &[&seeds[..]][..];
+
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                signer_seeds,
+            );
+            transfer(ctx, jackpot)?;
+
+            emit!(JackpotWon {
+                bet: self.bet.key(),
+                player: self.bet.player,
+                amount: jackpot,
+            });
+
+            payout.checked_add(jackpot).ok_or(DiceError::Overflow)?
+        } else {
+            payout
+        };
+
+        // LOG: The outcome, before the solvency check that could still fail it
+        #[cfg(feature = "verbose-logs")]
+        msg!("resolve_bet: roll={} won={} payout={}", roll, won, payout);
+
+        // SOLVENCY: The vault must cover the payout and treasury cut without dropping
+        // below its balance floor
+        // Checked before any transfer so an underfunded vault fails cleanly
+        require!(
+            payout
+                .checked_add(treasury_cut)
+                .ok_or(DiceError::Overflow)?
+                <= vault_available_lamports(&self.vault.to_account_info())?,
+            DiceError::InsufficientFunds
+        );
+
+        // EVENT: Publish the rolled value and outcome before the bet account closes
+        // RENT: Only returned now if the bet is closed by this instruction
+        let rent_refunded = if KEEP_RESOLVED_BETS {
+            0
+        } else {
+            self.bet.to_account_info().lamports()
+        };
+
+        emit!(BetResolved {
+            bet: self.bet.key(),
+            player: self.bet.player,
+            seed: self.bet.seed,
+            prediction: self.bet.roll,
+            roll,
+            payout,
+            won,
+            rent_refunded,
+        });
+
+        // PLAYER STATS: Winnings and streak; a loss resets the streak
+        self.player_stats.record_outcome(won, push, payout)?;
+
+        // REFERRAL: Credit the referrer from the house's share, capped at the edge
+        // The reward stays in the vault until the referrer claims it
+        if let Some(referrer) = self.bet.referrer {
+            let referral = self.referral.as_mut().ok_or(DiceError::InvalidReferral)?;
+            require!(
+                referral.referrer == referrer && referral.house == self.house.key(),
+                DiceError::InvalidReferral
+            );
+
+            let reward = (self.bet.amount as u128)
+                .checked_mul(REFERRAL_BPS.min(house_edge_bp) as u128)
+                .ok_or(DiceError::Overflow)?
+                .checked_div(10000)
+                .ok_or(DiceError::Overflow)? as u64;
+            referral.unclaimed = referral
+                .unclaimed
+                .checked_add(reward)
+                .ok_or(DiceError::Overflow)?;
+            referral.total_earned = referral
+                .total_earned
+                .checked_add(reward)
+                .ok_or(DiceError::Overflow)?;
+            self.stats.referral_credits = self
+                .stats
+                .referral_credits
+                .checked_add(reward)
+                .ok_or(DiceError::Overflow)?;
+        }
+
+        // REBATE: Credit the player their share of the house edge on the bet
+        // The rebate stays in the vault until the player claims it
+        let rebate = edge_rebate(self.bet.amount, house_edge_bp, REBATE_BPS);
+        if rebate > 0 {
+            self.player_stats.rebate_unclaimed = self
+                .player_stats
+                .rebate_unclaimed
+                .checked_add(rebate)
+                .ok_or(DiceError::Overflow)?;
+            self.stats.rebate_pool = self
+                .stats
+                .rebate_pool
+                .checked_add(rebate)
+                .ok_or(DiceError::Overflow)?;
+        }
+
+        // CIRCUIT BREAKER: Pause new bets for review once lifetime payouts reach the cap
+        // This bet still settles; only new bets are rejected
+        let tripped = self.stats.crosses_payout_cap(payout, LIFETIME_PAYOUT_CAP);
+
+        // STATS: Count the outcome and the amount paid out
+        self.stats.record_outcome(won, push, payout)?;
+        if tripped {
+            self.config.is_paused = true;
+            emit!(PayoutCapReached {
+                house: self.house.key(),
+                total_payout: self.stats.total_payout,
+                cap: LIFETIME_PAYOUT_CAP,
+            });
+        }
+
+        // DISTRIBUTION: Count the roll by decile, publishing the counts when due
+        if self.stats.record_roll(roll, self.bet.roll_range) {
+            let divergence_bps = decile_divergence_bps(&self.stats.roll_deciles);
+            emit!(RollDistribution {
+                house: self.house.key(),
+                deciles: self.stats.roll_deciles,
+                divergence_bps,
+                diverged: divergence_bps > MAX_ROLL_DIVERGENCE_BPS,
+            });
+        }
+
+        // RECORD: Keep the outcome on the bet for any mode that leaves it open
+        self.bet.rolled_value = roll;
+        self.bet.won = won;
+        self.bet.payout = payout;
+
+        // OUTCOME: Returned to the submitter through the transaction return data
+        let outcome = ResolutionOutcome { roll, won, payout };
+
+        // TRACKER: The bet is settled and no longer open
+        // Bets waiting out the dispute window stay counted until finalize_resolution
+        self.tracker.open_bets = self.tracker.open_bets.saturating_sub(1);
+        self.tracker.open_stakes = self.tracker.open_stakes.saturating_sub(self.bet.amount);
+        self.player_stats.open_bets = self.player_stats.open_bets.saturating_sub(1);
+
+        // CEILING: Pay out at most the player's remaining allowance for the window
+        // The rest stays in the vault, owed to the player through claim_deferred_payout
+        let paid_now = self.player_stats.take_payout_allowance(
+            payout,
+            Clock::get()?.slot,
+            DAILY_PAYOUT_CEILING_LAMPORTS,
+        );
+        let deferred = payout - paid_now;
+        if deferred > 0 {
+            self.player_stats.deferred_payout = self
+                .player_stats
+                .deferred_payout
+                .checked_add(deferred)
+                .ok_or(DiceError::Overflow)?;
+            self.stats.deferred_payouts = self
+                .stats
+                .deferred_payouts
+                .checked_add(deferred)
+                .ok_or(DiceError::Overflow)?;
+        }
+
+        if paid_now > 0 {
             // TRANSFER: Pay the winner from the house vault
+            // In pull mode the payout goes into the player's escrow instead
+            let recipient = if PULL_PAYOUTS {
+                let winnings = self
+                    .winnings
+                    .as_mut()
+                    .ok_or(DiceError::InvalidWinningsAccount)?;
+                require_keys_eq!(
+                    winnings.player,
+                    self.player.key(),
+                    DiceError::InvalidWinningsAccount
+                );
+                winnings.credit(paid_now)?;
+                winnings.to_account_info()
+            } else {
+                self.player.to_account_info()
+            };
+
             let accounts = Transfer {
                 from: self.vault.to_account_info(),
-                to: self.player.to_account_info(),
+                to: recipient,
             };
 
             // Create PDA signer seeds for the vault
@@ -190,11 +952,50 @@ impl<'info> ResolveBet<'info> {
                 accounts,
                 signer_seeds,
             );
-            transfer(ctx, payout)?;
+            transfer(ctx, paid_now)?;
         }
-        // If player loses (roll >= bet.roll), no payout is made
-        // The bet amount stays in the vault as house profit
 
-        Ok(())
+        if treasury_cut > 0 {
+            // TRANSFER: Send the treasury its cut of the lost wager
+            let treasury = self.treasury.as_ref().ok_or(DiceError::InvalidTreasury)?;
+            require!(
+                self.config.treasury == Some(treasury.key()),
+                DiceError::InvalidTreasury
+            );
+            require_not_vault(&treasury.key(), &self.vault.key())?;
+
+            let accounts = Transfer {
+                from: self.vault.to_account_info(),
+                to: treasury.to_account_info(),
+            };
+
+            let house_key = self.house.key();
+            let seeds = [b"vault", house_key.as_ref(), &[bumps.vault]];
+            let signer_seeds = &[&seeds[..]][..];
+
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                signer_seeds,
+            );
+            transfer(ctx, treasury_cut)?;
+
+            emit!(TreasuryPaid {
+                house: self.house.key(),
+                bet: self.bet.key(),
+                treasury: treasury.key(),
+                amount: treasury_cut,
+            });
+        }
+
+        // KEEP: Leave the settled bet open for inspection; the player closes it with close_bet
+        if KEEP_RESOLVED_BETS {
+            return Ok(outcome);
+        }
+
+        // CLOSE: Settle immediately and return the bet rent to the player
+        self.bet.close(self.player.to_account_info())?;
+
+        Ok(outcome)
     }
 }
@@ -0,0 +1,396 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::{
+    error::DiceError,
+    events::{BetResolved, FairnessReceipt, PayoutCapReached, RollDistribution, TreasuryPaid},
+    state::{Bet, BetTracker, GameConfig, HouseStats, PlayerStats, ResolutionOutcome},
+    utils::{
+        bet_message, calculate_payout, decile_divergence_bps, require_not_vault, roll_from_entropy,
+        vault_state_hash, verify_house_signature,
+    },
+    BIND_VAULT_STATE, LIFETIME_PAYOUT_CAP, MAX_ROLL_DIVERGENCE_BPS, REQUIRE_PLAYER_SIGNATURE,
+};
+
+/// Resolve Bet Token Instruction - Resolves a token bet using an Ed25519 signature
+///
+/// The token counterpart of resolve_bet. Randomness and payout math are shared
+/// with the native SOL path; winnings are paid from the vault token account
+/// with the vault PDA signing. Token bets always settle immediately and close.
+#[derive(Accounts)]
+pub struct ResolveBetToken<'info> {
+    /// House authority (unchecked for efficiency)
+    /// Used only for PDA seed derivation
+    ///CHECK: This check is safe - the config constraint ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The primary authority or one of the additional authorities
+    /// Provides the Ed25519 signature for randomness and must sign this transaction
+    pub authority: Signer<'info>,
+
+    /// Player who placed the bet (unchecked for efficiency)
+    /// Receives the bet account rent; the bet seeds enforce the original player
+    #[account(mut)]
+    ///CHECK: This is safe
+    pub player: UncheckedAccount<'info>,
+
+    /// The player's token account receiving any payout
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = player
+    )]
+    pub player_token_account: Account<'info, TokenAccount>,
+
+    /// The game config providing the payout rounding, refund timeout and mint
+    /// Mutable so the lifetime payout circuit breaker can pause the game
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        has_one = mint @ DiceError::InvalidMint,
+        constraint = config.is_authority(&authority.key()) @ DiceError::UnauthorizedAuthority
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The tracker counting the game's open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// Aggregate statistics for the game
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"stats", house.key().as_ref()],
+        bump = stats.bump
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// The player's betting statistics
+    ///
+    /// Seeds: ["player_stats", house_pubkey, player_pubkey]
+    #[account(
+        mut,
+        seeds = [b"player_stats", house.key().as_ref(), player.key().as_ref()],
+        bump = player_stats.bump
+    )]
+    pub player_stats: Account<'info, PlayerStats>,
+
+    /// Mint of the token the game is played in
+    pub mint: Account<'info, Mint>,
+
+    /// The house vault PDA, signing authority of the vault token account
+    ///
+    /// Seeds: ["vault", house_pubkey]
+    /// CHECK: Only the address and bump are used; the seeds guarantee it is the program's PDA
+    #[account(
+        seeds = [b"vault", house.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// Vault token account paying out winning bets
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vault
+    )]
+    pub vault_token_account: Account<'info, TokenAccount>,
+
+    /// Token account receiving the treasury's share of a lost wager
+    /// Required only when the config names a treasury; must be owned by it
+    /// Never the vault token account, which would pay the cut to itself
+    #[account(
+        mut,
+        constraint = treasury_token_account.key() != vault_token_account.key() @ DiceError::VaultAsRecipient
+    )]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// The token bet to be resolved
+    /// Closed and rent returned to the player after resolution
+    /// A bet already refunded through refund_bet_token fails with `BetAlreadyResolved`
+    #[account(
+        mut,
+        close = player,
+        seeds = [b"bet", bet.player.as_ref(), bet.seed.to_le_bytes().as_ref()],
+        bump = bet.bump,
+        constraint = bet.player == player.key() @ DiceError::NotPlayerBet,
+        constraint = bet.house == house.key() @ DiceError::WrongHouse,
+        constraint = bet.mint == mint.key() @ DiceError::InvalidMint,
+        constraint = !bet.is_resolved @ DiceError::BetAlreadyResolved
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// Randomness account linked to the bet at placement
+    #[account(address = bet.randomness_account @ DiceError::RandomnessMismatch)]
+    /// CHECK: The address is matched against the bet; liveness is checked in the handler
+    pub randomness_account: AccountInfo<'info>,
+
+    /// Instruction sysvar account containing Ed25519 signature data
+    #[account(
+        address = solana_program::sysvar::instructions::ID @ DiceError::InvalidInstructionSysvar
+    )]
+    /// CHECK: This is safe
+    pub instruction_sysvar: AccountInfo<'info>,
+
+    /// Token program used for the payout transfer
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ResolveBetToken<'info> {
+    /// Verify the house signature, roll the dice and pay out a winning token bet
+    ///
+    /// # Arguments
+    /// * `bumps` - PDA bumps needed for vault signing
+    /// * `sig` - The house's Ed25519 signature over the bet message
+    ///
+    /// # Returns
+    /// * `Result<ResolutionOutcome>` - The roll, outcome and payout, or validation/payout error
+    ///
+    /// # Differences from resolve_bet
+    /// - Payouts are in the mint's base units, so the lamport dust threshold does not apply
+    /// - Insurance, dispute windows, scaled payout accounting and KEEP_RESOLVED_BETS
+    ///   are native SOL only
+    /// - A loss's treasury cut goes to a token account owned by the config's treasury
+    ///
+    /// Emits the same `FairnessReceipt` as resolve_bet, with the payout in base units.
+    /// Like resolve_bet, fails with `RandomnessExpired` once the bet is refundable.
+    pub fn resolve_bet_token(
+        &mut self,
+        bumps: &ResolveBetTokenBumps,
+        sig: &[u8],
+    ) -> Result<ResolutionOutcome> {
+        // SECURITY: The vault signs with the canonical bump recorded at initialize
+        require_eq!(
+            bumps.vault,
+            self.config.vault_bump,
+            DiceError::InvalidVaultBump
+        );
+
+        // VALIDATION: Defense in depth - the payout divides by the number of winning rolls
+        self.bet.require_winnable()?;
+
+        // VALIDATION: Not in the placement slot and not past the refund timeout
+        // Otherwise the house could co-schedule placement and resolution, or hold back
+        // bets and resolve only the favorable ones
+        self.bet
+            .require_resolvable_at(Clock::get()?.slot, self.config.refund_timeout_slots)?;
+
+        if REQUIRE_PLAYER_SIGNATURE {
+            require!(self.player.is_signer, DiceError::PlayerSignatureRequired);
+        }
+
+        // SECURITY: Same Ed25519 verification and signable message as native SOL bets,
+        // with the vault token balance standing in for the vault lamports
+        let mut message = bet_message(&self.bet.key(), &self.bet)?;
+        if BIND_VAULT_STATE {
+            message.extend_from_slice(&vault_state_hash(
+                self.vault_token_account.amount,
+                self.tracker.open_bets,
+            ));
+        }
+        let entropy = verify_house_signature(
+            &self.instruction_sysvar.to_account_info(),
+            &self.authority.key(),
+            &message,
+            sig,
+            1,
+        )?;
+
+        // VALIDATION: The randomness account must be in the state it was in at placement
+        require!(
+            self.randomness_account.lamports() > 0
+                && self.randomness_account.data_len() as u64 == self.bet.randomness_data_len,
+            DiceError::RandomnessAccountClosed
+        );
+
+        // RANDOMNESS: Generate provably fair random number from signature entropy
+        let roll = roll_from_entropy(&entropy, self.bet.roll_range);
+
+        // GAME LOGIC: Player wins if the random roll is on their side of the prediction
+        let won = self.bet.direction.wins(self.bet.roll, roll);
+
+        // TIE: A roll equal to the prediction never wins; placed with tie_is_push it is a push
+        let push = !won && self.bet.tie_is_push && roll == self.bet.roll;
+
+        let payout = if won {
+            let payout = calculate_payout(
+                self.bet.amount,
+                self.bet.roll,
+                self.bet.roll_range,
+                self.bet.direction,
+                self.bet.house_edge_bp,
+                self.config.payout_rounding,
+            )?;
+
+            // MINIMUM ACCEPTABLE: Refund the stake rather than pay less than accepted
+            if payout < self.bet.min_acceptable_payout {
+                self.bet.amount
+            } else {
+                payout
+            }
+        } else if push {
+            // PUSH: The tie returns the stake, neither a win nor a loss
+            self.bet.amount
+        } else {
+            0
+        };
+
+        // TREASURY: A loss routes the configured share of the wager out of the vault
+        let treasury_cut = if won || push {
+            0
+        } else {
+            self.config.treasury_cut(self.bet.amount)
+        };
+
+        // SOLVENCY: The vault token account must cover the payout and treasury cut
+        require!(
+            payout
+                .checked_add(treasury_cut)
+                .ok_or(DiceError::Overflow)?
+                <= self.vault_token_account.amount,
+            DiceError::InsufficientFunds
+        );
+
+        emit!(BetResolved {
+            bet: self.bet.key(),
+            player: self.bet.player,
+            seed: self.bet.seed,
+            prediction: self.bet.roll,
+            roll,
+            payout,
+            won,
+            rent_refunded: self.bet.to_account_info().lamports(),
+        });
+
+        // RECEIPT: Everything needed to recompute this resolution offline
+        emit!(FairnessReceipt {
+            bet: self.bet.key(),
+            player: self.bet.player,
+            seed: self.bet.seed,
+            amount: self.bet.amount,
+            prediction: self.bet.roll,
+            direction: self.bet.direction,
+            roll_range: self.bet.roll_range,
+            signature: sig.to_vec(),
+            authority: self.authority.key(),
+            entropy,
+            roll,
+            payout,
+            house_edge_bp: self.bet.house_edge_bp,
+            tie_is_push: self.bet.tie_is_push,
+        });
+
+        // DOUBLE RESOLVE: Mark the bet resolved before any payout CPI, as refund_bet_token does
+        // The account is closed at the end of the instruction regardless
+        self.bet.is_resolved = true;
+
+        // TRACKER: The bet is no longer open
+        self.tracker.open_bets = self.tracker.open_bets.saturating_sub(1);
+        self.tracker.open_stakes = self.tracker.open_stakes.saturating_sub(self.bet.amount);
+        self.player_stats.open_bets = self.player_stats.open_bets.saturating_sub(1);
+
+        // PLAYER STATS: Winnings and streak; a loss resets the streak
+        self.player_stats.record_outcome(won, push, payout)?;
+
+        // CIRCUIT BREAKER: Pause new bets for review once lifetime payouts reach the cap
+        // This bet still settles; only new bets are rejected
+        let tripped = self.stats.crosses_payout_cap(payout, LIFETIME_PAYOUT_CAP);
+
+        // STATS: Count the outcome and the amount paid out
+        self.stats.record_outcome(won, push, payout)?;
+        if tripped {
+            self.config.is_paused = true;
+            emit!(PayoutCapReached {
+                house: self.house.key(),
+                total_payout: self.stats.total_payout,
+                cap: LIFETIME_PAYOUT_CAP,
+            });
+        }
+
+        // DISTRIBUTION: Count the roll by decile, publishing the counts when due
+        if self.stats.record_roll(roll, self.bet.roll_range) {
+            let divergence_bps = decile_divergence_bps(&self.stats.roll_deciles);
+            emit!(RollDistribution {
+                house: self.house.key(),
+                deciles: self.stats.roll_deciles,
+                divergence_bps,
+                diverged: divergence_bps > MAX_ROLL_DIVERGENCE_BPS,
+            });
+        }
+
+        if payout > 0 {
+            // TRANSFER: Pay the winner from the vault token account
+            let accounts = TransferChecked {
+                from: self.vault_token_account.to_account_info(),
+                mint: self.mint.to_account_info(),
+                to: self.player_token_account.to_account_info(),
+                authority: self.vault.to_account_info(),
+            };
+
+            let house_key = self.house.key();
+            let seeds = [b"vault", house_key.as_ref(), &[bumps.vault]];
+            let signer_seeds = &[&seeds[..]][..];
+
+            let ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                accounts,
+                signer_seeds,
+            );
+            transfer_checked(ctx, payout, self.mint.decimals)?;
+        }
+
+        if treasury_cut > 0 {
+            // TRANSFER: Send the treasury its cut of the lost wager
+            let treasury_token_account = self
+                .treasury_token_account
+                .as_ref()
+                .ok_or(DiceError::InvalidTreasury)?;
+            require!(
+                self.config.treasury == Some(treasury_token_account.owner)
+                    && treasury_token_account.mint == self.mint.key(),
+                DiceError::InvalidTreasury
+            );
+            require_not_vault(
+                &treasury_token_account.key(),
+                &self.vault_token_account.key(),
+            )?;
+
+            let accounts = TransferChecked {
+                from: self.vault_token_account.to_account_info(),
+                mint: self.mint.to_account_info(),
+                to: treasury_token_account.to_account_info(),
+                authority: self.vault.to_account_info(),
+            };
+
+            let house_key = self.house.key();
+            let seeds = [b"vault", house_key.as_ref(), &[bumps.vault]];
+            let signer_seeds = &[&seeds[..]][..];
+
+            let ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                accounts,
+                signer_seeds,
+            );
+            transfer_checked(ctx, treasury_cut, self.mint.decimals)?;
+
+            emit!(TreasuryPaid {
+                house: self.house.key(),
+                bet: self.bet.key(),
+                treasury: treasury_token_account.key(),
+                amount: treasury_cut,
+            });
+        }
+
+        Ok(ResolutionOutcome { roll, won, payout })
+    }
+}
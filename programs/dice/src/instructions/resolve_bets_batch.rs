@@ -0,0 +1,265 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DiceError,
+    state::{Bet, BetTracker, GameConfig, HouseStats, PlayerStats},
+    utils::vault_state_hash,
+    ResolveBet, ResolveBetBumps, MAX_BATCH_RESOLVE, REQUIRE_PLAYER_SIGNATURE,
+};
+
+/// Resolve Bets Batch Instruction - Resolves several native SOL bets in one transaction
+///
+/// Each bet is resolved exactly as by resolve_bet, including its own Ed25519
+/// verification. The shared game accounts are passed once; the per-bet
+/// accounts come in `remaining_accounts` as groups of four:
+/// [bet, player, player_stats, randomness_account]. Referred bets, and every
+/// winning bet under PULL_PAYOUTS, need extra accounts and must be resolved
+/// individually.
+#[derive(Accounts)]
+pub struct ResolveBetsBatch<'info> {
+    /// House authority (unchecked for efficiency)
+    /// Used only for PDA seed derivation
+    ///CHECK: This check is safe - the config constraint ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The primary authority or one of the additional authorities
+    /// Provides the Ed25519 signatures for randomness and must sign this transaction
+    pub authority: Signer<'info>,
+
+    /// House vault containing funds for payouts
+    /// Must hold no data - a system-owned vault is never allocated by the program
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref()],
+        bump,
+        constraint = vault.data_is_empty() @ DiceError::VaultHasData
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// The game config providing the payout rounding and refund timeout
+    /// Mutable so the lifetime payout circuit breaker can pause the game
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        constraint = config.is_authority(&authority.key()) @ DiceError::UnauthorizedAuthority
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The tracker counting the game's open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// Aggregate statistics for the game
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"stats", house.key().as_ref()],
+        bump = stats.bump
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// Insurance pool funding the cover on insured losses
+    /// Required only when the batch holds an insured bet
+    ///
+    /// Seeds: ["insurance", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"insurance", house.key().as_ref()],
+        bump
+    )]
+    pub insurance_pool: Option<SystemAccount<'info>>,
+
+    /// Jackpot pool funded by a JACKPOT_BPS share of every native SOL stake
+    /// Required only when the jackpot holds funds, so never while JACKPOT_BPS is 0
+    ///
+    /// Seeds: ["jackpot", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"jackpot", house.key().as_ref()],
+        bump
+    )]
+    pub jackpot_pool: Option<SystemAccount<'info>>,
+
+    /// Treasury receiving its share of every lost wager in the batch
+    /// Required only when the config names a treasury; must be that account
+    #[account(mut)]
+    /// CHECK: Only receives lamports; resolution matches it against the config
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// Instruction sysvar account containing the Ed25519 signature data
+    #[account(
+        address = solana_program::sysvar::instructions::ID @ DiceError::InvalidInstructionSysvar
+    )]
+    /// CHECK: This is safe
+    pub instruction_sysvar: AccountInfo<'info>,
+
+    /// System program required for transferring payouts
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ResolveBetsBatch<'info> {
+    /// Resolve every bet in `remaining_accounts` with its matching signature
+    ///
+    /// # Arguments
+    /// * `bumps` - PDA bumps needed for vault, insurance and jackpot pool signing
+    /// * `remaining_accounts` - Groups of [bet, player, player_stats, randomness_account]
+    /// * `sigs` - One house signature per bet, in the same order
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, or the first error; a failing bet reverts the whole batch
+    ///
+    /// # Instruction Ordering
+    /// For N bets, the N instructions directly before this one must be the
+    /// Ed25519 instructions for bets 1..N in order, so bet i is verified
+    /// against the instruction N - i + 1 positions back.
+    ///
+    /// # Vault State Binding
+    /// Each payout changes the vault balance and open bet count, so under
+    /// BIND_VAULT_STATE every signature binds the vault state from before the
+    /// batch rather than the state left by the bets resolved ahead of it.
+    ///
+    /// # Batch Limit
+    /// At most MAX_BATCH_RESOLVE bets: each one needs its own Ed25519 instruction
+    /// and four accounts, which fills the transaction size limit well before
+    /// the compute budget runs out. A full batch needs a versioned transaction
+    /// loading the accounts from an address lookup table.
+    pub fn resolve_bets_batch(
+        &mut self,
+        bumps: &ResolveBetsBatchBumps,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        sigs: &[Vec<u8>],
+    ) -> Result<()> {
+        // VALIDATION: A non-empty batch within the limit, with four accounts per bet
+        let count = sigs.len();
+        require!(
+            count != 0 && count <= MAX_BATCH_RESOLVE,
+            DiceError::InvalidBatchSize
+        );
+        require_eq!(
+            remaining_accounts.len(),
+            count * 4,
+            DiceError::InvalidBatchSize
+        );
+
+        // SNAPSHOT: The vault state every signature in the batch is bound to
+        let vault_state = vault_state_hash(self.vault.lamports(), self.tracker.open_bets);
+
+        for (i, (accounts, sig)) in remaining_accounts.chunks_exact(4).zip(sigs).enumerate() {
+            let mut single = self.load_bet(accounts)?;
+
+            if REQUIRE_PLAYER_SIGNATURE {
+                single.verify_player_signature()?;
+            }
+
+            // SECURITY: Each bet is verified against its own Ed25519 instruction
+            let distance = (count - i) as u16;
+            let entropy = single.verify_ed25519_signature_at(sig, distance, &vault_state)?;
+
+            let single_bumps = ResolveBetBumps {
+                vault: bumps.vault,
+                insurance_pool: bumps.insurance_pool,
+                jackpot_pool: bumps.jackpot_pool,
+            };
+            single.resolve_bet(&single_bumps, sig, &entropy)?;
+
+            // PERSIST: Write back the per-bet accounts and carry the shared state forward
+            single.bet.exit(&crate::ID)?;
+            single.player_stats.exit(&crate::ID)?;
+            self.tracker.set_inner(single.tracker.into_inner());
+            self.stats.set_inner(single.stats.into_inner());
+            self.config.set_inner(single.config.into_inner());
+        }
+
+        Ok(())
+    }
+
+    /// Validate one group of per-bet accounts and assemble a single resolution
+    ///
+    /// # Arguments
+    /// * `accounts` - [bet, player, player_stats, randomness_account]
+    ///
+    /// # Returns
+    /// * `Result<ResolveBet>` - The accounts resolve_bet would receive for this bet
+    ///
+    /// # Security Requirements
+    /// Applies the same checks as the ResolveBet account constraints: bet and
+    /// player stats PDAs, stored player, native SOL and still pending, committed
+    /// randomness account.
+    fn load_bet(&self, accounts: &'info [AccountInfo<'info>]) -> Result<ResolveBet<'info>> {
+        let (bet_info, player_info, player_stats_info, randomness_info) =
+            (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+
+        // SECURITY: Bet must be this program's PDA for the given player
+        let bet: Account<'info, Bet> = Account::try_from(bet_info)?;
+        let bet_address = Pubkey::create_program_address(
+            &[
+                b"bet",
+                player_info.key.as_ref(),
+                bet.seed.to_le_bytes().as_ref(),
+                &[bet.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| DiceError::NotPlayerBet)?;
+        require_keys_eq!(bet_address, bet_info.key(), DiceError::NotPlayerBet);
+        require_keys_eq!(bet.player, player_info.key(), DiceError::NotPlayerBet);
+        require_keys_eq!(bet.house, self.house.key(), DiceError::WrongHouse);
+        require!(bet.mint == Pubkey::default(), DiceError::InvalidMint);
+        require!(!bet.is_resolved, DiceError::BetAlreadyResolved);
+
+        // SECURITY: Player stats must be the player's PDA
+        let player_stats: Account<'info, PlayerStats> = Account::try_from(player_stats_info)?;
+        let player_stats_address = Pubkey::create_program_address(
+            &[
+                b"player_stats",
+                self.house.key.as_ref(),
+                player_info.key.as_ref(),
+                &[player_stats.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| DiceError::NotPlayerBet)?;
+        require_keys_eq!(
+            player_stats_address,
+            player_stats_info.key(),
+            DiceError::NotPlayerBet
+        );
+
+        // SECURITY: The randomness account must be the one committed at placement
+        require_keys_eq!(
+            randomness_info.key(),
+            bet.randomness_account,
+            DiceError::RandomnessMismatch
+        );
+
+        Ok(ResolveBet {
+            house: self.house.clone(),
+            authority: self.authority.clone(),
+            player: UncheckedAccount::try_from(player_info),
+            vault: self.vault.clone(),
+            config: self.config.clone(),
+            tracker: self.tracker.clone(),
+            stats: self.stats.clone(),
+            player_stats,
+            insurance_pool: self.insurance_pool.clone(),
+            jackpot_pool: self.jackpot_pool.clone(),
+            bet,
+            randomness_account: randomness_info.clone(),
+            referral: None,
+            winnings: None,
+            treasury: self.treasury.clone(),
+            instruction_sysvar: self.instruction_sysvar.clone(),
+            system_program: self.system_program.clone(),
+        })
+    }
+}
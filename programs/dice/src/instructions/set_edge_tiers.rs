@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::DiceError, EdgeTier, GameConfig, MAX_EDGE_TIERS, MAX_HOUSE_EDGE_BP};
+
+/// Set Edge Tiers Instruction - Lets the house give larger bets a lower edge
+///
+/// Each tier applies its own house edge to stakes of at least its minimum
+/// amount; smaller stakes keep the config's base house_edge_bp. Like the base
+/// edge, the tier edge is snapshotted on each bet at placement.
+#[derive(Accounts)]
+pub struct SetEdgeTiers<'info> {
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for config PDA seed derivation
+    ///CHECK: This check is safe - the config's has_one ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The game's primary authority
+    /// Must sign the transaction
+    pub authority: Signer<'info>,
+
+    /// The game config holding the tier schedule
+    ///
+    /// Seeds: ["config", house_pubkey]
+    /// - Only the primary authority can change it
+    #[account(
+        mut,
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, GameConfig>,
+}
+
+impl<'info> SetEdgeTiers<'info> {
+    /// Replace the edge tier schedule
+    ///
+    /// # Arguments
+    /// * `edge_tiers` - Tiers in strictly increasing `min_amount` order, at most
+    ///   MAX_EDGE_TIERS; an empty schedule applies the base edge to every bet
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or `InvalidEdgeTiers`
+    pub fn set_edge_tiers(&mut self, edge_tiers: Vec<EdgeTier>) -> Result<()> {
+        // VALIDATION: The schedule must fit the config account
+        require!(
+            edge_tiers.len() <= MAX_EDGE_TIERS,
+            DiceError::InvalidEdgeTiers
+        );
+
+        // VALIDATION: Thresholds must be strictly increasing so each stake has one tier
+        require!(
+            edge_tiers
+                .windows(2)
+                .all(|pair| pair[0].min_amount < pair[1].min_amount),
+            DiceError::InvalidEdgeTiers
+        );
+
+        // VALIDATION: Every tier edge is bounded like the base edge
+        require!(
+            edge_tiers
+                .iter()
+                .all(|tier| tier.house_edge_bp <= MAX_HOUSE_EDGE_BP),
+            DiceError::InvalidEdgeTiers
+        );
+
+        self.config.edge_tiers = edge_tiers;
+        Ok(())
+    }
+}
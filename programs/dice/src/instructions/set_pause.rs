@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::GameConfig;
+
+/// Set Pause Instruction - Lets the house stop or resume new bets
+///
+/// For incident response: a paused game rejects new bets, while bets already
+/// placed can still be resolved or refunded so no funds are locked.
+/// Also resumes a game paused by the LIFETIME_PAYOUT_CAP circuit breaker.
+#[derive(Accounts)]
+pub struct SetPause<'info> {
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for config PDA seed derivation
+    ///CHECK: This check is safe - the config's has_one ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The game's primary authority
+    /// Must sign the transaction
+    pub authority: Signer<'info>,
+
+    /// The game config holding the pause flag
+    ///
+    /// Seeds: ["config", house_pubkey]
+    /// - Only the primary authority can pause it
+    #[account(
+        mut,
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, GameConfig>,
+}
+
+impl<'info> SetPause<'info> {
+    /// Set whether the game accepts new bets
+    ///
+    /// # Arguments
+    /// * `paused` - True to reject new bets, false to accept them again
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success
+    pub fn set_pause(&mut self, paused: bool) -> Result<()> {
+        self.config.is_paused = paused;
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{GameConfig, PayoutRounding};
+
+/// Set Payout Rounding Instruction - Lets the house choose how payouts are rounded
+///
+/// Applies to every payout computed after the change, including bets already
+/// placed, like the house edge read at resolution.
+#[derive(Accounts)]
+pub struct SetPayoutRounding<'info> {
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for config PDA seed derivation
+    ///CHECK: This check is safe - the config's has_one ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The game's primary authority
+    /// Must sign the transaction
+    pub authority: Signer<'info>,
+
+    /// The game config holding the rounding mode
+    ///
+    /// Seeds: ["config", house_pubkey]
+    /// - Only the primary authority can change it
+    #[account(
+        mut,
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, GameConfig>,
+}
+
+impl<'info> SetPayoutRounding<'info> {
+    /// Set the rounding mode of the payout formula
+    ///
+    /// # Arguments
+    /// * `payout_rounding` - Floor (house keeps the remainder) or Nearest
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success
+    pub fn set_payout_rounding(&mut self, payout_rounding: PayoutRounding) -> Result<()> {
+        self.config.payout_rounding = payout_rounding;
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::GameConfig;
+
+/// Set Tie Is Push Instruction - Lets the house choose how a tied roll settles
+///
+/// A roll equal to the prediction is on neither side of it. By default the
+/// tie loses; as a push it returns the stake. Each bet keeps the tie handling
+/// in effect when it was placed.
+#[derive(Accounts)]
+pub struct SetTieIsPush<'info> {
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for config PDA seed derivation
+    ///CHECK: This check is safe - the config's has_one ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The game's primary authority
+    /// Must sign the transaction
+    pub authority: Signer<'info>,
+
+    /// The game config holding the tie handling
+    ///
+    /// Seeds: ["config", house_pubkey]
+    /// - Only the primary authority can change it
+    #[account(
+        mut,
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, GameConfig>,
+}
+
+impl<'info> SetTieIsPush<'info> {
+    /// Set whether a tied roll returns the stake to bets placed from now on
+    ///
+    /// # Arguments
+    /// * `tie_is_push` - True to return the stake on a tie, false for a tie to lose
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success
+    pub fn set_tie_is_push(&mut self, tie_is_push: bool) -> Result<()> {
+        self.config.tie_is_push = tie_is_push;
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::DiceError, utils::require_not_vault, GameConfig};
+
+/// Set Treasury Instruction - Lets the house route part of every lost wager to a treasury
+///
+/// By default a lost wager stays in the vault as house profit. With a treasury
+/// set, resolution moves treasury_bps of each lost wager out of the vault, so
+/// profit is separated from the operating float backing payouts.
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for config PDA seed derivation
+    ///CHECK: This check is safe - the config's has_one ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The game's primary authority
+    /// Must sign the transaction
+    pub authority: Signer<'info>,
+
+    /// The game config holding the treasury split
+    ///
+    /// Seeds: ["config", house_pubkey]
+    /// - Only the primary authority can change it
+    #[account(
+        mut,
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// The house vault PDA the treasury cut is paid out of
+    ///
+    /// Seeds: ["vault", house_pubkey]
+    /// CHECK: Only the address is used; the seeds guarantee it is the program's PDA
+    #[account(
+        seeds = [b"vault", house.key().as_ref()],
+        bump = config.vault_bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+}
+
+impl<'info> SetTreasury<'info> {
+    /// Set or clear the treasury and its share of lost wagers
+    ///
+    /// # Arguments
+    /// * `treasury` - Account receiving the cut (a system account for native SOL
+    ///   games, the owner of the receiving token account for token games), or
+    ///   `None` to keep every lost wager in the vault
+    /// * `treasury_bps` - Share of each lost wager sent to the treasury (at most 10000)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, `InvalidTreasury` or `VaultAsRecipient`
+    pub fn set_treasury(&mut self, treasury: Option<Pubkey>, treasury_bps: u16) -> Result<()> {
+        // VALIDATION: The cut can never exceed the wager
+        require!(treasury_bps <= 10000, DiceError::InvalidTreasury);
+
+        // VALIDATION: No treasury is expressed as None, never as the default key
+        require!(
+            treasury != Some(Pubkey::default()),
+            DiceError::InvalidTreasury
+        );
+
+        // VALIDATION: A vault treasury would keep the cut as house profit; for token
+        // games the vault's token account is the one paying the cut
+        if let Some(treasury) = treasury {
+            require_not_vault(&treasury, &self.vault.key())?;
+        }
+
+        self.config.treasury = treasury;
+        self.config.treasury_bps = treasury_bps;
+        Ok(())
+    }
+}
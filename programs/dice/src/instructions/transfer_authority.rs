@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::DiceError, GameConfig};
+
+/// Transfer Authority Instruction - Hands control of a game to a new key
+///
+/// For operational handoffs and key rotation. Only the primary authority stored
+/// in the config changes: the vault, config and other game PDAs stay seeded by
+/// the original house key, so no funds migrate and clients keep passing that
+/// key as `house`. From then on only the new authority (and the additional
+/// authorities) can resolve, withdraw and manage the game; withdrawals and the
+/// proceeds of close_game go to the new authority.
+#[derive(Accounts)]
+pub struct TransferAuthority<'info> {
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for config PDA seed derivation
+    ///CHECK: This check is safe - the config's has_one ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The game's current primary authority
+    /// Must sign the transaction
+    pub authority: Signer<'info>,
+
+    /// The game config holding the primary authority
+    ///
+    /// Seeds: ["config", house_pubkey]
+    /// - Only the current primary authority can hand the game over
+    #[account(
+        mut,
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, GameConfig>,
+}
+
+impl<'info> TransferAuthority<'info> {
+    /// Replace the game's primary authority
+    ///
+    /// # Arguments
+    /// * `new_authority` - Key taking over control of the game
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or `InvalidNewAuthority`
+    pub fn transfer_authority(&mut self, new_authority: Pubkey) -> Result<()> {
+        // VALIDATION: A real key that actually changes hands
+        require!(
+            new_authority != Pubkey::default() && new_authority != self.config.authority,
+            DiceError::InvalidNewAuthority
+        );
+
+        // AUTHORITIES: The new primary no longer needs an entry in the additional set
+        self.config.authorities.retain(|key| *key != new_authority);
+        self.config.authority = new_authority;
+
+        Ok(())
+    }
+}
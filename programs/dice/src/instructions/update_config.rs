@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::DiceError, GameConfig, MAX_HOUSE_EDGE_BP, MAX_ROLL_RANGE, MIN_GAME_AGE_SLOTS,
+    MIN_REFUND_TIMEOUT_SLOTS,
+};
+
+/// Update Config Instruction - Lets the house adjust the game economics
+///
+/// Changes apply to bets placed after the update: limits are only checked at
+/// placement and each bet keeps the dice range and house edge it was placed under.
+/// A game's launch economics are locked for MIN_GAME_AGE_SLOTS after initialize.
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for config PDA seed derivation
+    ///CHECK: This check is safe - the config's has_one ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The game's primary authority
+    /// Must sign the transaction
+    pub authority: Signer<'info>,
+
+    /// The game config to update
+    ///
+    /// Seeds: ["config", house_pubkey]
+    /// - Only the primary authority can update it
+    #[account(
+        mut,
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        has_one = authority
+    )]
+    pub config: Account<'info, GameConfig>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    /// Replace the adjustable economics in the game config
+    ///
+    /// # Arguments
+    /// * `house_edge_bp` - House edge in basis points (0 to MAX_HOUSE_EDGE_BP)
+    /// * `min_bet` - Minimum bet amount in lamports
+    /// * `max_bet` - Maximum bet amount in lamports (at least `min_bet`)
+    /// * `roll_range` - Dice range for new bets (2 to MAX_ROLL_RANGE)
+    /// * `min_roll` - Minimum roll prediction (at least 2)
+    /// * `max_roll` - Maximum roll prediction (between `min_roll` and `roll_range`)
+    /// * `refund_timeout_slots` - Refund timeout (at least MIN_REFUND_TIMEOUT_SLOTS)
+    /// * `cancel_fee_bps` - Share of the wager kept on cancel_bet (at most 10000)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success, `GameTooYoung` before MIN_GAME_AGE_SLOTS, or validation error
+    #[allow(clippy::too_many_arguments)] // One argument per config field
+    pub fn update_config(
+        &mut self,
+        house_edge_bp: u16,
+        min_bet: u64,
+        max_bet: u64,
+        roll_range: u16,
+        min_roll: u16,
+        max_roll: u16,
+        refund_timeout_slots: u64,
+        cancel_fee_bps: u16,
+    ) -> Result<()> {
+        // VALIDATION: Keep the launch economics until the game has run long enough
+        require!(
+            self.config
+                .has_run_for(Clock::get()?.slot, MIN_GAME_AGE_SLOTS),
+            DiceError::GameTooYoung
+        );
+
+        // VALIDATION: The edge must stay within a sane bound (10% by default)
+        // This also keeps it well below 10000 bp, which would make every payout zero
+        require!(
+            house_edge_bp <= MAX_HOUSE_EDGE_BP,
+            DiceError::InvalidHouseEdge
+        );
+
+        // VALIDATION: Bet limits must form a non-empty range
+        require!(min_bet <= max_bet, DiceError::InvalidBetLimits);
+
+        // VALIDATION: A roll of 1 can never win and rolls are at most roll_range
+        require!(
+            (2..=max_roll).contains(&min_roll)
+                && max_roll <= roll_range
+                && roll_range <= MAX_ROLL_RANGE,
+            DiceError::InvalidRollLimits
+        );
+
+        // VALIDATION: Refunds must not open before the house can reasonably respond
+        require!(
+            refund_timeout_slots >= MIN_REFUND_TIMEOUT_SLOTS,
+            DiceError::InvalidRefundTimeout
+        );
+
+        // VALIDATION: The fee can never exceed the wager
+        require!(cancel_fee_bps <= 10000, DiceError::InvalidCancelFee);
+
+        let config = &mut self.config;
+        config.house_edge_bp = house_edge_bp;
+        config.min_bet = min_bet;
+        config.max_bet = max_bet;
+        config.roll_range = roll_range;
+        config.min_roll = min_roll;
+        config.max_roll = max_roll;
+        config.refund_timeout_slots = refund_timeout_slots;
+        config.cancel_fee_bps = cancel_fee_bps;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    utils::{calculate_payout, entropy_from_signatures, roll_from_entropy},
+    BetDirection, FairnessCheck, PayoutRounding, DUST_PAYOUT_POLICY,
+};
+
+/// Verify Fairness Instruction - Recomputes a signature-based resolution
+///
+/// Changes no state and needs no accounts. Given the signatures and bet
+/// parameters of a past resolution (all published in its FairnessReceipt), it
+/// returns the entropy, roll and base payout exactly as resolve_bet derives them,
+/// so auditors can reproduce a resolution with the program itself.
+#[derive(Accounts)]
+pub struct VerifyFairness {}
+
+impl VerifyFairness {
+    /// Reproduce the roll and base payout of a resolution
+    ///
+    /// # Arguments
+    /// * `signatures` - The house signature followed by any co-signatures, in instruction order
+    /// * `amount` - Amount wagered
+    /// * `prediction` - Player's roll prediction
+    /// * `direction` - Roll under or roll over the prediction
+    /// * `roll_range` - Dice range the bet was placed under
+    /// * `house_edge_bp` - House edge snapshotted on the bet
+    /// * `tie_is_push` - Tie handling snapshotted on the bet
+    /// * `rounding` - Payout rounding of the config at resolution
+    ///
+    /// # Returns
+    /// * `Result<FairnessCheck>` - Entropy, roll, outcome and base payout (the stake on a push)
+    ///
+    /// # Reproduction Procedure
+    /// 1. Check off-chain that the first signature is the resolving authority's
+    ///    Ed25519 signature over the hash of the bet address followed by the
+    ///    serialized bet (see `utils::bet_message`); this instruction does not
+    ///    verify signatures
+    /// 2. Simulate this instruction with the receipt's signatures and parameters
+    /// 3. Compare `entropy` and `roll` with the receipt
+    /// 4. Compare `payout` with the receipt; it differs only when resolution added
+    ///    components outside the odds formula: the slow resolution bonus, the
+    ///    jackpot, or a stake refund for a payout below `min_acceptable_payout`
+    #[allow(clippy::too_many_arguments)] // One argument per receipt field
+    pub fn verify_fairness(
+        &self,
+        signatures: &[[u8; 64]],
+        amount: u64,
+        prediction: u16,
+        direction: BetDirection,
+        roll_range: u16,
+        house_edge_bp: u16,
+        tie_is_push: bool,
+        rounding: PayoutRounding,
+    ) -> Result<FairnessCheck> {
+        // RANDOMNESS: Same entropy and roll derivation as resolve_bet
+        let entropy = entropy_from_signatures(signatures);
+        let roll = roll_from_entropy(&entropy, roll_range);
+        let won = direction.wins(prediction, roll);
+        let push = !won && tie_is_push && roll == prediction;
+
+        // PAYOUT: Same formula and dust policy as a win in resolve_bet
+        let payout = if won {
+            let payout = calculate_payout(
+                amount,
+                prediction,
+                roll_range,
+                direction,
+                house_edge_bp,
+                rounding,
+            )?;
+            DUST_PAYOUT_POLICY.settle(payout, amount)
+        } else if push {
+            amount
+        } else {
+            0
+        };
+
+        Ok(FairnessCheck {
+            entropy,
+            roll,
+            won,
+            payout,
+        })
+    }
+}
@@ -0,0 +1,147 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    error::DiceError,
+    utils::{vault_available_lamports, vault_liabilities},
+    BetTracker, GameConfig, HouseStats, VaultWithdrawn,
+};
+
+/// Withdraw Instruction - Lets the house pull profits out of the vault
+///
+/// The vault only receives funds through initialize, fund_vault and placed bets, so this
+/// is the house's way to take accumulated profits back. The vault PDA signs
+/// the transfer and is never drained below its balance floor (rent exemption
+/// plus MIN_VAULT_RESERVE_LAMPORTS) plus its liabilities: the stakes of open
+/// bets and unclaimed referral, deferred payout and rebate credits. The primary authority or any of the
+/// additional authorities may withdraw, but the lamports always go to the
+/// primary authority. Once liquidity providers have joined, the house can only
+/// withdraw the value of its own shares.
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for PDA seed derivation
+    ///CHECK: This check is safe - the config constraint ties the authority to it
+    pub house: UncheckedAccount<'info>,
+
+    /// The primary authority or one of the additional authorities
+    /// Must sign the transaction
+    pub authority: Signer<'info>,
+
+    /// The game's primary authority, receiving the withdrawn lamports
+    #[account(mut, address = config.authority @ DiceError::UnauthorizedAuthority)]
+    ///CHECK: This check is safe - the address is matched against the config
+    pub primary_authority: UncheckedAccount<'info>,
+
+    /// The game config holding the authority set
+    ///
+    /// Seeds: ["config", house_pubkey]
+    #[account(
+        seeds = [b"config", house.key().as_ref()],
+        bump = config.bump,
+        constraint = config.is_authority(&authority.key()) @ DiceError::UnauthorizedAuthority
+    )]
+    pub config: Account<'info, GameConfig>,
+
+    /// Aggregate statistics holding the house's liquidity shares
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"stats", house.key().as_ref()],
+        bump = stats.bump
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// The tracker summing the stakes of open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// The house vault to withdraw from
+    ///
+    /// Seeds: ["vault", house_pubkey]
+    /// - Only the game's authorities can withdraw from it
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref()],
+        bump,
+        constraint = vault.data_is_empty() @ DiceError::VaultHasData
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// System program required for SOL transfers
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Withdraw<'info> {
+    /// Transfer lamports from the vault to the primary authority
+    ///
+    /// # Arguments
+    /// * `amount` - Amount in lamports to withdraw
+    /// * `bumps` - PDA bumps needed for vault signing authority
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or `InsufficientFunds` if the vault would drop below its
+    ///   balance floor plus liabilities, or the house's shares are worth less than `amount`
+    ///
+    /// # Liquidity Shares
+    /// With LP shares outstanding, the withdrawal burns the house shares worth
+    /// `amount`, rounded up so liquidity providers never cover the rounding.
+    pub fn withdraw(&mut self, amount: u64, bumps: &WithdrawBumps) -> Result<()> {
+        // VALIDATION: Never leave the vault below its balance floor, and never take
+        // lamports owed back to players or referrers
+        let value = vault_available_lamports(&self.vault.to_account_info())?
+            .saturating_sub(vault_liabilities(&self.tracker, &self.stats));
+        require!(amount <= value, DiceError::InsufficientFunds);
+
+        // SHARES: Liquidity providers' part of the vault is not the house's to take
+        let stats = &mut self.stats;
+        if stats.lp_shares > 0 && amount > 0 {
+            let burn = u64::try_from(
+                (amount as u128)
+                    .checked_mul(stats.lp_shares as u128)
+                    .ok_or(DiceError::Overflow)?
+                    .div_ceil(value as u128),
+            )
+            .map_err(|_| error!(DiceError::Overflow))?;
+            require!(burn <= stats.house_shares, DiceError::InsufficientFunds);
+
+            stats.house_shares -= burn;
+            stats.lp_shares -= burn;
+        }
+
+        // SETUP: Prepare vault PDA signing authority
+        let house_key = self.house.key();
+        let seeds = &[b"vault", house_key.as_ref(), &[bumps.vault]];
+        let signer = &[&seeds[..]];
+
+        // TRANSFER: Move the requested amount from vault to the primary authority
+        let accounts = Transfer {
+            from: self.vault.to_account_info(),
+            to: self.primary_authority.to_account_info(),
+        };
+
+        let ctx =
+            CpiContext::new_with_signer(self.system_program.to_account_info(), accounts, signer);
+
+        transfer(ctx, amount)?;
+
+        // EVENT: Report the outflow for treasury monitoring
+        emit!(VaultWithdrawn {
+            house: house_key,
+            actor: self.authority.key(),
+            recipient: self.primary_authority.key(),
+            amount,
+            vault_balance: self.vault.lamports(),
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{
+    error::DiceError,
+    utils::{lamports_for_shares, vault_available_lamports, vault_liabilities},
+    BetTracker, HouseStats, VaultLp, VaultWithdrawn,
+};
+
+/// Withdraw LP Instruction - Redeems a liquidity provider's shares for lamports
+///
+/// Pays out the shares' proportional part of the vault's net value: its
+/// available balance less the stakes of open bets and unclaimed credits. The
+/// value includes the house's profit or loss since the deposit.
+#[derive(Accounts)]
+pub struct WithdrawLp<'info> {
+    /// The liquidity provider redeeming shares
+    /// Must sign the transaction and receives the lamports
+    #[account(mut)]
+    pub provider: Signer<'info>,
+
+    /// House key the game was created with (unchecked for efficiency)
+    /// Used only for PDA seed derivation
+    ///CHECK: This check is safe - the LP account seeds tie it to the game
+    pub house: UncheckedAccount<'info>,
+
+    /// Aggregate statistics holding the share totals
+    ///
+    /// Seeds: ["stats", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"stats", house.key().as_ref()],
+        bump = stats.bump
+    )]
+    pub stats: Account<'info, HouseStats>,
+
+    /// The tracker summing the stakes of open bets
+    ///
+    /// Seeds: ["tracker", house_pubkey]
+    #[account(
+        seeds = [b"tracker", house.key().as_ref()],
+        bump = tracker.bump
+    )]
+    pub tracker: Account<'info, BetTracker>,
+
+    /// The provider's shares in this vault
+    ///
+    /// Seeds: ["lp", house_pubkey, provider_pubkey]
+    #[account(
+        mut,
+        seeds = [b"lp", house.key().as_ref(), provider.key().as_ref()],
+        bump = lp.bump,
+        has_one = provider
+    )]
+    pub lp: Account<'info, VaultLp>,
+
+    /// The house vault paying the withdrawal
+    ///
+    /// Seeds: ["vault", house_pubkey]
+    #[account(
+        mut,
+        seeds = [b"vault", house.key().as_ref()],
+        bump,
+        constraint = vault.data_is_empty() @ DiceError::VaultHasData
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// System program required for SOL transfers
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawLp<'info> {
+    /// Burn shares and pay out their part of the vault
+    ///
+    /// # Arguments
+    /// * `shares` - Number of shares to redeem (at most the provider's holding)
+    /// * `bumps` - PDA bumps needed for vault signing authority
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or `InvalidShares`
+    ///
+    /// # Share Pricing
+    /// lamports = shares * net vault value / lp_shares, rounded down so
+    /// the remaining holders never pay for the rounding. Shares of a vault
+    /// that lost everything redeem for nothing.
+    pub fn withdraw_lp(&mut self, shares: u64, bumps: &WithdrawLpBumps) -> Result<()> {
+        // VALIDATION: Only shares the provider holds can be redeemed
+        require!(
+            shares > 0 && shares <= self.lp.shares,
+            DiceError::InvalidShares
+        );
+
+        // SHARES: Price the shares against the current vault value, net of the
+        // lamports backing open bets and unclaimed credits
+        let value = vault_available_lamports(&self.vault.to_account_info())?
+            .saturating_sub(vault_liabilities(&self.tracker, &self.stats));
+        let amount = lamports_for_shares(shares, self.stats.lp_shares, value)?;
+
+        // BURN: Remove the shares before paying out
+        self.lp.shares -= shares;
+        self.stats.lp_shares = self
+            .stats
+            .lp_shares
+            .checked_sub(shares)
+            .ok_or(DiceError::InvalidShares)?;
+
+        // TRANSFER: Pay the provider from the vault
+        if amount > 0 {
+            let house_key = self.house.key();
+            let seeds = &[b"vault", house_key.as_ref(), &[bumps.vault]];
+            let signer = &[&seeds[..]];
+
+            let accounts = Transfer {
+                from: self.vault.to_account_info(),
+                to: self.provider.to_account_info(),
+            };
+
+            let ctx = CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                accounts,
+                signer,
+            );
+            transfer(ctx, amount)?;
+        }
+
+        // EVENT: Report the outflow for treasury monitoring
+        emit!(VaultWithdrawn {
+            house: self.house.key(),
+            actor: self.provider.key(),
+            recipient: self.provider.key(),
+            amount,
+            vault_balance: self.vault.lamports(),
+        });
+
+        Ok(())
+    }
+}
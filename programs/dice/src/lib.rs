@@ -10,13 +10,16 @@
 // Module declarations for the dice game program
 pub mod constants; // Game configuration and betting limits
 pub mod error; // Custom error definitions for the program
-pub mod instructions; // All instruction handlers (initialize, place_bet, resolve_bet, refund_bet)
+pub mod events; // Events emitted for indexers and frontends
+pub mod instructions; // All instruction handlers (initialize, place_bet, resolve_bet, refund_bet, ...)
 pub mod state; // Data structures and account definitions
+pub mod utils; // Shared validation helpers used across instructions
 
 use anchor_lang::prelude::*;
 
 // Re-export all modules for easier access
 pub use constants::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 
@@ -26,16 +29,17 @@ declare_id!("CV4X2KEEEv9PEmPwH1Uk1kL6vw7mTpMBBduTp713ZcU3");
 pub mod dice_game {
     use super::*;
 
-    /// Initialize the game vault with initial funds from the house
+    /// Initialize the game vault with initial funds from the house and create the game config
     ///
     /// # Arguments
     /// * `ctx` - Context containing accounts needed for initialization
     /// * `amount` - Initial amount in lamports to fund the vault
+    /// * `max_bet` - Maximum bet in lamports the game starts with
     ///
     /// # Returns
     /// * `Result<()>` - Success or error
-    pub fn initialize(ctx: Context<Initialize>, amount: u64) -> Result<()> {
-        ctx.accounts.init(amount)
+    pub fn initialize(ctx: Context<Initialize>, amount: u64, max_bet: u64) -> Result<()> {
+        ctx.accounts.init(amount, max_bet, &ctx.bumps)
     }
 
     /// Place a new bet on a dice roll outcome
@@ -43,21 +47,56 @@ pub mod dice_game {
     /// # Arguments
     /// * `ctx` - Context containing all required accounts
     /// * `seed` - Unique seed to allow multiple bets from same player
-    /// * `roll` - Player's prediction (2-96, higher numbers = higher payout)
+    /// * `roll` - Player's prediction (2-96 by default, higher = likelier win rolling under)
+    /// * `direction` - Win by rolling under (the default game) or over the prediction
     /// * `amount` - Bet amount in lamports
+    /// * `insured` - Opt in to loss insurance for an extra premium
+    /// * `min_acceptable_payout` - Smallest winning payout accepted; a lower payout refunds the stake
+    /// * `committed_hash` - Hash of a house secret obtained before placement, or zeroes
+    /// * `referrer` - Registered referrer to credit, if any (never the player)
+    /// * `vrf` - Resolve through Switchboard VRF (`resolve_bet_vrf`) instead of the house
     ///
     /// # Returns
     /// * `Result<()>` - Success or error
     ///
+    /// # Commit-Reveal
+    /// With a non-zero `committed_hash` the bet can only be resolved through
+    /// `reveal_and_resolve`. If the house never reveals a matching secret, the
+    /// player recovers the wager through `refund_bet` after the timeout.
+    ///
+    /// # Resolution Method
+    /// With `vrf` set the bet can only be resolved through `resolve_bet_vrf`,
+    /// and without it never, so the house cannot choose the randomness source
+    /// after seeing the bet.
+    ///
     /// # Game Logic
-    /// Player wins if the random roll is LESS than their predicted number.
-    /// Higher predictions = higher chance of winning but lower payout multiplier.
-    pub fn place_bet(ctx: Context<PlaceBet>, seed: u128, roll: u8, amount: u64) -> Result<()> {
+    /// Player wins if the random roll is LESS than their predicted number, or
+    /// GREATER when rolling over. More winning rolls = higher chance of winning
+    /// but lower payout multiplier.
+    #[allow(clippy::too_many_arguments)] // One argument per bet parameter
+    pub fn place_bet(
+        ctx: Context<PlaceBet>,
+        seed: u128,
+        roll: u16,
+        direction: BetDirection,
+        amount: u64,
+        insured: bool,
+        min_acceptable_payout: u64,
+        committed_hash: [u8; 32],
+        referrer: Option<Pubkey>,
+        vrf: bool,
+    ) -> Result<()> {
         ctx.accounts.create_bet(
             amount,
             roll,
+            direction,
             seed,
             ctx.accounts.randomness_account.key(),
+            insured,
+            min_acceptable_payout,
+            committed_hash,
+            referrer,
+            vrf,
             &ctx.bumps,
         )
     }
@@ -69,14 +108,130 @@ pub mod dice_game {
     /// * `sig` - Ed25519 signature bytes used to generate random number
     ///
     /// # Returns
-    /// * `Result<()>` - Success or error
+    /// * `Result<ResolutionOutcome>` - Roll, outcome and payout (in the return data), or error
     ///
     /// # Security
     /// The signature is verified to ensure it comes from the house authority
-    /// and is used as entropy source for provably fair randomness.
-    pub fn resolve_bet(ctx: Context<ResolveBet>, sig: Vec<u8>) -> Result<()> {
-        ctx.accounts.verify_ed25519_signature(&sig)?;
-        ctx.accounts.resolve_bet(&ctx.bumps, &sig)
+    /// and is used as entropy source for provably fair randomness. When
+    /// ENTROPY_SIGNERS are configured, their co-signatures are mixed in too.
+    pub fn resolve_bet(ctx: Context<ResolveBet>, sig: Vec<u8>) -> Result<ResolutionOutcome> {
+        if REQUIRE_PLAYER_SIGNATURE {
+            ctx.accounts.verify_player_signature()?;
+        }
+        let entropy = ctx.accounts.verify_ed25519_signature(&sig)?;
+        ctx.accounts.resolve_bet(&ctx.bumps, &sig, &entropy)
+    }
+
+    /// Resolve a placed bet with the player co-signing the transaction
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing bet and vault accounts (player must sign)
+    /// * `sig` - Ed25519 signature bytes used to generate random number
+    ///
+    /// # Returns
+    /// * `Result<ResolutionOutcome>` - Roll, outcome and payout (in the return data), or error
+    ///
+    /// # Security
+    /// Same as `resolve_bet`, but always requires the player's signature so
+    /// funds never move without the player's awareness. Mandatory when
+    /// REQUIRE_PLAYER_SIGNATURE is set.
+    pub fn resolve_bet_cosigned(
+        ctx: Context<ResolveBet>,
+        sig: Vec<u8>,
+    ) -> Result<ResolutionOutcome> {
+        ctx.accounts.verify_player_signature()?;
+        let entropy = ctx.accounts.verify_ed25519_signature(&sig)?;
+        ctx.accounts.resolve_bet(&ctx.bumps, &sig, &entropy)
+    }
+
+    /// Resolve a placed bet with a roll signed by the configured oracle
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing bet and vault accounts
+    /// * `roll` - Final roll (1-roll_range) produced by the oracle's off-chain VRF
+    /// * `sig` - Oracle's Ed25519 signature over the bet message followed by `roll`
+    ///
+    /// # Returns
+    /// * `Result<ResolutionOutcome>` - Roll, outcome and payout (in the return data), or error
+    ///
+    /// # Security
+    /// Only available when ORACLE_AUTHORITY is set. The roll is used as-is
+    /// instead of being derived from signature entropy.
+    pub fn resolve_bet_oracle(
+        ctx: Context<ResolveBet>,
+        roll: u16,
+        sig: Vec<u8>,
+    ) -> Result<ResolutionOutcome> {
+        if REQUIRE_PLAYER_SIGNATURE {
+            ctx.accounts.verify_player_signature()?;
+        }
+        ctx.accounts.verify_oracle_signature(roll, &sig)?;
+        ctx.accounts.settle_bet(&ctx.bumps, roll)
+    }
+
+    /// Resolve a placed bet with a Switchboard On-Demand VRF value
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing bet and vault accounts; the randomness
+    ///   account must be the Switchboard account committed at placement
+    ///
+    /// # Returns
+    /// * `Result<ResolutionOutcome>` - Roll, outcome and payout (in the return data), or error
+    ///
+    /// # Security
+    /// The roll is derived from the revealed VRF value, so the house cannot
+    /// grind signatures to influence the outcome. Only bets placed with `vrf`
+    /// set resolve here (`ResolutionMethodMismatch` otherwise).
+    pub fn resolve_bet_vrf(ctx: Context<ResolveBet>) -> Result<ResolutionOutcome> {
+        if REQUIRE_PLAYER_SIGNATURE {
+            ctx.accounts.verify_player_signature()?;
+        }
+        let roll = ctx.accounts.verify_vrf_randomness()?;
+        ctx.accounts.settle_bet(&ctx.bumps, roll)
+    }
+
+    /// Resolve a committed bet by revealing the house secret
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing bet and vault accounts
+    /// * `secret` - House secret whose hash was committed on the bet at placement
+    ///
+    /// # Returns
+    /// * `Result<ResolutionOutcome>` - Roll, outcome and payout (in the return data), or error
+    ///
+    /// # Security
+    /// The secret was fixed before the player chose the bet, so the house
+    /// cannot grind it; a secret that does not match fails with `RevealMismatch`.
+    pub fn reveal_and_resolve(
+        ctx: Context<ResolveBet>,
+        secret: [u8; 32],
+    ) -> Result<ResolutionOutcome> {
+        if REQUIRE_PLAYER_SIGNATURE {
+            ctx.accounts.verify_player_signature()?;
+        }
+        let roll = ctx.accounts.verify_reveal(&secret)?;
+        ctx.accounts.settle_bet(&ctx.bumps, roll)
+    }
+
+    /// Resolve up to MAX_BATCH_RESOLVE bets in a single transaction
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the shared game accounts; each bet's
+    ///   [bet, player, player_stats, randomness_account] follow as remaining accounts
+    /// * `sigs` - One Ed25519 signature per bet, in the same order
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Security
+    /// Every bet is verified and settled exactly as by `resolve_bet`, against
+    /// its own Ed25519 instruction preceding this one.
+    pub fn resolve_bets_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveBetsBatch<'info>>,
+        sigs: Vec<Vec<u8>>,
+    ) -> Result<()> {
+        ctx.accounts
+            .resolve_bets_batch(&ctx.bumps, ctx.remaining_accounts, &sigs)
     }
 
     /// Refund a bet that hasn't been resolved within the timeout period
@@ -89,8 +244,585 @@ pub mod dice_game {
     ///
     /// # Refund Policy
     /// Players can claim refunds if their bet hasn't been resolved
-    /// after REFUND_TIMEOUT_SLOTS (~1 minute) have passed.
+    /// after the config's refund timeout (~1 minute by default) has passed.
     pub fn refund_bet(ctx: Context<RefundBet>) -> Result<()> {
         ctx.accounts.refund_bet(&ctx.bumps)
     }
+
+    /// Cancel a pending bet immediately, for a fee
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing bet, randomness and vault accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Cancellation Policy
+    /// Unlike refund_bet there is no timeout: the player gets the wager back
+    /// minus the config's cancellation fee (2% by default), which stays in the vault.
+    /// Only possible before the bet becomes resolvable (MIN_RESOLVE_DELAY_SLOTS).
+    pub fn cancel_bet(ctx: Context<CancelBet>) -> Result<()> {
+        ctx.accounts.cancel_bet(&ctx.bumps)
+    }
+
+    /// Refund a pending bet immediately on the house's initiative
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the authority, bet and vault accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Dispute Resolution
+    /// For bets the house cannot resolve, e.g. when no signature can be
+    /// produced. The full wager goes back to the player without any timeout.
+    pub fn admin_refund(ctx: Context<AdminRefund>) -> Result<()> {
+        ctx.accounts.admin_refund(&ctx.bumps)
+    }
+
+    /// Recompute a player's open bet count from their bet accounts
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the authority, config and player stats accounts;
+    ///   the player's open bets follow as remaining accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Usage
+    /// Repairs a drifted PlayerStats counter that would otherwise lock the
+    /// player out of MAX_OPEN_BETS_PER_PLAYER. Bets already settled are not counted.
+    pub fn reconcile_player_stats<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReconcilePlayerStats<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.reconcile_player_stats(ctx.remaining_accounts)
+    }
+
+    /// Refund a timed-out bet on the player's behalf
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the keeper, player, bet and vault accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Refund Policy
+    /// Permissionless: any keeper can call this once the refund timeout has
+    /// passed. The wager goes to the player; the keeper earns KEEPER_TIP_LAMPORTS.
+    pub fn keeper_refund(ctx: Context<KeeperRefund>) -> Result<()> {
+        ctx.accounts.keeper_refund(&ctx.bumps)
+    }
+
+    /// Settle a resolved bet once its dispute window has passed
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing bet and vault accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Dispute Window
+    /// Only used when DISPUTE_WINDOW_SLOTS is non-zero. Funds move to the
+    /// player here rather than in resolve_bet, after the player has had time
+    /// to re-verify the resolution.
+    pub fn finalize_resolution(ctx: Context<FinalizeResolution>) -> Result<()> {
+        ctx.accounts.finalize_resolution(&ctx.bumps)
+    }
+
+    /// Top up the vault of an existing game in exchange for liquidity shares
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the funder, house, config, stats, LP and vault accounts
+    /// * `amount` - Amount in lamports to add to the vault
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Usage
+    /// Anyone may fund the vault. The deposit mints shares of the vault's
+    /// available balance, redeemable through withdraw_lp.
+    pub fn fund_vault(ctx: Context<FundVault>, amount: u64) -> Result<()> {
+        ctx.accounts.fund_vault(amount, &ctx.bumps)
+    }
+
+    /// Redeem liquidity shares for their part of the vault
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the provider, house, stats, LP and vault accounts
+    /// * `shares` - Number of shares to redeem
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Usage
+    /// The payout follows the vault: shares are worth more after house profits
+    /// and less after house losses.
+    pub fn withdraw_lp(ctx: Context<WithdrawLp>, shares: u64) -> Result<()> {
+        ctx.accounts.withdraw_lp(shares, &ctx.bumps)
+    }
+
+    /// Withdraw profits from the vault back to the house
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house, authority, config and vault accounts
+    /// * `amount` - Amount in lamports to withdraw
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Security
+    /// Only the primary authority or one of the additional authorities can
+    /// withdraw, the lamports always go to the primary authority, and the vault is
+    /// never drained below its rent-exempt minimum.
+    pub fn withdraw(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw(amount, &ctx.bumps)
+    }
+
+    /// Close a settled bet and return its rent to the player
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the player and bet accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Usage
+    /// Settled bets stay open when KEEP_RESOLVED_BETS is set so the rolled
+    /// value can be inspected on-chain; the player closes them afterwards.
+    pub fn close_bet(ctx: Context<CloseBet>) -> Result<()> {
+        ctx.accounts.close_bet()
+    }
+
+    /// Update the adjustable game economics
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house and config accounts
+    /// * `house_edge_bp` - House edge in basis points (at most MAX_HOUSE_EDGE_BP)
+    /// * `min_bet` - Minimum bet amount in lamports
+    /// * `max_bet` - Maximum bet amount in lamports
+    /// * `roll_range` - Dice range for new bets (rolls are 1-roll_range)
+    /// * `min_roll` - Minimum roll prediction
+    /// * `max_roll` - Maximum roll prediction
+    /// * `refund_timeout_slots` - Slots before an unresolved bet can be refunded
+    /// * `cancel_fee_bps` - Share of the wager kept when a player cancels a bet
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    #[allow(clippy::too_many_arguments)] // One argument per config field
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        house_edge_bp: u16,
+        min_bet: u64,
+        max_bet: u64,
+        roll_range: u16,
+        min_roll: u16,
+        max_roll: u16,
+        refund_timeout_slots: u64,
+        cancel_fee_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.update_config(
+            house_edge_bp,
+            min_bet,
+            max_bet,
+            roll_range,
+            min_roll,
+            max_roll,
+            refund_timeout_slots,
+            cancel_fee_bps,
+        )
+    }
+
+    /// Pause or resume new bets
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house and config accounts
+    /// * `paused` - True to reject new bets, false to accept them again
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Usage
+    /// Existing bets can still be resolved and refunded while the game is paused.
+    pub fn set_pause(ctx: Context<SetPause>, paused: bool) -> Result<()> {
+        ctx.accounts.set_pause(paused)
+    }
+
+    /// Choose how the payout formula rounds
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house, authority and config accounts
+    /// * `payout_rounding` - Floor or Nearest
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Usage
+    /// Floor (the default) keeps the sub-unit remainder for the house; Nearest
+    /// splits it fairly. See `utils::calculate_payout` for the exact impact.
+    pub fn set_payout_rounding(
+        ctx: Context<SetPayoutRounding>,
+        payout_rounding: PayoutRounding,
+    ) -> Result<()> {
+        ctx.accounts.set_payout_rounding(payout_rounding)
+    }
+
+    /// Route a share of every lost wager to a treasury
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house, authority, config and vault accounts
+    /// * `treasury` - Treasury account (token games: owner of the receiving token account), or None
+    /// * `treasury_bps` - Share of each lost wager sent to the treasury, at most 10000
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Usage
+    /// Once set, losing resolutions must pass the treasury (or its token account).
+    /// The cut leaves the vault, so it is no longer backing payouts or LP shares.
+    pub fn set_treasury(
+        ctx: Context<SetTreasury>,
+        treasury: Option<Pubkey>,
+        treasury_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.set_treasury(treasury, treasury_bps)
+    }
+
+    /// Choose whether a tied roll returns the stake
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house, authority and config accounts
+    /// * `tie_is_push` - True for a push (stake returned), false for a tie to lose
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Usage
+    /// A tie is a roll equal to the prediction. It loses by default; as a push
+    /// it gives the player back part of the house edge. Open bets keep the
+    /// setting they were placed under.
+    pub fn set_tie_is_push(ctx: Context<SetTieIsPush>, tie_is_push: bool) -> Result<()> {
+        ctx.accounts.set_tie_is_push(tie_is_push)
+    }
+
+    /// Replace the house edge tiers for larger stakes
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house, authority and config accounts
+    /// * `edge_tiers` - Minimum stakes and their edges, by increasing minimum (up to MAX_EDGE_TIERS)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Usage
+    /// A stake uses the edge of the highest tier it reaches, or the base
+    /// house_edge_bp below every tier. Open bets keep the edge they were placed under.
+    pub fn set_edge_tiers(ctx: Context<SetEdgeTiers>, edge_tiers: Vec<EdgeTier>) -> Result<()> {
+        ctx.accounts.set_edge_tiers(edge_tiers)
+    }
+
+    /// Add a key that may resolve bets and withdraw for the house
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house, authority and config accounts
+    /// * `authority` - Key to add to the authority set
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Security
+    /// Only the primary authority can change the set, and at most MAX_AUTHORITIES
+    /// keys can be added.
+    pub fn add_authority(ctx: Context<ManageAuthorities>, authority: Pubkey) -> Result<()> {
+        ctx.accounts.add_authority(authority)
+    }
+
+    /// Remove a key previously added with `add_authority`
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house, authority and config accounts
+    /// * `authority` - Key to remove from the authority set
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn remove_authority(ctx: Context<ManageAuthorities>, authority: Pubkey) -> Result<()> {
+        ctx.accounts.remove_authority(authority)
+    }
+
+    /// Hand control of the game to a new primary authority
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house, authority and config accounts
+    /// * `new_authority` - Key taking over the game
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Vault
+    /// The game PDAs stay seeded by the original house key, so funds do not
+    /// move; only who may control and withdraw from them changes.
+    pub fn transfer_authority(
+        ctx: Context<TransferAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.transfer_authority(new_authority)
+    }
+
+    /// Register as a referrer for a game
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the referrer, house and earnings accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        ctx.accounts.register_referrer(&ctx.bumps)
+    }
+
+    /// Claim the referral rewards accrued in a game
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the referrer, vault and earnings accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Usage
+    /// Rewards are credited when referred bets resolve and paid from the vault.
+    pub fn claim_referral(ctx: Context<ClaimReferral>) -> Result<()> {
+        ctx.accounts.claim_referral(&ctx.bumps)
+    }
+
+    /// Withdraw payouts escrowed for the player
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the player and winnings accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Usage
+    /// Only holds funds when PULL_PAYOUTS is set, in which case resolution
+    /// escrows payouts instead of sending them to the player.
+    pub fn claim_winnings(ctx: Context<ClaimWinnings>) -> Result<()> {
+        ctx.accounts.claim_winnings()
+    }
+
+    /// Stop new bets and deposits so the game can be shut down
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house, authority and config accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Usage
+    /// Open bets still resolve, refund and cancel as usual; once the last one
+    /// has settled the primary authority calls close_game. Closing cannot be undone.
+    pub fn begin_close_game(ctx: Context<BeginCloseGame>) -> Result<()> {
+        ctx.accounts.begin_close_game()
+    }
+
+    /// Collect winnings held back by the daily payout ceiling
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the player, vault, stats and player stats accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Usage
+    /// Only owes anything when DAILY_PAYOUT_CEILING_LAMPORTS is set. Pays at most
+    /// the player's remaining allowance for the current window; call again in a
+    /// later window for the rest.
+    pub fn claim_deferred_payout(ctx: Context<ClaimDeferredPayout>) -> Result<()> {
+        ctx.accounts.claim_deferred_payout(&ctx.bumps)
+    }
+
+    /// Claim the house edge rebates accrued in a game
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the player, vault, stats and player stats accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Usage
+    /// Only accrues when REBATE_BPS is set: each resolved bet credits the player
+    /// that share of its house edge, paid from the vault here.
+    pub fn claim_rebate(ctx: Context<ClaimRebate>) -> Result<()> {
+        ctx.accounts.claim_rebate(&ctx.bumps)
+    }
+
+    /// Shut down the game and return all funds and rent to the primary authority
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house, authority, vault and game accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    ///
+    /// # Security
+    /// Only the primary authority can close the game, only after begin_close_game,
+    /// and only once no bets are open.
+    pub fn close_game(ctx: Context<CloseGame>) -> Result<()> {
+        ctx.accounts.close_game(&ctx.bumps)
+    }
+
+    /// Quote the payout and win probability for a prospective bet
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house and config accounts
+    /// * `amount` - Bet amount in lamports
+    /// * `roll` - Player's roll prediction
+    /// * `direction` - Roll under or roll over the prediction
+    ///
+    /// # Returns
+    /// * `Result<PayoutQuote>` - Success with the quote, or error
+    ///
+    /// # Usage
+    /// Changes no state. Anchor places the returned `PayoutQuote` in the
+    /// transaction return data; clients simulate the instruction (e.g.
+    /// `program.methods.quotePayout(amount, roll, direction).accounts({...}).view()`)
+    /// and decode it from there.
+    pub fn quote_payout(
+        ctx: Context<QuotePayout>,
+        amount: u64,
+        roll: u16,
+        direction: BetDirection,
+    ) -> Result<PayoutQuote> {
+        ctx.accounts.quote_payout(amount, roll, direction)
+    }
+
+    /// Return the win probability of a prediction under the current config
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house and config accounts
+    /// * `roll` - Player's roll prediction
+    /// * `direction` - Roll under or roll over the prediction
+    ///
+    /// # Returns
+    /// * `Result<u16>` - Success with the probability in basis points, or error
+    ///
+    /// # Usage
+    /// Changes no state; simulate it like quote_payout. Accounts for the
+    /// configured dice range and the direction, e.g. rolling under 50 of 100 is 4900.
+    pub fn win_probability(
+        ctx: Context<QuotePayout>,
+        roll: u16,
+        direction: BetDirection,
+    ) -> Result<u16> {
+        ctx.accounts.win_probability(roll, direction)
+    }
+
+    /// Recompute the roll and payout of a past signature-based resolution
+    ///
+    /// # Arguments
+    /// * `ctx` - Empty context; no accounts are needed
+    /// * `signatures` - House signature followed by any co-signatures, from the FairnessReceipt
+    /// * `amount` - Amount wagered
+    /// * `prediction` - Player's roll prediction
+    /// * `direction` - Roll under or roll over the prediction
+    /// * `roll_range` - Dice range the bet was placed under
+    /// * `house_edge_bp` - House edge applied to the bet
+    /// * `tie_is_push` - Whether a tie returned the stake for the bet
+    /// * `rounding` - Payout rounding in effect at resolution
+    ///
+    /// # Returns
+    /// * `Result<FairnessCheck>` - Success with the recomputed resolution, or error
+    ///
+    /// # Usage
+    /// Changes no state; simulate it like quote_payout and compare the result
+    /// with the FairnessReceipt. See `VerifyFairness::verify_fairness` for the
+    /// full reproduction procedure.
+    #[allow(clippy::too_many_arguments)] // One argument per receipt field
+    pub fn verify_fairness(
+        ctx: Context<VerifyFairness>,
+        signatures: Vec<[u8; 64]>,
+        amount: u64,
+        prediction: u16,
+        direction: BetDirection,
+        roll_range: u16,
+        house_edge_bp: u16,
+        tie_is_push: bool,
+        rounding: PayoutRounding,
+    ) -> Result<FairnessCheck> {
+        ctx.accounts.verify_fairness(
+            &signatures,
+            amount,
+            prediction,
+            direction,
+            roll_range,
+            house_edge_bp,
+            tie_is_push,
+            rounding,
+        )
+    }
+
+    /// Initialize a token game: create the vault token account and config for `mint`
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing the house, mint and vault token accounts
+    /// * `amount` - Initial amount in the mint's base units to fund the vault
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn initialize_token(ctx: Context<InitializeToken>, amount: u64) -> Result<()> {
+        ctx.accounts.init_token(amount, &ctx.bumps)
+    }
+
+    /// Place a new bet in the game's SPL token
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing all required accounts
+    /// * `seed` - Unique seed to allow multiple bets from same player
+    /// * `roll` - Player's prediction (higher = likelier win rolling under)
+    /// * `direction` - Win by rolling under or over the prediction
+    /// * `amount` - Bet amount in the mint's base units
+    /// * `min_acceptable_payout` - Smallest winning payout accepted; a lower payout refunds the stake
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn place_bet_token(
+        ctx: Context<PlaceBetToken>,
+        seed: u128,
+        roll: u16,
+        direction: BetDirection,
+        amount: u64,
+        min_acceptable_payout: u64,
+    ) -> Result<()> {
+        ctx.accounts.create_bet_token(
+            amount,
+            roll,
+            direction,
+            seed,
+            min_acceptable_payout,
+            &ctx.bumps,
+        )
+    }
+
+    /// Resolve a token bet using Ed25519 signature for randomness
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing bet and vault token accounts
+    /// * `sig` - Ed25519 signature bytes used to generate random number
+    ///
+    /// # Returns
+    /// * `Result<ResolutionOutcome>` - Roll, outcome and payout (in the return data), or error
+    pub fn resolve_bet_token(
+        ctx: Context<ResolveBetToken>,
+        sig: Vec<u8>,
+    ) -> Result<ResolutionOutcome> {
+        ctx.accounts.resolve_bet_token(&ctx.bumps, &sig)
+    }
+
+    /// Refund a token bet that hasn't been resolved within the timeout period
+    ///
+    /// # Arguments
+    /// * `ctx` - Context containing bet and vault token accounts
+    ///
+    /// # Returns
+    /// * `Result<()>` - Success or error
+    pub fn refund_bet_token(ctx: Context<RefundBetToken>) -> Result<()> {
+        ctx.accounts.refund_bet_token(&ctx.bumps)
+    }
 }
@@ -1,5 +1,10 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    error::DiceError, utils::calculate_payout, JACKPOT_ROLL, MIN_PAYOUT_LAMPORTS,
+    MIN_RESOLVE_DELAY_SLOTS, PAYOUT_WINDOW_SLOTS, REFUND_WINDOW_SLOTS, ROLL_DISTRIBUTION_INTERVAL,
+};
+
 /// Bet Account - Stores all information about a single dice bet
 ///
 /// Each bet is a Program Derived Account (PDA) with seeds:
@@ -10,11 +15,20 @@ use anchor_lang::prelude::*;
 #[derive(InitSpace)]
 pub struct Bet {
     /// Amount wagered in lamports
+    /// Excludes any charity donation taken from the stake at placement
     pub amount: u64,
 
+    /// Portion of the stake donated to charity at placement, in lamports
+    /// Tracked separately from the wager and the house edge
+    pub donation: u64,
+
     /// Public key of the player who placed this bet
     pub player: Pubkey,
 
+    /// House key of the game the bet was placed in
+    /// Bet PDAs are keyed by player alone, so settlement checks this against the house
+    pub house: Pubkey,
+
     /// Solana slot number when the bet was placed
     /// Used for timeout calculations and ordering
     pub slot: u64,
@@ -23,10 +37,24 @@ pub struct Bet {
     /// Prevents collision when same player wants multiple active bets
     pub seed: u128,
 
-    /// Player's roll prediction (2-96)
-    /// Player wins if the actual random roll is LESS than this number
-    /// Higher numbers = higher win probability but lower payout multiplier
-    pub roll: u8,
+    /// Player's roll prediction (2-96 by default)
+    /// Player wins if the actual random roll is LESS than this number (GREATER when rolling over)
+    /// Rolling under, higher numbers = higher win probability but lower payout multiplier
+    pub roll: u16,
+
+    /// Dice range the bet was placed under: the roll is drawn from 1-roll_range
+    /// Copied from the config at placement, so a later range change cannot alter the odds
+    pub roll_range: u16,
+
+    /// Whether the player wins by rolling under or over the prediction
+    pub direction: BetDirection,
+
+    /// House edge in basis points at placement, applied to the payout at resolution
+    /// Snapshotted so a later update_config can never worsen the odds of an open bet
+    pub house_edge_bp: u16,
+
+    /// Whether a tie returns the stake, snapshotted from the config at placement
+    pub tie_is_push: bool,
 
     /// PDA bump for this bet account
     /// Used for signing transactions on behalf of this account
@@ -36,6 +64,10 @@ pub struct Bet {
     /// Links this bet to a specific source of randomness
     pub randomness_account: Pubkey,
 
+    /// Data length of the randomness account when the bet was placed
+    /// Resolution fails if the account was closed or reallocated since
+    pub randomness_data_len: u64,
+
     /// Slot number when the bet was committed/finalized
     /// Used to calculate refund eligibility timeouts
     pub commit_slot: u64,
@@ -45,4 +77,1304 @@ pub struct Bet {
     /// - false: Bet is active and awaiting resolution
     /// - true: Bet has been resolved (win/loss) or refunded
     pub is_resolved: bool,
+
+    /// Payout decided at resolution (0 on an uninsured loss)
+    /// With DISPUTE_WINDOW_SLOTS set, it is owed until finalize_resolution runs
+    pub payout: u64,
+
+    /// Slot at which the bet was resolved, pending finalization
+    /// 0 unless the bet is waiting out the dispute window
+    pub resolved_slot: u64,
+
+    /// Actual rolled value (1-roll_range), recorded at resolution
+    /// Only observable when the bet is left open after resolution
+    pub rolled_value: u16,
+
+    /// Whether the player won, recorded at resolution
+    pub won: bool,
+
+    /// Whether the player paid the insurance premium for this bet
+    /// An insured loss returns INSURANCE_COVER_BPS of the wager from the insurance pool
+    pub insured: bool,
+
+    /// Smallest winning payout the player accepted at placement, in lamports
+    /// If a later config change would pay less, resolution refunds the stake instead
+    pub min_acceptable_payout: u64,
+
+    /// Mint of the token wagered, or `Pubkey::default()` for native SOL bets
+    /// Keeps a bet from being settled through the other currency's instructions
+    pub mint: Pubkey,
+
+    /// Hash of the house secret committed before placement, or all zeroes for none
+    /// A committed bet can only be resolved by revealing the secret
+    pub committed_hash: [u8; 32],
+
+    /// Whether the player chose Switchboard VRF randomness at placement
+    /// A VRF bet only resolves through resolve_bet_vrf, any other bet never does
+    pub vrf: bool,
+
+    /// Registered referrer credited with REFERRAL_BPS at resolution, if any
+    pub referrer: Option<Pubkey>,
+}
+
+impl Bet {
+    /// Check that the bet can be resolved without revealing a house secret
+    ///
+    /// Called first by every resolution path except reveal_and_resolve, so the
+    /// house cannot skip the reveal of a committed bet. Fails with
+    /// `RevealRequired` when the bet carries a commitment.
+    pub fn require_no_commitment(&self) -> Result<()> {
+        require!(self.committed_hash == [0; 32], DiceError::RevealRequired);
+        Ok(())
+    }
+
+    /// Check that the bet is resolved through the method chosen at placement
+    ///
+    /// `vrf` tells whether the calling path reads Switchboard VRF randomness.
+    /// Every resolution path calls this, so the house cannot pick the randomness
+    /// source per bet. Fails with `ResolutionMethodMismatch` otherwise.
+    pub fn require_resolution_method(&self, vrf: bool) -> Result<()> {
+        require!(self.vrf == vrf, DiceError::ResolutionMethodMismatch);
+        Ok(())
+    }
+
+    /// Slots passed between the bet's commit slot and `slot`
+    ///
+    /// A commit slot ahead of `slot` (e.g. after a fork rollback) is an anomaly:
+    /// saturating it to 0 slots passed would block refunds without explanation,
+    /// so it fails with `InvalidSlot` instead. A bet without a commit slot would
+    /// count every slot since genesis and be instantly refundable, so an unset
+    /// commit slot of 0 fails with `BetNotCommitted`.
+    pub fn slots_since_commit(&self, slot: u64) -> Result<u64> {
+        require!(self.commit_slot != 0, DiceError::BetNotCommitted);
+        slot.checked_sub(self.commit_slot)
+            .ok_or(error!(DiceError::InvalidSlot))
+    }
+
+    /// Check that the bet may be resolved at `slot`
+    ///
+    /// Resolution must wait MIN_RESOLVE_DELAY_SLOTS after the commit slot, so the
+    /// randomness is bound to a slot the house could not fully control at placement
+    /// (`TooEarly`), and stops at the refund timeout, from which on the bet belongs
+    /// to the player's refund (`RandomnessExpired`).
+    pub fn require_resolvable_at(&self, slot: u64, refund_timeout_slots: u64) -> Result<()> {
+        let age = slot.saturating_sub(self.commit_slot);
+        require!(age < refund_timeout_slots, DiceError::RandomnessExpired);
+        require!(age >= MIN_RESOLVE_DELAY_SLOTS, DiceError::TooEarly);
+        Ok(())
+    }
+
+    /// Check that at least one roll wins the bet
+    ///
+    /// Defense in depth for the resolution paths: the payout divides by the
+    /// number of winning rolls, so a bet without any (e.g. a roll of 1 rolling
+    /// under) fails with `MinimumRoll` instead of an arithmetic Overflow.
+    pub fn require_winnable(&self) -> Result<()> {
+        require!(
+            self.direction.winning_outcomes(self.roll, self.roll_range) > 0,
+            DiceError::MinimumRoll
+        );
+        Ok(())
+    }
+}
+
+/// Game Config Account - Adjustable economics for a single house's game
+///
+/// PDA with seeds: ["config", house_pubkey]
+///
+/// Created during initialize with the defaults from constants.rs and changed
+/// through update_config, so the economics can be tuned without a redeploy.
+#[account]
+#[derive(InitSpace)]
+pub struct GameConfig {
+    /// House key the game was created with; seeds every game PDA and never changes
+    pub house: Pubkey,
+
+    /// Primary authority controlling the game
+    /// Starts as the house and is handed over through transfer_authority
+    pub authority: Pubkey,
+
+    /// House edge in basis points (150 = 1.5%)
+    /// Must stay below 10000, otherwise every payout would be zero
+    pub house_edge_bp: u16,
+
+    /// Minimum bet amount in lamports
+    pub min_bet: u64,
+
+    /// Maximum bet amount in lamports
+    pub max_bet: u64,
+
+    /// Dice range for new bets: rolls are drawn from 1-roll_range (100 by default)
+    pub roll_range: u16,
+
+    /// Minimum roll prediction (at least 2, so the player can win)
+    pub min_roll: u16,
+
+    /// Maximum roll prediction (at most roll_range)
+    pub max_roll: u16,
+
+    /// Mint of the betting token, or `Pubkey::default()` for a native SOL game
+    /// Set once at initialization; bet limits are in this mint's base units
+    pub mint: Pubkey,
+
+    /// Slots after the commit slot before an unresolved bet can be refunded
+    pub refund_timeout_slots: u64,
+
+    /// Share of the wager kept in the vault when a player cancels a pending bet, in basis points
+    pub cancel_fee_bps: u16,
+
+    /// Whether new bets are rejected (set through set_pause)
+    /// Resolution and refunds keep working while paused
+    pub is_paused: bool,
+
+    /// Whether the house has started shutting the game down (set through begin_close_game)
+    /// Rejects new bets and deposits; open bets still resolve and refund, and
+    /// close_game completes once none is left
+    pub is_closing: bool,
+
+    /// Rounding of the payout formula (set through set_payout_rounding)
+    pub payout_rounding: PayoutRounding,
+
+    /// Whether a roll equal to the prediction returns the stake instead of losing
+    /// Set through set_tie_is_push; bets keep the value they were placed under
+    pub tie_is_push: bool,
+
+    /// Account receiving a share of every lost wager, or None to keep losses in the vault
+    /// Set through set_treasury; for token games, the owner of the receiving token account
+    pub treasury: Option<Pubkey>,
+
+    /// Share of each lost wager sent to the treasury, in basis points
+    pub treasury_bps: u16,
+
+    /// Additional keys that may resolve bets and withdraw alongside the primary authority
+    /// Managed by the primary authority through add_authority/remove_authority
+    #[max_len(4)]
+    pub authorities: Vec<Pubkey>,
+
+    /// Lower house edges for larger stakes, by increasing minimum amount
+    /// Managed by the primary authority through set_edge_tiers
+    #[max_len(4)]
+    pub edge_tiers: Vec<EdgeTier>,
+
+    /// Slot the game was initialized in
+    /// update_config is locked until MIN_GAME_AGE_SLOTS have passed since
+    pub created_slot: u64,
+
+    /// Canonical bump of the vault PDA, recorded at initialize
+    /// Resolutions and refunds must sign for the vault with exactly this bump
+    pub vault_bump: u8,
+
+    /// PDA bump for this config account
+    pub bump: u8,
+}
+
+impl GameConfig {
+    /// Whether `key` may act for the house: the primary authority or an added authority
+    pub fn is_authority(&self, key: &Pubkey) -> bool {
+        *key == self.authority || self.authorities.contains(key)
+    }
+
+    /// House edge for a stake of `amount`: the highest tier it reaches, else the base edge
+    pub fn house_edge_for(&self, amount: u64) -> u16 {
+        self.edge_tiers
+            .iter()
+            .rev()
+            .find(|tier| amount >= tier.min_amount)
+            .map_or(self.house_edge_bp, |tier| tier.house_edge_bp)
+    }
+
+    /// Treasury cut of a lost wager of `amount`: treasury_bps of it, or 0 without a treasury
+    pub fn treasury_cut(&self, amount: u64) -> u64 {
+        match self.treasury {
+            Some(_) => (amount as u128 * self.treasury_bps as u128 / 10000) as u64,
+            None => 0,
+        }
+    }
+
+    /// Whether the game has run for at least `min_age` slots at `slot`
+    pub fn has_run_for(&self, slot: u64, min_age: u64) -> bool {
+        slot.saturating_sub(self.created_slot) >= min_age
+    }
+
+    /// Largest payout a bet within this config's limits can win
+    ///
+    /// The max bet on the longest shot in either direction: under min_roll, or
+    /// over the highest prediction that can still win, at the edge applied to the max bet
+    pub fn worst_case_payout(&self) -> Result<u64> {
+        let house_edge_bp = self.house_edge_for(self.max_bet);
+        let under = calculate_payout(
+            self.max_bet,
+            self.min_roll,
+            self.roll_range,
+            BetDirection::Under,
+            house_edge_bp,
+            self.payout_rounding,
+        )?;
+        let over = calculate_payout(
+            self.max_bet,
+            self.max_roll.min(self.roll_range.saturating_sub(1)),
+            self.roll_range,
+            BetDirection::Over,
+            house_edge_bp,
+            self.payout_rounding,
+        )?;
+        Ok(under.max(over))
+    }
+}
+
+/// Bet Tracker Account - Global count of a house's open bets
+///
+/// PDA with seeds: ["tracker", house_pubkey]
+///
+/// Incremented when a bet is placed and decremented when it is resolved or
+/// refunded, so the MAX_OPEN_BETS limit is enforced without scanning bets.
+#[account]
+#[derive(InitSpace)]
+pub struct BetTracker {
+    /// House authority that owns this game
+    pub house: Pubkey,
+
+    /// Number of bets placed but not yet resolved or refunded
+    pub open_bets: u32,
+
+    /// Sum of those bets' stakes, which the vault owes back if they are refunded
+    /// Counted as a vault liability by withdraw and LP share pricing
+    pub open_stakes: u64,
+
+    /// PDA bump for this tracker account
+    pub bump: u8,
+}
+
+/// House Stats Account - Aggregate counters for a house's game
+///
+/// PDA with seeds: ["stats", house_pubkey]
+///
+/// Updated on every placement and resolution so dashboards can track volume
+/// and profit without indexing every transaction. Amounts are in the game's
+/// betting currency (lamports, or token base units for token games).
+#[account]
+#[derive(InitSpace)]
+pub struct HouseStats {
+    /// House authority that owns this game
+    pub house: Pubkey,
+
+    /// Number of bets placed
+    pub total_bets: u64,
+
+    /// Total amount wagered
+    pub total_volume: u64,
+
+    /// Total amount paid out on resolution
+    pub total_payout: u64,
+
+    /// Number of resolved bets the player won
+    pub wins: u64,
+
+    /// Number of resolved bets the player lost
+    pub losses: u64,
+
+    /// Lamports in the jackpot pool, paid in full to the next JACKPOT_ROLL
+    pub jackpot_pool: u64,
+
+    /// Outstanding liquidity shares in the vault, the house's included
+    /// 0 until the first fund_vault deposit; the house then owns the whole vault implicitly
+    pub lp_shares: u64,
+
+    /// Part of `lp_shares` owned by the house, issued for the vault balance
+    /// present at the first deposit and burned by withdraw
+    pub house_shares: u64,
+
+    /// Winnings held back by DAILY_PAYOUT_CEILING_LAMPORTS and still owed to players
+    /// Stays in the vault until claimed through claim_deferred_payout
+    pub deferred_payouts: u64,
+
+    /// Rebate pool: house edge rebates credited to players and not yet claimed
+    /// Stays in the vault until claimed through claim_rebate
+    pub rebate_pool: u64,
+
+    /// Referral rewards credited to referrers and not yet claimed
+    /// Stays in the vault until claimed through claim_referral
+    pub referral_credits: u64,
+
+    /// Resolved rolls counted by tenth of the bet's dice range, lowest first
+    /// Published through RollDistribution every ROLL_DISTRIBUTION_INTERVAL rolls
+    pub roll_deciles: [u64; 10],
+
+    /// PDA bump for this stats account
+    pub bump: u8,
+}
+
+impl HouseStats {
+    /// Count a settled bet as a win, a loss or (neither) a push, and add `payout`
+    /// to the total paid out
+    pub fn record_outcome(&mut self, won: bool, push: bool, payout: u64) -> Result<()> {
+        if won {
+            self.wins = self.wins.checked_add(1).ok_or(DiceError::Overflow)?;
+        } else if !push {
+            self.losses = self.losses.checked_add(1).ok_or(DiceError::Overflow)?;
+        }
+        self.total_payout = self
+            .total_payout
+            .checked_add(payout)
+            .ok_or(DiceError::Overflow)?;
+        Ok(())
+    }
+
+    /// Whether adding `payout` to the total paid out reaches `cap` for the first time
+    ///
+    /// A `cap` of 0 means no cap. Only the crossing counts, so once the operator
+    /// resumes a game paused by the breaker, later payouts do not pause it again.
+    pub fn crosses_payout_cap(&self, payout: u64, cap: u64) -> bool {
+        cap != 0 && self.total_payout < cap && self.total_payout.saturating_add(payout) >= cap
+    }
+
+    /// Count a resolved `roll` (1-range) in its decile of `range`
+    ///
+    /// Returns true when the total count reaches a multiple of
+    /// ROLL_DISTRIBUTION_INTERVAL, i.e. when a RollDistribution event is due.
+    pub fn record_roll(&mut self, roll: u16, range: u16) -> bool {
+        let decile = (roll.saturating_sub(1) as usize * 10 / range.max(1) as usize).min(9);
+        self.roll_deciles[decile] = self.roll_deciles[decile].saturating_add(1);
+
+        let total = self
+            .roll_deciles
+            .iter()
+            .fold(0u64, |total, count| total.saturating_add(*count));
+        ROLL_DISTRIBUTION_INTERVAL != 0 && total % ROLL_DISTRIBUTION_INTERVAL == 0
+    }
+
+    /// Add a bet's JACKPOT_BPS contribution to the jackpot pool
+    pub fn fund_jackpot(&mut self, contribution: u64) -> Result<()> {
+        self.jackpot_pool = self
+            .jackpot_pool
+            .checked_add(contribution)
+            .ok_or(DiceError::Overflow)?;
+        Ok(())
+    }
+
+    /// Whether a resolved `roll` wins the jackpot: JACKPOT_ROLL while the pool holds funds
+    pub fn hits_jackpot(&self, roll: u16) -> bool {
+        roll == JACKPOT_ROLL && self.jackpot_pool > 0
+    }
+
+    /// Pay out the jackpot, limited to the `available` lamports of the pool account
+    ///
+    /// Returns the lamports won and resets the pool, unless nothing can be paid.
+    pub fn take_jackpot(&mut self, available: u64) -> u64 {
+        let jackpot = self.jackpot_pool.min(available);
+        if jackpot > 0 {
+            self.jackpot_pool = 0;
+        }
+        jackpot
+    }
+}
+
+/// Player Stats Account - Betting history summary for a single player in one game
+///
+/// PDA with seeds: ["player_stats", house_pubkey, player_pubkey]
+///
+/// Created with the player's first bet and updated on every placement and
+/// resolution, so players can see their record without scanning transactions.
+#[account]
+#[derive(InitSpace)]
+pub struct PlayerStats {
+    /// Player these statistics belong to
+    pub player: Pubkey,
+
+    /// Number of bets placed
+    pub bets_placed: u64,
+
+    /// Total amount wagered
+    pub total_wagered: u64,
+
+    /// Total amount paid out on winning bets
+    pub total_won: u64,
+
+    /// Largest single winning payout
+    pub biggest_win: u64,
+
+    /// Consecutive wins, reset to 0 by a loss
+    pub current_streak: u32,
+
+    /// Bets placed but not yet settled, capped by MAX_OPEN_BETS_PER_PLAYER
+    pub open_bets: u16,
+
+    /// First slot of the current payout ceiling window
+    pub window_start_slot: u64,
+
+    /// Lamports paid out to the player in the current window
+    pub window_paid: u64,
+
+    /// Winnings above the payout ceiling, still held by the vault for the player
+    /// Collected through claim_deferred_payout as later windows allow
+    pub deferred_payout: u64,
+
+    /// House edge rebates credited to the player and not yet claimed, in lamports
+    /// Held by the vault until claim_rebate
+    pub rebate_unclaimed: u64,
+
+    /// First slot of the current refund cap window
+    pub refund_window_start_slot: u64,
+
+    /// Refunds claimed in the current window, capped by MAX_REFUNDS_PER_WINDOW
+    pub refunds_in_window: u16,
+
+    /// Sub-lamport payout remainder banked for the player, in PAYOUT_SCALE units
+    /// Only used with SCALED_PAYOUT_ACCOUNTING; always below PAYOUT_SCALE
+    pub payout_remainder: u64,
+
+    /// PDA bump for this stats account
+    pub bump: u8,
+}
+
+impl PlayerStats {
+    /// Part of a payout of `amount` the player may receive now under `ceiling`
+    ///
+    /// Starts a new window once PAYOUT_WINDOW_SLOTS have passed since the current
+    /// one began, and counts the returned part against the window. A `ceiling`
+    /// of 0 means no ceiling: the whole amount is returned and nothing is counted.
+    pub fn take_payout_allowance(&mut self, amount: u64, slot: u64, ceiling: u64) -> u64 {
+        if ceiling == 0 {
+            return amount;
+        }
+        if slot.saturating_sub(self.window_start_slot) >= PAYOUT_WINDOW_SLOTS {
+            self.window_start_slot = slot;
+            self.window_paid = 0;
+        }
+        let allowed = amount.min(ceiling.saturating_sub(self.window_paid));
+        self.window_paid = self.window_paid.saturating_add(allowed);
+        allowed
+    }
+
+    /// Count a refund at `slot` against the player's refund cap of `cap` per window
+    ///
+    /// Starts a new window once REFUND_WINDOW_SLOTS have passed since the current
+    /// one began. Fails with `TooManyRefunds` once `cap` refunds were counted in
+    /// the window; a `cap` of 0 means no cap. A timed-out bet can no longer be
+    /// resolved, so a refund held back for a whole window (`refundable_for` slots
+    /// since the bet became refundable) goes through uncounted instead of
+    /// queueing behind the player's later refunds.
+    pub fn record_refund(&mut self, slot: u64, refundable_for: u64, cap: u16) -> Result<()> {
+        if cap == 0 || refundable_for >= REFUND_WINDOW_SLOTS {
+            return Ok(());
+        }
+        if slot.saturating_sub(self.refund_window_start_slot) >= REFUND_WINDOW_SLOTS {
+            self.refund_window_start_slot = slot;
+            self.refunds_in_window = 0;
+        }
+        require!(self.refunds_in_window < cap, DiceError::TooManyRefunds);
+        self.refunds_in_window += 1;
+        Ok(())
+    }
+
+    /// Count a settled bet: a win adds its `payout` and extends the streak, a
+    /// loss resets the streak, and a push leaves both unchanged
+    pub fn record_outcome(&mut self, won: bool, push: bool, payout: u64) -> Result<()> {
+        if won {
+            self.total_won = self
+                .total_won
+                .checked_add(payout)
+                .ok_or(DiceError::Overflow)?;
+            self.biggest_win = self.biggest_win.max(payout);
+            self.current_streak = self.current_streak.saturating_add(1);
+        } else if !push {
+            self.current_streak = 0;
+        }
+        Ok(())
+    }
+}
+
+/// Winnings - Escrow of a player's resolved but unclaimed payouts
+///
+/// Created with the player's first bet placed while PULL_PAYOUTS is set; push
+/// mode never creates it. Resolution moves payouts here and claim_winnings
+/// releases them.
+///
+/// Seeds: ["winnings", player_pubkey]
+#[account]
+#[derive(InitSpace)]
+pub struct Winnings {
+    /// Player the escrowed winnings belong to
+    pub player: Pubkey,
+
+    /// Lamports escrowed above the account's rent-exempt minimum
+    pub unclaimed: u64,
+
+    /// PDA bump for this escrow account
+    pub bump: u8,
+}
+
+impl Winnings {
+    /// Escrow a resolved payout of `amount` until the player claims it
+    pub fn credit(&mut self, amount: u64) -> Result<()> {
+        self.unclaimed = self
+            .unclaimed
+            .checked_add(amount)
+            .ok_or(DiceError::Overflow)?;
+        Ok(())
+    }
+
+    /// Release everything escrowed so far, returning the amount to pay out
+    pub fn take_unclaimed(&mut self) -> u64 {
+        std::mem::take(&mut self.unclaimed)
+    }
+}
+
+/// Referral Earnings - Rewards accrued by a referrer in one game
+///
+/// Created by register_referrer, credited at resolution of referred bets and
+/// paid out from the vault by claim_referral.
+///
+/// Seeds: ["referral", house_pubkey, referrer_pubkey]
+#[account]
+#[derive(InitSpace)]
+pub struct ReferralEarnings {
+    /// House authority of the game the rewards are owed by
+    pub house: Pubkey,
+
+    /// Referrer the rewards belong to
+    pub referrer: Pubkey,
+
+    /// Rewards credited but not yet claimed, in lamports
+    pub unclaimed: u64,
+
+    /// All rewards ever credited, in lamports
+    pub total_earned: u64,
+
+    /// PDA bump for this earnings account
+    pub bump: u8,
+}
+
+/// Vault LP - A liquidity provider's stake in one game's vault
+///
+/// Created by the provider's first fund_vault deposit. Shares are worth the
+/// same fraction of the vault's available balance as they are of
+/// `HouseStats::lp_shares`, so they grow and shrink with the house's results.
+///
+/// Seeds: ["lp", house_pubkey, provider_pubkey]
+#[account]
+#[derive(InitSpace)]
+pub struct VaultLp {
+    /// House authority of the game the shares are in
+    pub house: Pubkey,
+
+    /// Provider the shares belong to
+    pub provider: Pubkey,
+
+    /// Shares held, redeemed through withdraw_lp
+    pub shares: u64,
+
+    /// PDA bump for this LP account
+    pub bump: u8,
+}
+
+/// Edge Tier - House edge applied to stakes of at least a minimum amount
+///
+/// Stored in GameConfig::edge_tiers; see `GameConfig::house_edge_for`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct EdgeTier {
+    /// Smallest stake the tier applies to, in lamports or the mint's base units
+    pub min_amount: u64,
+
+    /// House edge in basis points for stakes in this tier
+    pub house_edge_bp: u16,
+}
+
+/// Payout Quote - Returned by quote_payout for a prospective bet
+///
+/// Not an account: it is serialized into the transaction return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PayoutQuote {
+    /// Lamports a win would pay, including the returned stake
+    pub payout: u64,
+
+    /// Chance of winning in basis points (4900 = 49%)
+    pub win_probability_bp: u16,
+}
+
+/// Fairness Check - Returned by verify_fairness for a past resolution
+///
+/// Not an account: it is serialized into the transaction return data.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct FairnessCheck {
+    /// Entropy hashed from the signatures
+    pub entropy: [u8; 32],
+
+    /// Rolled value derived from the entropy
+    pub roll: u16,
+
+    /// Whether the roll wins for the given prediction and direction
+    pub won: bool,
+
+    /// Base payout of the odds formula and dust policy (0 on a loss)
+    pub payout: u64,
+}
+
+/// Resolution Outcome - Returned by the resolve instructions for the resolved bet
+///
+/// Not an account: it is serialized into the transaction return data, so the
+/// submitter reads the outcome from the transaction meta without parsing events.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ResolutionOutcome {
+    /// Rolled value (1-roll_range)
+    pub roll: u16,
+
+    /// Whether the roll won for the bet's prediction and direction
+    pub won: bool,
+
+    /// Amount paid to the player, escrowed, or deferred by a dispute window
+    pub payout: u64,
+}
+
+/// Payout Rounding - How the payout formula rounds its final division
+///
+/// Stored in GameConfig and changed through set_payout_rounding.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum PayoutRounding {
+    /// Round down; the remainder stays with the house (at most 1 base unit per payout)
+    Floor,
+
+    /// Round to the nearest base unit, halves up (off by at most half a unit either way)
+    Nearest,
+}
+
+/// Bet Direction - Which side of the prediction wins
+///
+/// Chosen by the player at placement and stored on the bet.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum BetDirection {
+    /// Win if the roll is below the prediction (the classic mode)
+    Under,
+
+    /// Win if the roll is above the prediction
+    Over,
+}
+
+impl BetDirection {
+    /// Whether `roll` wins against `prediction` in this direction
+    /// Strict in both directions: a roll equal to the prediction (a tie) never
+    /// wins, see `GameConfig::tie_is_push` for how it settles
+    pub fn wins(&self, prediction: u16, roll: u16) -> bool {
+        match self {
+            BetDirection::Under => roll < prediction,
+            BetDirection::Over => roll > prediction,
+        }
+    }
+
+    /// Number of rolls in 1-`range` that win against `prediction`
+    /// Under wins on 1..prediction-1, over on prediction+1..range
+    pub fn winning_outcomes(&self, prediction: u16, range: u16) -> u16 {
+        match self {
+            BetDirection::Under => prediction.saturating_sub(1),
+            BetDirection::Over => range.saturating_sub(prediction),
+        }
+    }
+}
+
+/// Dust Payout Policy - How a winning payout below the dust threshold is settled
+///
+/// Selected at compile time through `DUST_PAYOUT_POLICY` so winners never
+/// receive an economically meaningless amount.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DustPayoutPolicy {
+    /// Raise the payout to MIN_PAYOUT_LAMPORTS, funded by the vault
+    RoundUp,
+
+    /// Return the original stake instead of the dust payout
+    RefundStake,
+}
+
+impl DustPayoutPolicy {
+    /// Amount paid for a winning `payout` of `stake`
+    /// Payouts of at least MIN_PAYOUT_LAMPORTS are paid unchanged; smaller ones
+    /// are rounded up or replaced by the stake
+    pub fn settle(&self, payout: u64, stake: u64) -> u64 {
+        if payout >= MIN_PAYOUT_LAMPORTS {
+            return payout;
+        }
+        match self {
+            DustPayoutPolicy::RoundUp => MIN_PAYOUT_LAMPORTS,
+            DustPayoutPolicy::RefundStake => stake,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player_stats() -> PlayerStats {
+        PlayerStats {
+            player: Pubkey::new_unique(),
+            bets_placed: 0,
+            total_wagered: 0,
+            total_won: 0,
+            biggest_win: 0,
+            current_streak: 0,
+            open_bets: 0,
+            window_start_slot: 0,
+            window_paid: 0,
+            deferred_payout: 0,
+            rebate_unclaimed: 0,
+            refund_window_start_slot: 0,
+            refunds_in_window: 0,
+            payout_remainder: 0,
+            bump: 255,
+        }
+    }
+
+    fn game_config(max_bet: u64) -> GameConfig {
+        GameConfig {
+            house: Pubkey::new_unique(),
+            authority: Pubkey::new_unique(),
+            house_edge_bp: crate::HOUSE_EDGE,
+            min_bet: crate::MIN_BET_LAMPORTS,
+            max_bet,
+            roll_range: crate::ROLL_RANGE,
+            min_roll: crate::MIN_ROLL,
+            max_roll: crate::MAX_ROLL,
+            mint: Pubkey::default(),
+            refund_timeout_slots: crate::REFUND_TIMEOUT_SLOTS,
+            cancel_fee_bps: crate::CANCEL_FEE_BPS,
+            is_paused: false,
+            is_closing: false,
+            payout_rounding: PayoutRounding::Floor,
+            tie_is_push: false,
+            treasury: None,
+            treasury_bps: 0,
+            authorities: Vec::new(),
+            edge_tiers: Vec::new(),
+            created_slot: 1_000,
+            vault_bump: 255,
+            bump: 255,
+        }
+    }
+
+    fn house_stats() -> HouseStats {
+        HouseStats {
+            house: Pubkey::new_unique(),
+            total_bets: 0,
+            total_volume: 0,
+            total_payout: 0,
+            wins: 0,
+            losses: 0,
+            jackpot_pool: 0,
+            lp_shares: 0,
+            house_shares: 0,
+            deferred_payouts: 0,
+            rebate_pool: 0,
+            referral_credits: 0,
+            roll_deciles: [0; 10],
+            bump: 255,
+        }
+    }
+
+    fn bet() -> Bet {
+        Bet {
+            amount: crate::MIN_BET_LAMPORTS,
+            donation: 0,
+            player: Pubkey::new_unique(),
+            house: Pubkey::new_unique(),
+            slot: 1_000,
+            seed: 1,
+            roll: 50,
+            roll_range: crate::ROLL_RANGE,
+            direction: BetDirection::Under,
+            house_edge_bp: crate::HOUSE_EDGE,
+            tie_is_push: false,
+            bump: 255,
+            randomness_account: Pubkey::new_unique(),
+            randomness_data_len: 0,
+            commit_slot: 1_000,
+            is_resolved: false,
+            payout: 0,
+            resolved_slot: 0,
+            rolled_value: 0,
+            won: false,
+            insured: false,
+            min_acceptable_payout: 0,
+            mint: Pubkey::default(),
+            committed_hash: [0; 32],
+            vrf: false,
+            referrer: None,
+        }
+    }
+
+    #[test]
+    fn payout_allowance_without_ceiling_is_unlimited() {
+        let mut stats = player_stats();
+        assert_eq!(stats.take_payout_allowance(u64::MAX, 10, 0), u64::MAX);
+        assert_eq!(stats.window_paid, 0);
+    }
+
+    #[test]
+    fn payout_allowance_stops_at_the_ceiling() {
+        let mut stats = player_stats();
+        assert_eq!(stats.take_payout_allowance(600, 10, 1_000), 600);
+        assert_eq!(stats.take_payout_allowance(600, 20, 1_000), 400);
+        assert_eq!(stats.take_payout_allowance(600, 30, 1_000), 0);
+        assert_eq!(stats.window_paid, 1_000);
+    }
+
+    #[test]
+    fn payout_allowance_resets_with_a_new_window() {
+        let mut stats = player_stats();
+        stats.window_start_slot = 10;
+        assert_eq!(stats.take_payout_allowance(1_500, 10, 1_000), 1_000);
+        assert_eq!(
+            stats.take_payout_allowance(1_500, 10 + PAYOUT_WINDOW_SLOTS - 1, 1_000),
+            0
+        );
+        assert_eq!(
+            stats.take_payout_allowance(1_500, 10 + PAYOUT_WINDOW_SLOTS, 1_000),
+            1_000
+        );
+        assert_eq!(stats.window_start_slot, 10 + PAYOUT_WINDOW_SLOTS);
+    }
+
+    #[test]
+    fn refund_cap_rejects_refunds_beyond_the_cap() {
+        let mut stats = player_stats();
+        assert!(stats.record_refund(10, 0, 2).is_ok());
+        assert!(stats.record_refund(20, 0, 2).is_ok());
+        assert_eq!(
+            stats.record_refund(30, 0, 2).unwrap_err(),
+            DiceError::TooManyRefunds.into()
+        );
+        assert_eq!(stats.refunds_in_window, 2);
+    }
+
+    #[test]
+    fn refund_cap_resets_with_a_new_window() {
+        let mut stats = player_stats();
+        stats.refund_window_start_slot = 10;
+        assert!(stats.record_refund(10, 0, 1).is_ok());
+        assert!(stats
+            .record_refund(10 + REFUND_WINDOW_SLOTS - 1, 0, 1)
+            .is_err());
+        assert!(stats.record_refund(10 + REFUND_WINDOW_SLOTS, 0, 1).is_ok());
+        assert_eq!(stats.refund_window_start_slot, 10 + REFUND_WINDOW_SLOTS);
+    }
+
+    #[test]
+    fn refund_cap_of_zero_is_unlimited() {
+        let mut stats = player_stats();
+        for slot in 0..100 {
+            assert!(stats.record_refund(slot, 0, 0).is_ok());
+        }
+        assert_eq!(stats.refunds_in_window, 0);
+    }
+
+    #[test]
+    fn refund_held_back_for_a_whole_window_is_not_capped() {
+        let mut stats = player_stats();
+        stats.refund_window_start_slot = 10;
+        assert!(stats.record_refund(10, 0, 1).is_ok());
+
+        // A timed-out bet cannot be resolved, so it waits at most one window
+        assert_eq!(
+            stats
+                .record_refund(20, REFUND_WINDOW_SLOTS - 1, 1)
+                .unwrap_err(),
+            DiceError::TooManyRefunds.into()
+        );
+        assert!(stats.record_refund(20, REFUND_WINDOW_SLOTS, 1).is_ok());
+        assert_eq!(stats.refunds_in_window, 1);
+    }
+
+    #[test]
+    fn player_outcomes_track_winnings_and_streak() {
+        let mut stats = player_stats();
+        stats.record_outcome(true, false, 300).unwrap();
+        stats.record_outcome(true, false, 500).unwrap();
+        stats.record_outcome(false, true, 100).unwrap();
+        assert_eq!(stats.total_won, 800);
+        assert_eq!(stats.biggest_win, 500);
+        assert_eq!(stats.current_streak, 2);
+
+        stats.record_outcome(false, false, 0).unwrap();
+        assert_eq!(stats.current_streak, 0);
+        assert_eq!(stats.total_won, 800);
+    }
+
+    #[test]
+    fn house_outcomes_count_wins_and_losses_but_not_pushes() {
+        let mut stats = house_stats();
+        stats.record_outcome(true, false, 300).unwrap();
+        stats.record_outcome(false, false, 0).unwrap();
+        stats.record_outcome(false, true, 100).unwrap();
+        assert_eq!(stats.wins, 1);
+        assert_eq!(stats.losses, 1);
+        assert_eq!(stats.total_payout, 400);
+
+        stats.total_payout = u64::MAX;
+        assert_eq!(
+            stats.record_outcome(true, false, 1).unwrap_err(),
+            DiceError::Overflow.into()
+        );
+    }
+
+    #[test]
+    fn roll_deciles_count_each_tenth_of_the_range() {
+        let mut stats = house_stats();
+        for roll in [1, 10, 11, 55, 90, 91, 100] {
+            stats.record_roll(roll, 100);
+        }
+        assert_eq!(stats.roll_deciles, [2, 1, 0, 0, 0, 1, 0, 0, 1, 2]);
+
+        // Ranges not divisible by 10 still spread over all ten deciles
+        stats.roll_deciles = [0; 10];
+        for roll in 1..=6 {
+            stats.record_roll(roll, 6);
+        }
+        assert_eq!(stats.roll_deciles, [1, 1, 0, 1, 0, 1, 1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn roll_distribution_is_due_every_interval() {
+        let mut stats = house_stats();
+        let due = (1..=2 * ROLL_DISTRIBUTION_INTERVAL)
+            .filter(|count| stats.record_roll((*count % 100) as u16 + 1, 100))
+            .count();
+        assert_eq!(due, 2);
+        assert_eq!(
+            stats.roll_deciles.iter().sum::<u64>(),
+            2 * ROLL_DISTRIBUTION_INTERVAL
+        );
+    }
+
+    #[test]
+    fn jackpot_accumulates_until_won_on_the_jackpot_roll() {
+        let mut stats = house_stats();
+        assert!(!stats.hits_jackpot(JACKPOT_ROLL));
+
+        for _ in 0..3 {
+            stats.fund_jackpot(100).unwrap();
+        }
+        assert_eq!(stats.jackpot_pool, 300);
+        assert!(!stats.hits_jackpot(JACKPOT_ROLL + 1));
+        assert!(stats.hits_jackpot(JACKPOT_ROLL));
+
+        // An empty pool account pays nothing and keeps the pool for the next winner
+        assert_eq!(stats.take_jackpot(0), 0);
+        assert_eq!(stats.jackpot_pool, 300);
+
+        assert_eq!(stats.take_jackpot(1_000), 300);
+        assert_eq!(stats.jackpot_pool, 0);
+        assert!(!stats.hits_jackpot(JACKPOT_ROLL));
+    }
+
+    #[test]
+    fn payout_cap_trips_only_when_crossed() {
+        let mut stats = house_stats();
+        assert!(!stats.crosses_payout_cap(999, 1_000));
+        stats.record_outcome(true, false, 999).unwrap();
+
+        // The payout taking the total to the cap trips the breaker
+        assert!(stats.crosses_payout_cap(1, 1_000));
+        stats.record_outcome(true, false, 1).unwrap();
+
+        // Past the cap, further payouts never trip it again
+        assert!(!stats.crosses_payout_cap(500, 1_000));
+        assert!(!stats.crosses_payout_cap(u64::MAX, 0));
+    }
+
+    #[test]
+    fn worst_case_payout_follows_the_game_max_bet() {
+        // The default game pays MAX_PAYOUT_LAMPORTS: 10 SOL under roll 2, 985 SOL
+        let config = game_config(crate::MAX_BET_LAMPORTS);
+        assert_eq!(
+            config.worst_case_payout().unwrap(),
+            crate::MAX_PAYOUT_LAMPORTS
+        );
+
+        // A 0.1 SOL max bet needs 9.85 SOL of funding instead
+        let config = game_config(100_000_000);
+        assert_eq!(config.worst_case_payout().unwrap(), 9_850_000_000);
+    }
+
+    #[test]
+    fn roll_2_win_pays_the_largest_multiplier_the_vault_is_funded_for() {
+        let mut config = game_config(2_000_000_000);
+        config.house_edge_bp = 300;
+
+        // Roll 2 divides by (roll - 1) = 1: amount * (10000 - edge) / 100
+        for amount in [crate::MIN_BET_LAMPORTS, 123_456_789, config.max_bet] {
+            let payout = calculate_payout(
+                amount,
+                2,
+                100,
+                BetDirection::Under,
+                config.house_edge_bp,
+                PayoutRounding::Floor,
+            )
+            .unwrap();
+            assert_eq!(payout, amount * (10000 - 300) / 100);
+        }
+
+        // The solvency check funds exactly that payout on the max bet
+        assert_eq!(config.worst_case_payout().unwrap(), 194_000_000_000);
+    }
+
+    #[test]
+    fn both_directions_win_strictly_and_pay_by_their_odds() {
+        let (under, over) = (BetDirection::Under, BetDirection::Over);
+        assert!(under.wins(50, 49) && !under.wins(50, 50) && !under.wins(50, 51));
+        assert!(over.wins(50, 51) && !over.wins(50, 50) && !over.wins(50, 49));
+
+        // Under 50 wins on 1-49, over 50 on 51-100
+        assert_eq!(under.winning_outcomes(50, 100), 49);
+        assert_eq!(over.winning_outcomes(50, 100), 50);
+        let payout = |roll, direction| {
+            calculate_payout(
+                1_000_000_000,
+                roll,
+                100,
+                direction,
+                150,
+                PayoutRounding::Floor,
+            )
+            .unwrap()
+        };
+        assert_eq!(payout(50, under), 2_010_204_081);
+        assert_eq!(payout(50, over), 1_970_000_000);
+
+        // Mirrored predictions have the same odds and pay the same
+        assert_eq!(payout(51, over), payout(50, under));
+        assert_eq!(payout(96, over), payout(5, under));
+    }
+
+    #[test]
+    fn edge_tiers_discount_larger_stakes() {
+        let sol = 1_000_000_000;
+        let mut config = game_config(100 * sol);
+        config.edge_tiers = vec![
+            EdgeTier {
+                min_amount: sol,
+                house_edge_bp: 100,
+            },
+            EdgeTier {
+                min_amount: 10 * sol,
+                house_edge_bp: 50,
+            },
+        ];
+
+        // Below the first tier the base edge applies; each tier starts at its minimum
+        assert_eq!(config.house_edge_for(sol - 1), 150);
+        assert_eq!(config.house_edge_for(sol), 100);
+        assert_eq!(config.house_edge_for(10 * sol - 1), 100);
+        assert_eq!(config.house_edge_for(10 * sol), 50);
+
+        // Under 50 of 100, the discount shows in the multiplier
+        let payout = |amount| {
+            calculate_payout(
+                amount,
+                50,
+                100,
+                BetDirection::Under,
+                config.house_edge_for(amount),
+                PayoutRounding::Floor,
+            )
+            .unwrap()
+        };
+        assert_eq!(payout(sol / 2), 1_005_102_040);
+        assert_eq!(payout(10 * sol), 20_306_122_448);
+    }
+
+    #[test]
+    fn worst_case_payout_covers_rolling_over() {
+        // Over 96 of 100 wins on 4 rolls; under 26 of 100 wins on 25
+        let mut config = game_config(100_000_000);
+        config.min_roll = 26;
+        assert_eq!(config.worst_case_payout().unwrap(), 2_462_500_000);
+
+        // A max roll at the top of the range is capped at the last winnable prediction
+        config.max_roll = 100;
+        assert_eq!(config.worst_case_payout().unwrap(), 9_850_000_000);
+    }
+
+    #[test]
+    fn game_age_gates_config_changes() {
+        let config = game_config(crate::MAX_BET_LAMPORTS);
+        assert!(!config.has_run_for(1_000, 500));
+        assert!(!config.has_run_for(1_499, 500));
+        assert!(config.has_run_for(1_500, 500));
+
+        // No minimum age allows changes from the creation slot on
+        assert!(config.has_run_for(1_000, 0));
+    }
+
+    #[test]
+    fn commit_slot_in_the_future_fails_with_invalid_slot() {
+        let mut bet = bet();
+        bet.commit_slot = 1_000;
+        assert_eq!(bet.slots_since_commit(1_000).unwrap(), 0);
+        assert_eq!(bet.slots_since_commit(1_150).unwrap(), 150);
+
+        // A clock behind the commit slot, as after a fork rollback
+        assert_eq!(
+            bet.slots_since_commit(999).unwrap_err(),
+            DiceError::InvalidSlot.into()
+        );
+    }
+
+    #[test]
+    fn bets_without_a_commit_slot_fail_with_bet_not_committed() {
+        let mut bet = bet();
+        bet.commit_slot = 0;
+        assert_eq!(
+            bet.slots_since_commit(1_000).unwrap_err(),
+            DiceError::BetNotCommitted.into()
+        );
+    }
+
+    #[test]
+    fn bets_resolve_only_after_the_placement_slot_and_before_the_timeout() {
+        let mut bet = bet();
+        bet.commit_slot = 1_000;
+
+        // Resolving in the placement slot, e.g. in the placement's own transaction
+        assert_eq!(
+            bet.require_resolvable_at(1_000, 150).unwrap_err(),
+            DiceError::TooEarly.into()
+        );
+        bet.require_resolvable_at(1_000 + MIN_RESOLVE_DELAY_SLOTS, 150)
+            .unwrap();
+        bet.require_resolvable_at(1_149, 150).unwrap();
+        assert_eq!(
+            bet.require_resolvable_at(1_150, 150).unwrap_err(),
+            DiceError::RandomnessExpired.into()
+        );
+    }
+
+    #[test]
+    fn bets_without_a_winning_roll_fail_with_minimum_roll() {
+        let mut bet = bet();
+        bet.roll = 1;
+        assert_eq!(
+            bet.require_winnable().unwrap_err(),
+            DiceError::MinimumRoll.into()
+        );
+        bet.roll = 2;
+        assert!(bet.require_winnable().is_ok());
+
+        // Rolling over the top of the range cannot win either
+        bet.direction = BetDirection::Over;
+        bet.roll = bet.roll_range;
+        assert_eq!(
+            bet.require_winnable().unwrap_err(),
+            DiceError::MinimumRoll.into()
+        );
+        bet.roll = bet.roll_range - 1;
+        assert!(bet.require_winnable().is_ok());
+    }
+
+    #[test]
+    fn committed_bets_require_the_reveal() {
+        let mut bet = bet();
+        assert!(bet.require_no_commitment().is_ok());
+
+        // Any non-zero commitment closes the signature, oracle and VRF paths
+        bet.committed_hash[31] = 1;
+        assert_eq!(
+            bet.require_no_commitment().unwrap_err(),
+            DiceError::RevealRequired.into()
+        );
+    }
+
+    #[test]
+    fn vrf_bets_resolve_only_through_vrf() {
+        let mut bet = bet();
+        assert!(bet.require_resolution_method(false).is_ok());
+        assert_eq!(
+            bet.require_resolution_method(true).unwrap_err(),
+            DiceError::ResolutionMethodMismatch.into()
+        );
+
+        // Opting into VRF at placement closes the signature, oracle and reveal paths
+        bet.vrf = true;
+        assert!(bet.require_resolution_method(true).is_ok());
+        assert_eq!(
+            bet.require_resolution_method(false).unwrap_err(),
+            DiceError::ResolutionMethodMismatch.into()
+        );
+    }
+
+    #[test]
+    fn winnings_escrow_releases_every_resolved_payout_once() {
+        let mut winnings = Winnings {
+            player: Pubkey::new_unique(),
+            unclaimed: 0,
+            bump: 255,
+        };
+
+        // Two resolutions escrow their payouts, one claim releases both
+        winnings.credit(19_700_000).unwrap();
+        winnings.credit(1_000_000).unwrap();
+        assert_eq!(winnings.take_unclaimed(), 20_700_000);
+
+        // A second claim has nothing left to release
+        assert_eq!(winnings.take_unclaimed(), 0);
+
+        winnings.unclaimed = u64::MAX;
+        assert_eq!(winnings.credit(1).unwrap_err(), DiceError::Overflow.into());
+    }
+
+    #[test]
+    fn bet_message_is_a_hash_of_the_bet_address_and_data() {
+        let bet = bet();
+        let key = Pubkey::new_unique();
+        let message = crate::utils::bet_message(&key, &bet).unwrap();
+
+        // One hash per bet keeps MAX_BATCH_RESOLVE Ed25519 instructions in a transaction
+        assert_eq!(message.len(), 32);
+        assert_eq!(
+            message,
+            solana_program::hash::hashv(&[key.as_ref(), &bet.try_to_vec().unwrap()]).to_bytes()
+        );
+        assert_ne!(
+            message,
+            crate::utils::bet_message(&Pubkey::new_unique(), &bet).unwrap()
+        );
+    }
+
+    #[test]
+    fn oracle_message_binds_the_roll_to_the_bet() {
+        let bet = bet();
+        let key = Pubkey::new_unique();
+        let message = crate::utils::oracle_message(&key, &bet, 42).unwrap();
+
+        // The bet message followed by the roll, so a signed roll fits one bet only
+        let mut expected = crate::utils::bet_message(&key, &bet).unwrap();
+        expected.extend_from_slice(&42u16.to_le_bytes());
+        assert_eq!(message, expected);
+        assert_ne!(
+            message,
+            crate::utils::oracle_message(&key, &bet, 43).unwrap()
+        );
+        assert_ne!(
+            message,
+            crate::utils::oracle_message(&Pubkey::new_unique(), &bet, 42).unwrap()
+        );
+    }
+
+    #[test]
+    fn dust_payouts_are_rounded_up_or_refunded() {
+        let stake = crate::MIN_BET_LAMPORTS;
+        for policy in [DustPayoutPolicy::RoundUp, DustPayoutPolicy::RefundStake] {
+            assert_eq!(
+                policy.settle(MIN_PAYOUT_LAMPORTS, stake),
+                MIN_PAYOUT_LAMPORTS
+            );
+            assert_eq!(policy.settle(2 * stake, stake), 2 * stake);
+        }
+        assert_eq!(
+            DustPayoutPolicy::RoundUp.settle(MIN_PAYOUT_LAMPORTS - 1, stake),
+            MIN_PAYOUT_LAMPORTS
+        );
+        assert_eq!(
+            DustPayoutPolicy::RefundStake.settle(MIN_PAYOUT_LAMPORTS - 1, stake),
+            stake
+        );
+    }
 }
@@ -0,0 +1,1677 @@
+use anchor_instruction_sysvar::Ed25519InstructionSignatures;
+use anchor_lang::prelude::*;
+use solana_program::{
+    ed25519_program,
+    hash::{hashv, Hasher},
+    instruction::Instruction,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+use crate::{
+    error::DiceError, Bet, BetDirection, BetTracker, HouseStats, PayoutRounding, ENTROPY_SIGNERS,
+    ENTROPY_SIGNER_THRESHOLD, INSURANCE_COVER_BPS, MAX_PAYOUT_VAULT_BPS,
+    MIN_VAULT_RESERVE_LAMPORTS, ORACLE_AUTHORITY, REQUIRE_PLAYER_SIGNATURE, SWITCHBOARD_PROGRAM_ID,
+};
+
+/// Ensure a split recipient is not the house vault itself
+///
+/// # Arguments
+/// * `recipient` - Account that is about to receive a cut of a bet (treasury, referrer, keeper, ...)
+/// * `vault` - The house vault PDA (or vault token account) the cut is paid out of
+///
+/// # Returns
+/// * `Result<()>` - Success or `VaultAsRecipient` error
+///
+/// # Why
+/// Routing a cut from the vault back into the vault is a no-op self-transfer:
+/// the split would be reported as paid while the lamports silently stay as
+/// house profit. Every instruction that pays a third party out of the vault
+/// must call this before the transfer.
+pub fn require_not_vault(recipient: &Pubkey, vault: &Pubkey) -> Result<()> {
+    require_keys_neq!(*recipient, *vault, DiceError::VaultAsRecipient);
+    Ok(())
+}
+
+/// Count the distinct byte values in the low 8 bytes of a bet seed
+///
+/// # Arguments
+/// * `seed` - The player-supplied bet seed
+///
+/// # Returns
+/// * `u8` - Number of distinct byte values (1-8)
+pub fn seed_distinct_bytes(seed: u128) -> u8 {
+    let bytes = seed.to_le_bytes();
+    let low = &bytes[..8];
+
+    low.iter()
+        .enumerate()
+        .filter(|(i, b)| !low[..*i].contains(b))
+        .count() as u8
+}
+
+/// Lamports the vault can pay out without dropping below its balance floor
+///
+/// # Arguments
+/// * `vault` - The house vault PDA
+///
+/// # Returns
+/// * `Result<u64>` - Spendable balance in lamports
+///
+/// # Balance Floor
+/// The floor is the rent-exempt minimum plus MIN_VAULT_RESERVE_LAMPORTS.
+/// Every instruction moving lamports out of the vault must check against this.
+pub fn vault_available_lamports(vault: &AccountInfo) -> Result<u64> {
+    let reserve = Rent::get()?
+        .minimum_balance(vault.data_len())
+        .saturating_add(MIN_VAULT_RESERVE_LAMPORTS);
+    Ok(vault.lamports().saturating_sub(reserve))
+}
+
+/// Largest winning payout a bet may carry against a vault balance
+///
+/// # Arguments
+/// * `vault_balance` - Spendable vault balance (lamports, or token base units for token games)
+///
+/// # Returns
+/// * `u64` - MAX_PAYOUT_VAULT_BPS of the balance, rounded down
+pub fn max_payout_for_vault(vault_balance: u64) -> u64 {
+    (vault_balance as u128 * MAX_PAYOUT_VAULT_BPS as u128 / 10000) as u64
+}
+
+/// Lamports in the vault already owed to players and referrers
+///
+/// # Arguments
+/// * `tracker` - The game's tracker, summing the stakes of open bets
+/// * `stats` - The game's statistics, holding the unclaimed credits
+///
+/// # Returns
+/// * `u64` - Open stakes plus unclaimed referral credits, deferred payouts and rebates
+///
+/// Withdrawals and LP share pricing only count the vault balance above this,
+/// so the house and liquidity providers can never take funds backing refunds
+/// or credits.
+pub fn vault_liabilities(tracker: &BetTracker, stats: &HouseStats) -> u64 {
+    tracker
+        .open_stakes
+        .saturating_add(stats.referral_credits)
+        .saturating_add(stats.deferred_payouts)
+        .saturating_add(stats.rebate_pool)
+}
+
+/// Share of a stake in basis points
+///
+/// # Arguments
+/// * `amount` - The stake (lamports)
+/// * `bps` - Share in basis points
+///
+/// # Returns
+/// * `Result<u64>` - amount * bps / 10000, rounded down
+///
+/// Used to carve the charity donation and the jackpot contribution out of a
+/// stake at placement; rounding down leaves the remainder in the wager.
+pub fn stake_share(amount: u64, bps: u16) -> Result<u64> {
+    Ok((amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(DiceError::Overflow)?
+        .checked_div(10000)
+        .ok_or(DiceError::Overflow)? as u64)
+}
+
+/// Bonus owed to the player for a slow resolution
+///
+/// # Arguments
+/// * `amount` - The wager (lamports)
+/// * `latency` - Slots from the bet's commit slot to the resolving slot
+/// * `slow_slots` - Latency above which the bonus is paid (SLOW_RESOLUTION_SLOTS), 0 disables it
+/// * `bonus_bps` - Bonus in basis points of the wager (SLOW_RESOLUTION_BONUS_BPS)
+///
+/// # Returns
+/// * `Result<u64>` - `bonus_bps` of the wager, rounded down, or 0 for a timely resolution
+pub fn slow_resolution_bonus(
+    amount: u64,
+    latency: u64,
+    slow_slots: u64,
+    bonus_bps: u16,
+) -> Result<u64> {
+    if slow_slots == 0 || latency <= slow_slots {
+        return Ok(0);
+    }
+    stake_share(amount, bonus_bps)
+}
+
+/// Cover paid from the insurance pool on an insured loss
+///
+/// # Arguments
+/// * `amount` - The wager of the lost bet (lamports)
+/// * `pool_lamports` - Current insurance pool balance
+/// * `rent_floor` - Rent-exempt minimum the pool must keep
+///
+/// # Returns
+/// * `Result<u64>` - INSURANCE_COVER_BPS of the wager, capped by what the pool
+///   holds above its rent-exempt minimum
+pub fn insurance_cover(amount: u64, pool_lamports: u64, rent_floor: u64) -> Result<u64> {
+    let cover = stake_share(amount, INSURANCE_COVER_BPS)?;
+    Ok(cover.min(pool_lamports.saturating_sub(rent_floor)))
+}
+
+/// LP shares minted for a deposit into the vault
+///
+/// # Arguments
+/// * `amount` - Lamports deposited
+/// * `lp_shares` - Shares outstanding before the deposit
+/// * `value` - Vault value before the deposit, net of liabilities
+///
+/// # Returns
+/// * `Result<u64>` - amount * lp_shares / value rounded down, or `amount` when
+///   no shares exist yet
+///
+/// Rounding down means a deposit never dilutes the existing providers.
+pub fn shares_for_deposit(amount: u64, lp_shares: u64, value: u64) -> Result<u64> {
+    if lp_shares == 0 {
+        return Ok(amount);
+    }
+    require!(value > 0, DiceError::InvalidShares);
+    u64::try_from(
+        (amount as u128)
+            .checked_mul(lp_shares as u128)
+            .ok_or(DiceError::Overflow)?
+            / value as u128,
+    )
+    .map_err(|_| error!(DiceError::Overflow))
+}
+
+/// Lamports paid for redeeming LP shares
+///
+/// # Arguments
+/// * `shares` - Shares being redeemed
+/// * `lp_shares` - Shares outstanding before the redemption
+/// * `value` - Vault value, net of liabilities
+///
+/// # Returns
+/// * `Result<u64>` - shares * value / lp_shares, rounded down
+///
+/// Rounding down means a redemption never takes from the remaining providers.
+pub fn lamports_for_shares(shares: u64, lp_shares: u64, value: u64) -> Result<u64> {
+    u64::try_from(
+        (shares as u128)
+            .checked_mul(value as u128)
+            .ok_or(DiceError::Overflow)?
+            .checked_div(lp_shares as u128)
+            .ok_or(DiceError::InvalidShares)?,
+    )
+    .map_err(|_| error!(DiceError::Overflow))
+}
+
+/// House edge rebate owed to the player for a resolved bet
+///
+/// # Arguments
+/// * `amount` - Amount wagered
+/// * `house_edge_bp` - House edge the bet was placed under
+/// * `rebate_bps` - Share of the edge rebated, in basis points of the edge
+///
+/// # Returns
+/// * `u64` - amount * house_edge_bp * rebate_bps / 10000^2, rounded down
+///
+/// Example: 1 SOL at the default 1.5% edge with a 2000 bp rebate returns 0.003 SOL.
+pub fn edge_rebate(amount: u64, house_edge_bp: u16, rebate_bps: u16) -> u64 {
+    // Both factors are at most 10000, so the quotient never exceeds `amount`
+    (amount as u128 * house_edge_bp as u128 * rebate_bps as u128 / 100_000_000) as u64
+}
+
+/// Calculate the exact payout for a winning bet in scaled units
+///
+/// # Arguments
+/// * `amount` - Amount wagered (lamports)
+/// * `roll` - Player's roll prediction
+/// * `range` - Dice range the roll is drawn from (1-range)
+/// * `direction` - Whether the bet wins under or over the prediction
+/// * `house_edge_bp` - House edge in basis points (below 10000)
+/// * `scale` - Internal units per lamport, see PAYOUT_SCALE
+///
+/// # Returns
+/// * `Result<u128>` - `calculate_payout`'s formula multiplied by `scale`, rounded
+///   down, or `Overflow`
+///
+/// # Bounds
+/// The largest intermediate value is u64::MAX * 10000 * MAX_ROLL_RANGE * scale,
+/// about 1.8e33 at the default scale of 10^6, well below u128::MAX.
+pub fn calculate_payout_scaled(
+    amount: u64,
+    roll: u16,
+    range: u16,
+    direction: BetDirection,
+    house_edge_bp: u16,
+    scale: u64,
+) -> Result<u128> {
+    let edge_factor = 10000u128
+        .checked_sub(house_edge_bp as u128)
+        .ok_or(DiceError::InvalidHouseEdge)?;
+    let divisor = (direction.winning_outcomes(roll, range) as u128)
+        .checked_mul(10000)
+        .ok_or(DiceError::Overflow)?;
+
+    (amount as u128)
+        .checked_mul(edge_factor)
+        .ok_or(DiceError::Overflow)?
+        .checked_mul(range as u128)
+        .ok_or(DiceError::Overflow)?
+        .checked_mul(scale as u128)
+        .ok_or(DiceError::Overflow)?
+        .checked_div(divisor)
+        .ok_or(error!(DiceError::Overflow))
+}
+
+/// Split a scaled payout into whole lamports and a remainder to bank
+///
+/// # Arguments
+/// * `scaled` - Payout in scaled units, from `calculate_payout_scaled`
+/// * `banked` - Remainder banked for the player by earlier payouts
+/// * `scale` - Internal units per lamport
+///
+/// # Returns
+/// * `Result<(u64, u64)>` - Lamports to pay now and the new remainder (below
+///   `scale`), or `Overflow` if the lamports do not fit a u64
+pub fn split_scaled_payout(scaled: u128, banked: u64, scale: u64) -> Result<(u64, u64)> {
+    let total = scaled
+        .checked_add(banked as u128)
+        .ok_or(DiceError::Overflow)?;
+    let lamports = u64::try_from(total / scale as u128).map_err(|_| error!(DiceError::Overflow))?;
+    Ok((lamports, (total % scale as u128) as u64))
+}
+
+/// Largest deviation of a decile count from an even split of all counted rolls
+///
+/// # Arguments
+/// * `deciles` - Roll counts per tenth of the dice range, as kept in HouseStats
+///
+/// # Returns
+/// * `u16` - Deviation in basis points of the expected per-decile count, capped at
+///   u16::MAX; 0 before any roll is counted
+///
+/// The expected count assumes deciles of equal width, which holds exactly for
+/// ranges divisible by 10 such as the default 1-100.
+pub fn decile_divergence_bps(deciles: &[u64; 10]) -> u16 {
+    let total: u128 = deciles.iter().map(|count| *count as u128).sum();
+    if total == 0 {
+        return 0;
+    }
+
+    // |count - total / 10| / (total / 10) == |10 * count - total| / total
+    deciles
+        .iter()
+        .map(|count| (*count as u128 * 10).abs_diff(total) * 10000 / total)
+        .max()
+        .unwrap_or(0)
+        .min(u16::MAX as u128) as u16
+}
+
+/// Commitment to the randomness scheme of a house's game
+///
+/// # Arguments
+/// * `house` - The house authority whose signatures provide the entropy
+///
+/// # Returns
+/// * `[u8; 32]` - Hash of every parameter that decides how rolls are produced
+///
+/// # Committed Parameters
+/// The house key, the ENTROPY_SIGNERS set and threshold, the ORACLE_AUTHORITY,
+/// the Switchboard program and REQUIRE_PLAYER_SIGNATURE, in that order.
+pub fn rng_commitment(house: &Pubkey) -> [u8; 32] {
+    let signers: Vec<&[u8]> = ENTROPY_SIGNERS.iter().map(|s| s.as_ref()).collect();
+    let threshold = (ENTROPY_SIGNER_THRESHOLD as u64).to_le_bytes();
+    let oracle = ORACLE_AUTHORITY.unwrap_or_default();
+
+    hashv(&[
+        house.as_ref(),
+        &signers.concat(),
+        &threshold,
+        oracle.as_ref(),
+        SWITCHBOARD_PROGRAM_ID.as_ref(),
+        &[REQUIRE_PLAYER_SIGNATURE as u8],
+    ])
+    .to_bytes()
+}
+
+/// Base message every resolution signature must cover
+///
+/// # Arguments
+/// * `bet_key` - Address of the bet PDA being resolved
+/// * `bet` - The bet account data
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The hash of the bet address followed by the serialized bet data
+///
+/// # Replay Protection
+/// The serialized data alone could repeat across two bets with identical
+/// contents; prefixing the unique bet PDA address ties each signature to
+/// exactly one bet, so it can never resolve a second one.
+///
+/// # Message Size
+/// Signing the 32-byte hash rather than the full bet keeps each Ed25519
+/// instruction small enough for MAX_BATCH_RESOLVE of them to share one
+/// transaction with resolve_bets_batch. The buffer leaves room for a trailing
+/// vault state hash, so signable messages can extend it without reallocating.
+pub fn bet_message(bet_key: &Pubkey, bet: &Bet) -> Result<Vec<u8>> {
+    let mut data = Vec::with_capacity(Bet::INIT_SPACE);
+    bet.serialize(&mut data)?;
+
+    let mut message = Vec::with_capacity(32 + 32);
+    message.extend_from_slice(&hashv(&[bet_key.as_ref(), &data]).to_bytes());
+    Ok(message)
+}
+
+/// Message the ORACLE_AUTHORITY signs to report a bet's final roll
+///
+/// # Arguments
+/// * `bet_key` - Address of the bet PDA being resolved
+/// * `bet` - The bet account data
+/// * `roll` - The final roll chosen by the oracle's off-chain VRF
+///
+/// # Returns
+/// * `Result<Vec<u8>>` - The bet message followed by `roll` (u16 little-endian)
+pub fn oracle_message(bet_key: &Pubkey, bet: &Bet, roll: u16) -> Result<Vec<u8>> {
+    let mut message = bet_message(bet_key, bet)?;
+    message.extend_from_slice(&roll.to_le_bytes());
+    Ok(message)
+}
+
+/// Hash of the vault state a house signature can be bound to
+///
+/// # Arguments
+/// * `balance` - Vault balance (lamports, or token base units for token games)
+/// * `open_bets` - Open bet count from the BetTracker
+///
+/// # Returns
+/// * `[u8; 32]` - Hash appended to the signed message when BIND_VAULT_STATE is set
+pub fn vault_state_hash(balance: u64, open_bets: u32) -> [u8; 32] {
+    hashv(&[&balance.to_le_bytes(), &open_bets.to_le_bytes()]).to_bytes()
+}
+
+/// Load an Ed25519 verification instruction preceding the current one
+///
+/// # Arguments
+/// * `instruction_sysvar` - The instructions sysvar account
+/// * `distance` - How many instructions before the current one it sits (1 = directly before)
+///
+/// # Returns
+/// * `Result<Instruction>` - The validated Ed25519 instruction
+///
+/// # Instruction Ordering
+/// The index is derived from the currently executing instruction rather than
+/// assumed to be 0, so compute budget or other instructions may come first.
+/// Single resolutions use the directly preceding instruction; a batch of N bets
+/// uses the N instructions before it, in bet order. An index before the start
+/// of the transaction is rejected.
+pub fn load_preceding_ed25519_instruction(
+    instruction_sysvar: &AccountInfo,
+    distance: u16,
+) -> Result<Instruction> {
+    let current_index = load_current_index_checked(instruction_sysvar)?;
+    let preceding_index = current_index
+        .checked_sub(distance.max(1))
+        .ok_or(DiceError::Ed25519Program)?;
+    let ix = load_instruction_at_checked(preceding_index as usize, instruction_sysvar)?;
+
+    // SECURITY: Ensure the instruction is addressed to the Ed25519 program
+    require_keys_eq!(
+        ix.program_id,
+        ed25519_program::ID,
+        DiceError::Ed25519Program
+    );
+
+    // SECURITY: Ed25519 verify instructions should not have any accounts
+    require_eq!(ix.accounts.len(), 0, DiceError::Ed25519Accounts);
+
+    Ok(ix)
+}
+
+/// Verify the house Ed25519 signature instruction and derive the roll entropy
+///
+/// # Arguments
+/// * `instruction_sysvar` - The instructions sysvar account
+/// * `house` - The house authority expected to provide the first signature
+/// * `message` - The signable message every signature must cover (see BIND_VAULT_STATE)
+/// * `sig` - The signature bytes that should match the Ed25519 instruction
+/// * `distance` - Position of the Ed25519 instruction before the current one (1 = directly before)
+///
+/// # Returns
+/// * `Result<[u8; 32]>` - Roll entropy derived from the verified signatures
+///
+/// # Security Requirements
+/// 1. The directly preceding instruction must be an Ed25519 verification instruction
+/// 2. The first signature must be from the house authority
+/// 3. The message being signed must be the bet message (see `bet_message`)
+/// 4. No accounts should be present in the Ed25519 instruction
+/// 5. The `sig` argument must be 64 bytes and equal the embedded signature
+/// 6. Exactly ENTROPY_SIGNER_THRESHOLD further signatures from distinct
+///    ENTROPY_SIGNERS must follow, signing the same message
+///
+/// # Threshold Entropy
+/// The entropy is the hash of the house signature followed by every co-signer
+/// signature, so no single signer controls the roll. With no co-signers
+/// configured this is simply the hash of the house signature. The signatures
+/// are fed to the hasher as they are verified instead of being copied first.
+pub fn verify_house_signature(
+    instruction_sysvar: &AccountInfo,
+    house: &Pubkey,
+    message: &[u8],
+    sig: &[u8],
+    distance: u16,
+) -> Result<[u8; 32]> {
+    // SECURITY: Ed25519 signatures are exactly 64 bytes
+    // Checked up front so a malformed argument fails with a clear error
+    require_eq!(sig.len(), 64, DiceError::Ed25519DataLength);
+
+    // Load the Ed25519 instruction that should precede this one
+    let ix = load_preceding_ed25519_instruction(instruction_sysvar, distance)?;
+
+    // Parse the Ed25519 instruction data to extract signature information
+    let signatures = Ed25519InstructionSignatures::unpack(&ix.data)?.0;
+
+    // SECURITY: Should contain the house signature plus the required co-signatures
+    require_eq!(
+        signatures.len(),
+        1 + ENTROPY_SIGNER_THRESHOLD,
+        DiceError::Ed25519DataLength
+    );
+    let signature = &signatures[0];
+
+    // SECURITY: All signature components must be verifiable (not None)
+    require!(signature.is_verifiable, DiceError::Ed25519Header);
+
+    // SECURITY: Public key must match the house authority
+    require_keys_eq!(
+        signature.public_key.ok_or(DiceError::Ed25519Pubkey)?,
+        *house,
+        DiceError::Ed25519Pubkey
+    );
+
+    // SECURITY: Signature bytes must match the provided signature
+    require!(
+        &signature
+            .signature
+            .ok_or(DiceError::Ed25519Signature)?
+            .eq(sig),
+        DiceError::Ed25519Signature
+    );
+
+    // SECURITY: Message must be the bet message (prevents signature reuse)
+    require!(
+        &signature
+            .message
+            .as_ref()
+            .ok_or(DiceError::Ed25519Signature)?
+            .eq(message),
+        DiceError::Ed25519Signature
+    );
+
+    // ENTROPY: Hash all signatures together in instruction order
+    let mut hasher = Hasher::default();
+    hasher.hash(sig);
+
+    // THRESHOLD: Every co-signature must come from a distinct configured signer
+    let mut co_signers: Vec<Pubkey> = Vec::with_capacity(ENTROPY_SIGNER_THRESHOLD);
+    for co_signature in &signatures[1..] {
+        require!(co_signature.is_verifiable, DiceError::Ed25519Header);
+
+        let co_signer = co_signature.public_key.ok_or(DiceError::Ed25519Pubkey)?;
+        require!(
+            ENTROPY_SIGNERS.contains(&co_signer) && !co_signers.contains(&co_signer),
+            DiceError::Ed25519Pubkey
+        );
+        require!(
+            &co_signature
+                .message
+                .as_ref()
+                .ok_or(DiceError::Ed25519Signature)?
+                .eq(message),
+            DiceError::Ed25519Signature
+        );
+
+        co_signers.push(co_signer);
+        hasher.hash(&co_signature.signature.ok_or(DiceError::Ed25519Signature)?);
+    }
+
+    Ok(hasher.result().to_bytes())
+}
+
+/// Roll entropy of a set of resolution signatures
+///
+/// # Arguments
+/// * `signatures` - The house signature followed by any co-signatures, in instruction order
+///
+/// # Returns
+/// * `[u8; 32]` - Hash of the signatures, as derived by `verify_house_signature`
+pub fn entropy_from_signatures(signatures: &[[u8; 64]]) -> [u8; 32] {
+    let mut hasher = Hasher::default();
+    for signature in signatures {
+        hasher.hash(signature);
+    }
+    hasher.result().to_bytes()
+}
+
+/// Derive a dice roll (1-range) from 32 bytes of entropy
+///
+/// # Arguments
+/// * `entropy` - Hash of the verified signature(s), or a revealed VRF value
+/// * `range` - Number of faces on the dice (at least 1)
+///
+/// # Returns
+/// * `u16` - Rolled value between 1 and `range`
+///
+/// # Derivation
+/// Rejection sampling removes the modulo bias of reducing the entropy to `range`:
+/// 1. Split the entropy into samples: single bytes for ranges up to 256,
+///    little-endian 16-bit words above that
+/// 2. Take the first sample below the largest multiple of `range` the sample
+///    size holds (200 for the default range of 100)
+/// 3. The roll is that sample modulo `range`, plus 1
+/// 4. If every sample is rejected, rehash and repeat
+///
+/// For ranges up to 256 this yields the same rolls as byte-wise sampling
+/// always has, so existing fairness receipts at the default range stay valid.
+pub fn roll_from_entropy(entropy: &[u8; 32], range: u16) -> u16 {
+    let range = range.max(1) as u32;
+    let (sample_size, space) = if range <= 256 { (1, 256) } else { (2, 65536) };
+    let limit = space - space % range;
+
+    let mut block = *entropy;
+    loop {
+        let sample = block
+            .chunks_exact(sample_size)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .rev()
+                    .fold(0u32, |acc, b| (acc << 8) | *b as u32)
+            })
+            .find(|sample| *sample < limit);
+        if let Some(sample) = sample {
+            return (sample % range + 1) as u16;
+        }
+        block = hashv(&[&block]).to_bytes();
+    }
+}
+
+/// Win probability of a prediction, in basis points
+///
+/// # Arguments
+/// * `roll` - Player's roll prediction
+/// * `range` - Dice range the roll is drawn from (1-range)
+/// * `direction` - Roll under or roll over the prediction
+///
+/// # Returns
+/// * `u16` - winning outcomes * 10000 / range, rounded down (0 for an empty range)
+///
+/// Example: under 50 of 100 wins on 1-49, so 4900 bp (49%); over 50 wins on 51-100, 5000 bp.
+/// A tie never wins, so a push on ties does not change the probability.
+pub fn win_probability_bp(roll: u16, range: u16, direction: BetDirection) -> u16 {
+    if range == 0 {
+        return 0;
+    }
+    // winning outcomes never exceed the range, so the result fits in 10000 bp
+    (direction.winning_outcomes(roll, range) as u32 * 10000 / range as u32) as u16
+}
+
+/// Calculate the payout for a winning bet
+///
+/// # Arguments
+/// * `amount` - Amount wagered (lamports or token base units)
+/// * `roll` - Player's roll prediction
+/// * `range` - Dice range the roll is drawn from (1-range)
+/// * `direction` - Whether the bet wins under or over the prediction
+/// * `house_edge_bp` - House edge in basis points (below 10000)
+/// * `rounding` - How the final division is rounded
+///
+/// # Returns
+/// * `Result<u64>` - Payout including the returned stake, or `Overflow`
+///
+/// # Formula
+/// payout = (amount * (10000 - house_edge_bp) * range) / (winning * 10000)
+/// where `winning` is the number of winning faces: roll - 1 rolling under,
+/// range - roll rolling over. The fair multiplier is range / winning before
+/// the edge is applied; a bet without winning faces fails with `Overflow`.
+/// Example: 1 SOL bet on roll 50 of 100 = (1 * 9850 * 100) / (49 * 10000) = ~2.01 SOL payout
+/// Example: 1 SOL bet on roll 4 of 6 = (1 * 9850 * 6) / (3 * 10000) = 1.97 SOL payout
+/// Example: 1 SOL bet over roll 50 of 100 = (1 * 9850 * 100) / (50 * 10000) = 1.97 SOL payout
+/// Roll 2 divides by 1, the largest multiplier (MAX_PAYOUT_LAMPORTS at the defaults)
+///
+/// # Rounding
+/// The formula is evaluated with a single division. `Floor` drops the
+/// remainder, so the player receives up to 1 base unit (lamport) less than
+/// the exact value. `Nearest` adds half the divisor first, so the payout is
+/// within half a unit of the exact value, halves rounding up.
+/// Example at the default edge: 1 lamport on roll 2 is exactly 98.5 lamports,
+/// paid as 98 (Floor) or 99 (Nearest).
+///
+/// # Bounds
+/// The largest intermediate value is u64::MAX * 10000 * MAX_ROLL_RANGE (~1.8e27),
+/// far below u128::MAX (~3.4e38), so no multiplication can overflow. `Overflow`
+/// is only returned for genuinely impossible inputs: a prediction with no winning
+/// rolls (a zero divisor) or a payout that does not fit a u64.
+/// At the defaults the extremes are comfortably in range:
+/// - MAX_BET_LAMPORTS on roll 2: 10^10 * 9850 * 100 / (1 * 10000) = 985 SOL
+/// - MIN_BET_LAMPORTS on roll 96: 10^7 * 9850 * 100 / (95 * 10000) = 10,368,421
+///   lamports, so the smallest winning bet never rounds towards zero
+///
+/// # Usage
+/// The single source of the payout formula for resolve_bet and resolve_bet_token.
+/// Pure and dependency-free, so off-chain clients linking the crate can call it
+/// to show the exact expected winnings before a bet is placed.
+pub fn calculate_payout(
+    amount: u64,
+    roll: u16,
+    range: u16,
+    direction: BetDirection,
+    house_edge_bp: u16,
+    rounding: PayoutRounding,
+) -> Result<u64> {
+    let edge_factor = 10000u128
+        .checked_sub(house_edge_bp as u128)
+        .ok_or(DiceError::InvalidHouseEdge)?;
+    let divisor = (direction.winning_outcomes(roll, range) as u128) // Odds-based multiplier
+        .checked_mul(10000)
+        .ok_or(DiceError::Overflow)?; // Convert basis points
+    let bias = match rounding {
+        PayoutRounding::Floor => 0,
+        PayoutRounding::Nearest => divisor / 2,
+    };
+
+    let payout = (amount as u128)
+        .checked_mul(edge_factor)
+        .ok_or(DiceError::Overflow)? // Apply house edge
+        .checked_mul(range as u128)
+        .ok_or(DiceError::Overflow)? // Scale by the dice range
+        .checked_add(bias)
+        .ok_or(DiceError::Overflow)?
+        .checked_div(divisor)
+        .ok_or(DiceError::Overflow)?;
+
+    u64::try_from(payout).map_err(|_| error!(DiceError::Overflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClaimRebate, ClaimRebateBumps, GameConfig, PlayerStats, INSURANCE_PREMIUM_BPS};
+
+    /// Instructions sysvar data for `ixs` while executing the one at `current`,
+    /// in the layout the runtime writes
+    fn instructions_sysvar(ixs: &[Instruction], current: u16) -> Vec<u8> {
+        let mut data = (ixs.len() as u16).to_le_bytes().to_vec();
+        data.resize(2 + 2 * ixs.len(), 0);
+        for (i, ix) in ixs.iter().enumerate() {
+            let offset = (data.len() as u16).to_le_bytes();
+            data[2 + 2 * i..4 + 2 * i].copy_from_slice(&offset);
+            data.extend_from_slice(&(ix.accounts.len() as u16).to_le_bytes());
+            for meta in &ix.accounts {
+                data.push(meta.is_signer as u8 | (meta.is_writable as u8) << 1);
+                data.extend_from_slice(meta.pubkey.as_ref());
+            }
+            data.extend_from_slice(ix.program_id.as_ref());
+            data.extend_from_slice(&(ix.data.len() as u16).to_le_bytes());
+            data.extend_from_slice(&ix.data);
+        }
+        data.extend_from_slice(&current.to_le_bytes());
+        data
+    }
+
+    fn sysvar_account<'a>(data: &'a mut [u8], lamports: &'a mut u64) -> AccountInfo<'a> {
+        AccountInfo::new(
+            &solana_program::sysvar::instructions::ID,
+            false,
+            false,
+            lamports,
+            data,
+            &solana_program::sysvar::ID,
+            false,
+            0,
+        )
+    }
+
+    /// Slot the host Clock sysvar reports
+    const HOST_SLOT: u64 = 1_000;
+
+    /// Serves the default Rent and a HOST_SLOT Clock to `Rent::get()` and
+    /// `Clock::get()` on the host
+    fn stub_syscalls() {
+        struct HostStubs;
+        impl solana_program::program_stubs::SyscallStubs for HostStubs {
+            fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+                unsafe { *(var_addr as *mut Rent) = Rent::default() };
+                solana_program::entrypoint::SUCCESS
+            }
+
+            fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+                let clock = Clock {
+                    slot: HOST_SLOT,
+                    ..Clock::default()
+                };
+                unsafe { *(var_addr as *mut Clock) = clock };
+                solana_program::entrypoint::SUCCESS
+            }
+        }
+
+        static STUB: std::sync::Once = std::sync::Once::new();
+        STUB.call_once(|| {
+            solana_program::program_stubs::set_syscall_stubs(Box::new(HostStubs));
+        });
+    }
+
+    /// Program account data for a zeroed `T` after `init` sets its fields
+    fn program_account<T>(init: impl FnOnce(&mut T)) -> Vec<u8>
+    where
+        T: AccountSerialize + AccountDeserialize + Discriminator + Space,
+    {
+        let mut data = T::DISCRIMINATOR.to_vec();
+        data.resize(T::DISCRIMINATOR.len() + T::INIT_SPACE, 0);
+        let mut account = T::try_deserialize(&mut &data[..]).unwrap();
+        init(&mut account);
+        data.clear();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    /// Host AccountInfos for `keys`, owned by `owners` and holding `data`
+    /// The first account signs and the last is the (executable) system program
+    fn host_accounts<'a>(
+        keys: &'a [Pubkey],
+        owners: &'a [Pubkey],
+        lamports: &'a mut [u64],
+        data: &'a mut [Vec<u8>],
+    ) -> Vec<AccountInfo<'a>> {
+        let last = keys.len() - 1;
+        keys.iter()
+            .zip(owners)
+            .zip(lamports.iter_mut().zip(data.iter_mut()))
+            .enumerate()
+            .map(|(i, ((key, owner), (lamports, data)))| {
+                AccountInfo::new(key, i == 0, true, lamports, data, owner, i == last, 0)
+            })
+            .collect()
+    }
+
+    /// Runs the account validation of ClaimRebate with a vault holding `vault_data`
+    fn validate_claim_rebate(vault_data: Vec<u8>) -> Result<()> {
+        let player = Pubkey::new_unique();
+        let house = Pubkey::new_unique();
+        let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &crate::ID);
+        let (vault, _) = pda(&[b"vault", house.as_ref()]);
+        let (config, config_bump) = pda(&[b"config", house.as_ref()]);
+        let (stats, stats_bump) = pda(&[b"stats", house.as_ref()]);
+        let (player_stats, player_stats_bump) =
+            pda(&[b"player_stats", house.as_ref(), player.as_ref()]);
+
+        let keys = [
+            player,
+            house,
+            vault,
+            config,
+            stats,
+            player_stats,
+            anchor_lang::system_program::ID,
+        ];
+        let owners = [
+            anchor_lang::system_program::ID,
+            anchor_lang::system_program::ID,
+            anchor_lang::system_program::ID,
+            crate::ID,
+            crate::ID,
+            crate::ID,
+            anchor_lang::system_program::ID,
+        ];
+        let mut lamports = [1_000_000_000u64; 7];
+        let mut data = [
+            vec![],
+            vec![],
+            vault_data,
+            program_account::<GameConfig>(|c| c.bump = config_bump),
+            program_account::<HouseStats>(|s| s.bump = stats_bump),
+            program_account::<PlayerStats>(|s| s.bump = player_stats_bump),
+            vec![],
+        ];
+        let accounts = host_accounts(&keys, &owners, &mut lamports, &mut data);
+
+        ClaimRebate::try_accounts(
+            &crate::ID,
+            &mut &accounts[..],
+            &[],
+            &mut ClaimRebateBumps::default(),
+            &mut std::collections::BTreeSet::new(),
+        )
+        .map(|_| ())
+    }
+
+    #[test]
+    fn require_not_vault_rejects_the_vault() {
+        let vault = Pubkey::new_unique();
+        assert_eq!(
+            require_not_vault(&vault, &vault).unwrap_err(),
+            DiceError::VaultAsRecipient.into()
+        );
+    }
+
+    #[test]
+    fn require_not_vault_accepts_other_recipients() {
+        let vault = Pubkey::new_unique();
+        assert!(require_not_vault(&Pubkey::new_unique(), &vault).is_ok());
+    }
+
+    #[test]
+    fn seed_distinct_bytes_counts_the_low_eight_bytes() {
+        assert_eq!(seed_distinct_bytes(0), 1);
+        assert_eq!(seed_distinct_bytes(u128::MAX), 1);
+        assert_eq!(seed_distinct_bytes(0x4242_4242_4242_4242), 1);
+        assert_eq!(seed_distinct_bytes(1), 2);
+        assert_eq!(seed_distinct_bytes(0x0807_0605_0403_0201), 8);
+    }
+
+    #[test]
+    fn seed_distinct_bytes_ignores_the_high_bytes() {
+        // Only the low 8 bytes count: varying the rest does not strengthen a seed
+        assert_eq!(seed_distinct_bytes(0x0102_0304_0506_0708 << 64), 1);
+        assert_eq!(
+            seed_distinct_bytes((0x0102_0304_0506_0708 << 64) | 0x4242_4242_4242_4242),
+            1
+        );
+    }
+
+    #[test]
+    fn edge_rebate_is_a_share_of_the_edge() {
+        // 1 SOL at 1.5% edge: 0.015 SOL edge, a fifth of it rebated
+        assert_eq!(edge_rebate(1_000_000_000, 150, 2000), 3_000_000);
+        assert_eq!(edge_rebate(1_000_000_000, 150, 0), 0);
+        assert_eq!(edge_rebate(1_000_000_000, 150, 10000), 15_000_000);
+    }
+
+    #[test]
+    fn edge_rebate_accumulates_over_bets() {
+        // Ten 0.1 SOL bets credit the same rebate as one 1 SOL bet
+        let pool: u64 = (0..10).map(|_| edge_rebate(100_000_000, 150, 2000)).sum();
+        assert_eq!(pool, edge_rebate(1_000_000_000, 150, 2000));
+        assert_eq!(edge_rebate(u64::MAX, 10000, 10000), u64::MAX);
+    }
+
+    #[test]
+    fn decile_divergence_of_an_even_split_is_zero() {
+        assert_eq!(decile_divergence_bps(&[0; 10]), 0);
+        assert_eq!(decile_divergence_bps(&[100; 10]), 0);
+    }
+
+    #[test]
+    fn decile_divergence_reports_the_largest_deviation() {
+        // Expected 100 per decile: 130 is 30% over, 80 is 20% under
+        let deciles = [130, 80, 100, 100, 100, 100, 100, 95, 95, 100];
+        assert_eq!(decile_divergence_bps(&deciles), 3000);
+
+        // Every roll in one decile is ten times its share: 900% over, capped
+        let mut skewed = [0; 10];
+        skewed[9] = 50;
+        assert_eq!(decile_divergence_bps(&skewed), u16::MAX);
+    }
+
+    #[test]
+    fn scaled_payout_matches_the_truncating_formula_in_whole_lamports() {
+        // 1 lamport on roll 2 at the default edge is exactly 98.5 lamports
+        let scaled =
+            calculate_payout_scaled(1, 2, 100, BetDirection::Under, 150, 1_000_000).unwrap();
+        assert_eq!(scaled, 98_500_000);
+        assert_eq!(
+            split_scaled_payout(scaled, 0, 1_000_000).unwrap(),
+            (98, 500_000)
+        );
+        assert_eq!(
+            calculate_payout(1, 2, 100, BetDirection::Under, 150, PayoutRounding::Floor).unwrap(),
+            98
+        );
+    }
+
+    #[test]
+    fn scaled_payout_banks_the_remainder_instead_of_drifting() {
+        // 1,000 wins of 1 lamport on roll 2: exactly 98,500 lamports in total
+        let exact = 98_500u64;
+        let (mut paid, mut banked) = (0u64, 0u64);
+        let mut truncated = 0u64;
+        for _ in 0..1_000 {
+            let scaled =
+                calculate_payout_scaled(1, 2, 100, BetDirection::Under, 150, 1_000_000).unwrap();
+            let (lamports, remainder) = split_scaled_payout(scaled, banked, 1_000_000).unwrap();
+            paid += lamports;
+            banked = remainder;
+            truncated +=
+                calculate_payout(1, 2, 100, BetDirection::Under, 150, PayoutRounding::Floor)
+                    .unwrap();
+        }
+
+        // Truncation loses half a lamport per payout; banking loses none
+        assert_eq!(exact - truncated, 500);
+        assert_eq!(paid, exact);
+        assert_eq!(banked, 0);
+    }
+
+    #[test]
+    fn scaled_payout_remainder_stays_below_one_lamport() {
+        // 1 lamport on roll 50 pays 100/49 * 0.985 lamports, a repeating fraction
+        let mut banked = 0u64;
+        let mut paid = 0u64;
+        for _ in 0..49 {
+            let scaled =
+                calculate_payout_scaled(1, 50, 100, BetDirection::Under, 150, 1_000_000).unwrap();
+            let (lamports, remainder) = split_scaled_payout(scaled, banked, 1_000_000).unwrap();
+            assert!(remainder < 1_000_000);
+            paid += lamports;
+            banked = remainder;
+        }
+        // 49 such wins are worth 98.5 lamports; at most a micro-lamport per win is dropped
+        assert_eq!(paid, 98);
+        assert!((500_000 - 49..500_000).contains(&banked));
+    }
+
+    #[test]
+    fn vault_liabilities_sum_stakes_and_unclaimed_credits() {
+        let tracker = BetTracker {
+            house: Pubkey::new_unique(),
+            open_bets: 2,
+            open_stakes: 3_000,
+            bump: 255,
+        };
+        let mut stats = HouseStats {
+            house: tracker.house,
+            total_bets: 0,
+            total_volume: 0,
+            total_payout: 0,
+            wins: 0,
+            losses: 0,
+            jackpot_pool: 0,
+            lp_shares: 0,
+            house_shares: 0,
+            deferred_payouts: 400,
+            rebate_pool: 50,
+            referral_credits: 6,
+            roll_deciles: [0; 10],
+            bump: 255,
+        };
+        assert_eq!(vault_liabilities(&tracker, &stats), 3_456);
+
+        stats.deferred_payouts = u64::MAX;
+        assert_eq!(vault_liabilities(&tracker, &stats), u64::MAX);
+    }
+
+    #[test]
+    fn roll_stays_within_the_range() {
+        for range in [1u16, 2, 6, 100, 256, 257, 1_000, crate::MAX_ROLL_RANGE] {
+            for i in 0u32..2_000 {
+                let entropy = hashv(&[&i.to_le_bytes(), &range.to_le_bytes()]).to_bytes();
+                let roll = roll_from_entropy(&entropy, range);
+                assert!((1..=range).contains(&roll), "roll {roll} of {range}");
+            }
+        }
+    }
+
+    #[test]
+    fn roll_rejects_bytes_above_the_largest_multiple_of_the_range() {
+        assert_eq!(roll_from_entropy(&[0; 32], 100), 1);
+        assert_eq!(roll_from_entropy(&[199; 32], 100), 100);
+
+        let mut entropy = [0; 32];
+        entropy[0] = 200;
+        entropy[1] = 5;
+        assert_eq!(roll_from_entropy(&entropy, 100), 6);
+
+        // Every byte rejected: the entropy is rehashed instead of biasing the roll
+        let roll = roll_from_entropy(&[255; 32], 100);
+        assert!((1..=100).contains(&roll));
+    }
+
+    #[test]
+    fn roll_is_uniform_over_the_default_range() {
+        const SAMPLES: u32 = 100_000;
+        let mut counts = [0u32; 100];
+        for i in 0..SAMPLES {
+            let entropy = hashv(&[&i.to_le_bytes()]).to_bytes();
+            counts[roll_from_entropy(&entropy, 100) as usize - 1] += 1;
+        }
+
+        // 1000 expected per face; the standard deviation is about 31
+        for (face, count) in counts.iter().enumerate() {
+            assert!(
+                (850..=1150).contains(count),
+                "face {} rolled {count} times",
+                face + 1
+            );
+        }
+    }
+
+    #[test]
+    fn classic_and_fine_ranges_pay_the_odds_of_their_rolls() {
+        const SAMPLES: u32 = 60_000;
+        let sol = 1_000_000_000;
+
+        // (range, roll under, payout of 1 SOL at the default edge)
+        for (range, roll, payout) in [
+            (6u16, 2u16, 5_910_000_000),
+            (6, 4, 1_970_000_000),
+            (1_000, 2, 985_000_000_000),
+            (1_000, 501, 1_970_000_000),
+        ] {
+            assert_eq!(
+                calculate_payout(
+                    sol,
+                    roll,
+                    range,
+                    BetDirection::Under,
+                    150,
+                    PayoutRounding::Floor
+                )
+                .unwrap(),
+                payout
+            );
+
+            // A bet wins (roll - 1) times in `range`, the inverse of its multiplier before the edge
+            let wins = (0..SAMPLES)
+                .filter(|i| {
+                    let entropy = hashv(&[&i.to_le_bytes(), &range.to_le_bytes()]).to_bytes();
+                    roll_from_entropy(&entropy, range) < roll
+                })
+                .count() as u32;
+            let expected = SAMPLES * (roll - 1) as u32 / range as u32;
+            assert!(
+                wins.abs_diff(expected) < 600,
+                "{wins} wins under {roll} of {range}, expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn tie_loses_unless_the_bet_was_placed_with_push_on_ties() {
+        // A signature rolling exactly the prediction of 50
+        let tie = (0u8..=255)
+            .map(|byte| [byte; 64])
+            .find(|signature| roll_from_entropy(&entropy_from_signatures(&[*signature]), 100) == 50)
+            .unwrap();
+        let check = |tie_is_push| {
+            crate::VerifyFairness {}
+                .verify_fairness(
+                    &[tie],
+                    1_000,
+                    50,
+                    BetDirection::Under,
+                    100,
+                    150,
+                    tie_is_push,
+                    PayoutRounding::Floor,
+                )
+                .unwrap()
+        };
+
+        // Strictly under 50 wins, so the tie loses the whole stake by default
+        let lost = check(false);
+        assert_eq!((lost.roll, lost.won, lost.payout), (50, false, 0));
+
+        // A push returns the stake instead
+        let push = check(true);
+        assert_eq!((push.roll, push.won, push.payout), (50, false, 1_000));
+    }
+
+    #[test]
+    fn win_probability_follows_the_range_and_direction() {
+        assert_eq!(win_probability_bp(50, 100, BetDirection::Under), 4900);
+        assert_eq!(win_probability_bp(50, 100, BetDirection::Over), 5000);
+        assert_eq!(win_probability_bp(4, 6, BetDirection::Under), 5000);
+        assert_eq!(win_probability_bp(2, 1_000, BetDirection::Under), 10);
+
+        // Rounded down, and 0 for a prediction no roll can win or an empty range
+        assert_eq!(win_probability_bp(3, 6, BetDirection::Under), 3333);
+        assert_eq!(win_probability_bp(100, 100, BetDirection::Over), 0);
+        assert_eq!(win_probability_bp(50, 0, BetDirection::Under), 0);
+    }
+
+    #[test]
+    fn payout_matches_the_documented_examples() {
+        let payout = |amount, roll, range, direction, rounding| {
+            calculate_payout(amount, roll, range, direction, 150, rounding).unwrap()
+        };
+        let sol = 1_000_000_000;
+
+        // 1 SOL under 50 of 100 = 9850 * 100 / 490000 SOL, ~2.01 SOL
+        assert_eq!(
+            payout(sol, 50, 100, BetDirection::Under, PayoutRounding::Floor),
+            2_010_204_081
+        );
+        assert_eq!(
+            payout(sol, 50, 100, BetDirection::Under, PayoutRounding::Nearest),
+            2_010_204_082
+        );
+        assert_eq!(
+            payout(sol, 50, 100, BetDirection::Over, PayoutRounding::Floor),
+            1_970_000_000
+        );
+        assert_eq!(
+            payout(sol, 4, 6, BetDirection::Under, PayoutRounding::Floor),
+            1_970_000_000
+        );
+        assert_eq!(
+            payout(1, 2, 100, BetDirection::Under, PayoutRounding::Nearest),
+            99
+        );
+    }
+
+    #[test]
+    fn nearest_rounding_is_floor_plus_one_only_from_half_a_lamport() {
+        let payout = |amount, roll, rounding| {
+            calculate_payout(amount, roll, 100, BetDirection::Under, 150, rounding).unwrap()
+        };
+
+        // The remainder of the exact payout (commented) decides the extra lamport
+        for (amount, roll, floor, nearest) in [
+            (1, 7, 16, 16),               // 16.42
+            (1, 4, 32, 33),               // 32.83
+            (2, 51, 3, 4),                // 3.94
+            (3, 4, 98, 99),               // 98.5, halves round up
+            (10_000, 51, 19_700, 19_700), // exact
+        ] {
+            assert_eq!(payout(amount, roll, PayoutRounding::Floor), floor);
+            assert_eq!(payout(amount, roll, PayoutRounding::Nearest), nearest);
+        }
+    }
+
+    #[test]
+    fn payout_rejects_an_edge_of_the_whole_wager_or_more() {
+        assert_eq!(
+            calculate_payout(
+                1,
+                50,
+                100,
+                BetDirection::Under,
+                10000,
+                PayoutRounding::Floor
+            )
+            .unwrap(),
+            0
+        );
+        assert_eq!(
+            calculate_payout(
+                1,
+                50,
+                100,
+                BetDirection::Under,
+                10001,
+                PayoutRounding::Floor
+            )
+            .unwrap_err(),
+            DiceError::InvalidHouseEdge.into()
+        );
+    }
+
+    #[test]
+    fn payout_at_the_bet_limits_is_in_range() {
+        use crate::{HOUSE_EDGE, MAX_BET_LAMPORTS, MAX_PAYOUT_LAMPORTS, MIN_BET_LAMPORTS};
+
+        // Largest bet on the lowest roll: the worst case the vault is funded for
+        let max = calculate_payout(
+            MAX_BET_LAMPORTS,
+            2,
+            100,
+            BetDirection::Under,
+            HOUSE_EDGE,
+            PayoutRounding::Floor,
+        )
+        .unwrap();
+        assert_eq!(max, 985_000_000_000);
+        assert_eq!(max, MAX_PAYOUT_LAMPORTS);
+
+        // Smallest bet on the highest roll still pays more than the stake
+        let min = calculate_payout(
+            MIN_BET_LAMPORTS,
+            96,
+            100,
+            BetDirection::Under,
+            HOUSE_EDGE,
+            PayoutRounding::Floor,
+        )
+        .unwrap();
+        assert_eq!(min, 10_368_421);
+        assert!(min > MIN_BET_LAMPORTS);
+    }
+
+    #[test]
+    fn payout_overflows_only_on_impossible_inputs() {
+        // u64::MAX wagers do not overflow the u128 intermediates
+        assert!(calculate_payout(
+            u64::MAX,
+            1,
+            crate::MAX_ROLL_RANGE,
+            BetDirection::Over,
+            10000 - 1,
+            PayoutRounding::Nearest
+        )
+        .is_ok());
+
+        // A payout above u64::MAX
+        assert_eq!(
+            calculate_payout(
+                u64::MAX,
+                2,
+                crate::MAX_ROLL_RANGE,
+                BetDirection::Under,
+                0,
+                PayoutRounding::Floor
+            )
+            .unwrap_err(),
+            DiceError::Overflow.into()
+        );
+
+        // Predictions without a winning roll
+        for (roll, direction) in [(1, BetDirection::Under), (100, BetDirection::Over)] {
+            assert_eq!(
+                calculate_payout(1, roll, 100, direction, 150, PayoutRounding::Floor).unwrap_err(),
+                DiceError::Overflow.into()
+            );
+        }
+    }
+
+    #[test]
+    fn ed25519_instruction_is_found_relative_to_the_current_one() {
+        // [compute budget, Ed25519, resolve]: the Ed25519 instruction is not at index 0
+        let other = Instruction::new_with_bytes(Pubkey::new_unique(), &[9], vec![]);
+        let ed25519 = Instruction::new_with_bytes(ed25519_program::ID, &[1], vec![]);
+        let mut data = instructions_sysvar(&[other.clone(), ed25519, other], 2);
+        let mut lamports = 1;
+        let sysvar = sysvar_account(&mut data, &mut lamports);
+
+        assert_eq!(
+            load_preceding_ed25519_instruction(&sysvar, 1).unwrap().data,
+            [1]
+        );
+        // A distance of 0 still means the directly preceding instruction
+        assert_eq!(
+            load_preceding_ed25519_instruction(&sysvar, 0).unwrap().data,
+            [1]
+        );
+        // Index 0 is the compute budget instruction, not an Ed25519 one
+        assert_eq!(
+            load_preceding_ed25519_instruction(&sysvar, 2).unwrap_err(),
+            DiceError::Ed25519Program.into()
+        );
+        // Nothing precedes the start of the transaction
+        assert_eq!(
+            load_preceding_ed25519_instruction(&sysvar, 3).unwrap_err(),
+            DiceError::Ed25519Program.into()
+        );
+    }
+
+    #[test]
+    fn ed25519_instructions_of_a_batch_are_found_in_bet_order() {
+        // [Ed25519 for bet 0, for bet 1, for bet 2, batch]: bet i sits count - i back
+        let ixs: Vec<Instruction> = (0..3u8)
+            .map(|i| Instruction::new_with_bytes(ed25519_program::ID, &[i], vec![]))
+            .chain([Instruction::new_with_bytes(crate::ID, &[], vec![])])
+            .collect();
+        let mut data = instructions_sysvar(&ixs, 3);
+        let mut lamports = 1;
+        let sysvar = sysvar_account(&mut data, &mut lamports);
+
+        for i in 0..3u8 {
+            let distance = 3 - i as u16;
+            assert_eq!(
+                load_preceding_ed25519_instruction(&sysvar, distance)
+                    .unwrap()
+                    .data,
+                [i]
+            );
+        }
+    }
+
+    #[test]
+    fn ed25519_instructions_of_a_batch_must_directly_precede_it() {
+        let budget = Instruction::new_with_bytes(Pubkey::new_unique(), &[9], vec![]);
+        let ed25519 = |i: u8| Instruction::new_with_bytes(ed25519_program::ID, &[i], vec![]);
+        let batch = Instruction::new_with_bytes(crate::ID, &[], vec![]);
+
+        // [compute budget, Ed25519 for bet 0, for bet 1, batch]: found past the budget
+        let ixs = [budget.clone(), ed25519(0), ed25519(1), batch.clone()];
+        let mut data = instructions_sysvar(&ixs, 3);
+        let mut lamports = 1;
+        let sysvar = sysvar_account(&mut data, &mut lamports);
+        for i in 0..2u8 {
+            let distance = 2 - i as u16;
+            assert_eq!(
+                load_preceding_ed25519_instruction(&sysvar, distance)
+                    .unwrap()
+                    .data,
+                [i]
+            );
+        }
+
+        // [Ed25519 for bet 0, compute budget, Ed25519 for bet 1, batch]: bet 0 finds
+        // the budget instruction in its slot
+        let ixs = [ed25519(0), budget, ed25519(1), batch];
+        let mut data = instructions_sysvar(&ixs, 3);
+        let mut lamports = 1;
+        let sysvar = sysvar_account(&mut data, &mut lamports);
+        assert_eq!(
+            load_preceding_ed25519_instruction(&sysvar, 2).unwrap_err(),
+            DiceError::Ed25519Program.into()
+        );
+        assert_eq!(
+            load_preceding_ed25519_instruction(&sysvar, 1).unwrap().data,
+            [1]
+        );
+    }
+
+    #[test]
+    fn ed25519_instruction_with_accounts_is_rejected() {
+        let ed25519 = Instruction::new_with_bytes(
+            ed25519_program::ID,
+            &[1],
+            vec![AccountMeta::new_readonly(Pubkey::new_unique(), false)],
+        );
+        let resolve = Instruction::new_with_bytes(crate::ID, &[], vec![]);
+        let mut data = instructions_sysvar(&[ed25519, resolve], 1);
+        let mut lamports = 1;
+        let sysvar = sysvar_account(&mut data, &mut lamports);
+
+        assert_eq!(
+            load_preceding_ed25519_instruction(&sysvar, 1).unwrap_err(),
+            DiceError::Ed25519Accounts.into()
+        );
+    }
+
+    #[test]
+    fn lp_shares_are_pro_rata_between_two_providers() {
+        const SOL: u64 = 1_000_000_000;
+
+        // The house owns the 10 SOL already in the vault, one share per lamport
+        let mut lp_shares = 10 * SOL;
+        let mut value = 10 * SOL;
+
+        let first = shares_for_deposit(SOL, lp_shares, value).unwrap();
+        lp_shares += first;
+        value += SOL;
+        let second = shares_for_deposit(2 * SOL, lp_shares, value).unwrap();
+        lp_shares += second;
+        value += 2 * SOL;
+        assert_eq!(second, 2 * first);
+
+        // The vault doubles: every provider's stake doubles with it
+        assert_eq!(
+            lamports_for_shares(first, lp_shares, 2 * value).unwrap(),
+            2 * SOL
+        );
+        assert_eq!(
+            lamports_for_shares(second, lp_shares, 2 * value).unwrap(),
+            4 * SOL
+        );
+
+        // The vault halves: every provider shares the loss
+        assert_eq!(
+            lamports_for_shares(first, lp_shares, value / 2).unwrap(),
+            SOL / 2
+        );
+        assert_eq!(
+            lamports_for_shares(second, lp_shares, value / 2).unwrap(),
+            SOL
+        );
+    }
+
+    #[test]
+    fn lp_share_rounding_never_pays_out_more_than_deposited() {
+        // Three shares for two lamports of value: deposits and redemptions round down
+        let shares = shares_for_deposit(1, 3, 2).unwrap();
+        assert_eq!(shares, 1);
+        assert_eq!(lamports_for_shares(shares, 4, 3).unwrap(), 0);
+
+        assert_eq!(shares_for_deposit(5, 0, 0).unwrap(), 5);
+        assert_eq!(
+            shares_for_deposit(5, 3, 0).unwrap_err(),
+            DiceError::InvalidShares.into()
+        );
+        assert_eq!(
+            lamports_for_shares(1, 0, 3).unwrap_err(),
+            DiceError::InvalidShares.into()
+        );
+    }
+
+    #[test]
+    fn vault_state_hash_changes_with_the_balance_and_open_bets() {
+        let state = vault_state_hash(10_000_000_000, 3);
+        assert_eq!(state, vault_state_hash(10_000_000_000, 3));
+        // A payout or a resolved bet mid-batch would change it
+        assert_ne!(state, vault_state_hash(9_990_000_000, 3));
+        assert_ne!(state, vault_state_hash(10_000_000_000, 2));
+    }
+
+    #[test]
+    fn house_signature_argument_must_be_64_bytes() {
+        let resolve = Instruction::new_with_bytes(crate::ID, &[], vec![]);
+        let mut data = instructions_sysvar(&[resolve], 0);
+        let mut lamports = 1;
+        let sysvar = sysvar_account(&mut data, &mut lamports);
+
+        // Rejected before the sysvar is read, with no Ed25519 instruction present
+        for len in [0, 63, 65] {
+            assert_eq!(
+                verify_house_signature(&sysvar, &Pubkey::new_unique(), &[], &vec![0; len], 1)
+                    .unwrap_err(),
+                DiceError::Ed25519DataLength.into()
+            );
+        }
+    }
+
+    #[test]
+    fn charity_donation_is_carved_out_of_the_stake() {
+        // 2.5% of a 1 SOL stake goes to charity; only the rest is wagered
+        let amount = 1_000_000_000;
+        let donation = stake_share(amount, 250).unwrap();
+        assert_eq!(donation, 25_000_000);
+        assert_eq!(amount - donation, 975_000_000);
+
+        // Disabled, and rounded down in the wager's favour
+        assert_eq!(stake_share(amount, 0).unwrap(), 0);
+        assert_eq!(stake_share(399, 250).unwrap(), 9);
+        assert_eq!(stake_share(u64::MAX, 10000).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn slow_resolution_bonus_is_paid_only_past_the_threshold() {
+        let amount = 1_000_000_000;
+
+        // 1% of the wager once resolution took more than 100 slots
+        assert_eq!(slow_resolution_bonus(amount, 100, 100, 100).unwrap(), 0);
+        assert_eq!(
+            slow_resolution_bonus(amount, 101, 100, 100).unwrap(),
+            10_000_000
+        );
+
+        // A threshold of 0 disables the bonus however slow the resolution
+        assert_eq!(slow_resolution_bonus(amount, u64::MAX, 0, 100).unwrap(), 0);
+    }
+
+    #[test]
+    fn threshold_entropy_depends_on_every_signature() {
+        let house = [1u8; 64];
+        let co_signer = [2u8; 64];
+
+        // Without co-signers the entropy is the hash of the house signature
+        assert_eq!(
+            entropy_from_signatures(&[house]),
+            solana_program::hash::hash(&house).to_bytes()
+        );
+
+        // A co-signature changes the roll, so the house alone cannot choose it
+        let entropy = entropy_from_signatures(&[house, co_signer]);
+        assert_eq!(entropy, hashv(&[&house, &co_signer]).to_bytes());
+        assert_ne!(entropy, entropy_from_signatures(&[house]));
+        assert_ne!(entropy, entropy_from_signatures(&[house, [3u8; 64]]));
+        assert_ne!(entropy, entropy_from_signatures(&[co_signer, house]));
+    }
+
+    #[test]
+    fn vault_holding_data_fails_account_validation() {
+        assert!(validate_claim_rebate(vec![]).is_ok());
+        assert_eq!(
+            validate_claim_rebate(vec![0; 8]).unwrap_err(),
+            DiceError::VaultHasData.into()
+        );
+    }
+
+    #[test]
+    fn insured_loss_returns_the_cover_and_premiums_accumulate() {
+        let wager = 1_000_000_000;
+        let rent_floor = 890_880;
+
+        // Each insured bet pays its premium into the pool
+        let premium = stake_share(wager, INSURANCE_PREMIUM_BPS).unwrap();
+        assert_eq!(premium, 10_000_000);
+        let pool = (0..80).fold(rent_floor, |pool, _| pool + premium);
+
+        // An insured loss returns the configured fraction of the wager
+        let cover = insurance_cover(wager, pool, rent_floor).unwrap();
+        assert_eq!(cover, 500_000_000);
+        assert_eq!(cover, stake_share(wager, INSURANCE_COVER_BPS).unwrap());
+
+        // A short pool pays what it holds above its rent-exempt minimum
+        assert_eq!(
+            insurance_cover(wager, rent_floor + premium, rent_floor).unwrap(),
+            premium
+        );
+        assert_eq!(insurance_cover(wager, rent_floor, rent_floor).unwrap(), 0);
+        assert_eq!(insurance_cover(wager, 0, rent_floor).unwrap(), 0);
+    }
+
+    #[test]
+    fn roll_2_stakes_are_capped_by_the_vault_share() {
+        let sol = 1_000_000_000;
+        let cap = max_payout_for_vault(100 * sol);
+        assert_eq!(cap, 10 * sol);
+
+        // Roll 2 pays 98.5x, so a 100 SOL vault takes at most ~0.1015 SOL on it
+        let payout = |amount| {
+            calculate_payout(
+                amount,
+                2,
+                100,
+                BetDirection::Under,
+                150,
+                PayoutRounding::Floor,
+            )
+            .unwrap()
+        };
+        assert!(payout(sol / 10) <= cap);
+        assert!(payout(sol / 10 + sol / 100) > cap);
+        assert_eq!(max_payout_for_vault(u64::MAX), u64::MAX / 10);
+    }
+
+    #[test]
+    fn vault_pays_out_only_above_its_rent_exempt_minimum_and_reserve() {
+        stub_syscalls();
+        let floor = Rent::default().minimum_balance(0) + MIN_VAULT_RESERVE_LAMPORTS;
+        let key = Pubkey::new_unique();
+        let owner = anchor_lang::system_program::ID;
+
+        let available = |lamports: u64, data: &mut [u8]| {
+            let mut lamports = lamports;
+            let vault = AccountInfo::new(&key, false, true, &mut lamports, data, &owner, false, 0);
+            vault_available_lamports(&vault).unwrap()
+        };
+
+        assert_eq!(available(floor + 1_000, &mut []), 1_000);
+        assert_eq!(available(floor, &mut []), 0);
+        assert_eq!(available(floor - 1, &mut []), 0);
+
+        // Account data raises the rent-exempt minimum
+        assert!(available(floor + 1_000, &mut [0; 8]) < 1_000);
+    }
+
+    #[cfg(feature = "verbose-logs")]
+    #[test]
+    fn refund_bet_logs_its_eligibility() {
+        use crate::{Bet, BetTracker, RefundBet, RefundBetBumps};
+
+        // `msg!` prints to stdout on the host, so the test reads the log line from
+        // its own output in a child run
+        const CHILD: &str = "DICE_VERBOSE_LOGS_CHILD";
+        if std::env::var_os(CHILD).is_none() {
+            let output = std::process::Command::new(std::env::current_exe().unwrap())
+                .args(["--exact", "utils::tests::refund_bet_logs_its_eligibility"])
+                .arg("--nocapture")
+                .env(CHILD, "1")
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+            assert!(String::from_utf8_lossy(&output.stdout)
+                .contains("refund_bet: slots_passed=10 timeout=30 eligible=false"));
+            return;
+        }
+
+        stub_syscalls();
+        let player = Pubkey::new_unique();
+        let house = Pubkey::new_unique();
+        let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &crate::ID);
+        let (vault, vault_bump) = pda(&[b"vault", house.as_ref()]);
+        let (bet, bet_bump) = pda(&[b"bet", player.as_ref(), &7u128.to_le_bytes()]);
+        let (config, config_bump) = pda(&[b"config", house.as_ref()]);
+        let (tracker, tracker_bump) = pda(&[b"tracker", house.as_ref()]);
+        let (player_stats, player_stats_bump) =
+            pda(&[b"player_stats", house.as_ref(), player.as_ref()]);
+
+        let system = anchor_lang::system_program::ID;
+        let keys = [
+            player,
+            house,
+            vault,
+            bet,
+            config,
+            tracker,
+            player_stats,
+            system,
+        ];
+        let owners = [
+            system,
+            system,
+            system,
+            crate::ID,
+            crate::ID,
+            crate::ID,
+            crate::ID,
+            system,
+        ];
+        let mut lamports = [1_000_000_000u64; 8];
+        let mut data = [
+            vec![],
+            vec![],
+            vec![],
+            program_account::<Bet>(|b| {
+                b.player = player;
+                b.house = house;
+                b.seed = 7;
+                b.commit_slot = HOST_SLOT - 10;
+                b.bump = bet_bump;
+            }),
+            program_account::<GameConfig>(|c| {
+                c.refund_timeout_slots = 30;
+                c.vault_bump = vault_bump;
+                c.bump = config_bump;
+            }),
+            program_account::<BetTracker>(|t| t.bump = tracker_bump),
+            program_account::<PlayerStats>(|s| s.bump = player_stats_bump),
+            vec![],
+        ];
+        let accounts = host_accounts(&keys, &owners, &mut lamports, &mut data);
+
+        let mut bumps = RefundBetBumps::default();
+        let mut refund = RefundBet::try_accounts(
+            &crate::ID,
+            &mut &accounts[..],
+            &[],
+            &mut bumps,
+            &mut std::collections::BTreeSet::new(),
+        )
+        .unwrap();
+        // Logged before the rejection, so a failed refund shows how long remains
+        assert_eq!(
+            refund.refund_bet(&bumps).unwrap_err(),
+            DiceError::RefundNotEligible.into()
+        );
+    }
+}
@@ -1,16 +1,2333 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program } from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { sha256 } from "@noble/hashes/sha256";
+import { assert } from "chai";
 import { Dice } from "../target/types/dice";
 
+const {
+  AddressLookupTableProgram,
+  ComputeBudgetProgram,
+  Ed25519Program,
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction,
+  TransactionMessage,
+  VersionedTransaction,
+  sendAndConfirmTransaction,
+} = anchor.web3;
+
 describe("dice", () => {
   // Configure the client to use the local cluster.
   anchor.setProvider(anchor.AnchorProvider.env());
 
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const connection = provider.connection;
   const program = anchor.workspace.dice as Program<Dice>;
 
-  it("Is initialized!", async () => {
-    // Add your test here.
-    const tx = await program.methods.initialize().rpc();
-    console.log("Your transaction signature", tx);
+  // One game for the whole suite, started at the smallest max bet so a few
+  // SOL cover its worst-case payout
+  const house = Keypair.generate();
+  const player = Keypair.generate();
+  const randomness = Keypair.generate();
+  const BET = new BN(LAMPORTS_PER_SOL / 100);
+  const UNDER = { under: {} };
+  const OVER = { over: {} };
+
+  const pda = (...seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+  const vault = pda(Buffer.from("vault"), house.publicKey.toBuffer());
+  const tracker = pda(Buffer.from("tracker"), house.publicKey.toBuffer());
+  const stats = pda(Buffer.from("stats"), house.publicKey.toBuffer());
+  const betPda = (seed: BN, owner = player.publicKey) =>
+    pda(
+      Buffer.from("bet"),
+      owner.toBuffer(),
+      seed.toArrayLike(Buffer, "le", 16)
+    );
+  const playerStatsPda = (owner: anchor.web3.PublicKey) =>
+    pda(
+      Buffer.from("player_stats"),
+      house.publicKey.toBuffer(),
+      owner.toBuffer()
+    );
+  const referralPda = (referrer: anchor.web3.PublicKey, game = house) =>
+    pda(
+      Buffer.from("referral"),
+      game.publicKey.toBuffer(),
+      referrer.toBuffer()
+    );
+
+  const airdrop = async (to: anchor.web3.PublicKey, sol: number) => {
+    const sig = await connection.requestAirdrop(to, sol * LAMPORTS_PER_SOL);
+    await connection.confirmTransaction(
+      { signature: sig, ...(await connection.getLatestBlockhash()) },
+      "confirmed"
+    );
+  };
+
+  const waitSlots = async (slots: number) => {
+    const target = (await connection.getSlot()) + slots;
+    while ((await connection.getSlot()) < target) {
+      await new Promise((resolve) => setTimeout(resolve, 200));
+    }
+  };
+
+  // Fails unless the promise rejects with the named program error
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+    } catch (err) {
+      const logs = (err.logs ?? err.transactionLogs ?? []).join("\n");
+      assert.include(`${err}\n${logs}`, code);
+      return;
+    }
+    assert.fail(`expected ${code}`);
+  };
+
+  // Instructions the program invoked while executing the last instruction
+  const innerInstructionsOf = async (signature: string) => {
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const last = tx.transaction.message.compiledInstructions.length - 1;
+    const inner = tx.meta.innerInstructions.find((ix) => ix.index === last);
+    return inner ? inner.instructions : [];
+  };
+
+  // The suite's player rolls under 50 in the suite's game unless told otherwise
+  type BetOptions = {
+    bettor?: anchor.web3.Keypair;
+    roll?: number;
+    direction?: object;
+    insured?: boolean;
+    insurancePool?: anchor.web3.PublicKey;
+    committedHash?: number[];
+    vrf?: boolean;
+    game?: anchor.web3.Keypair;
+    randomnessAccount?: anchor.web3.PublicKey;
+    minAcceptablePayout?: BN;
+    referrer?: anchor.web3.PublicKey;
+    amount?: BN;
+  };
+
+  const placeBet = (seed: BN, options: BetOptions = {}) =>
+    program.methods
+      .placeBet(
+        seed,
+        options.roll ?? 50,
+        options.direction ?? UNDER,
+        options.amount ?? BET,
+        !!options.insured,
+        options.minAcceptablePayout ?? new BN(0),
+        options.committedHash ?? Array(32).fill(0),
+        options.referrer ?? null,
+        !!options.vrf
+      )
+      .accountsPartial({
+        player: (options.bettor ?? player).publicKey,
+        house: (options.game ?? house).publicKey,
+        randomnessAccount: options.randomnessAccount ?? randomness.publicKey,
+        insurancePool: options.insurancePool ?? null,
+        jackpotPool: null,
+        winnings: null,
+        charity: null,
+        referral: options.referrer
+          ? referralPda(options.referrer, options.game ?? house)
+          : null,
+      })
+      .signers([options.bettor ?? player]);
+
+  const cancelBet = (seed: BN) =>
+    program.methods.cancelBet().accountsPartial({
+      player: player.publicKey,
+      house: house.publicKey,
+      bet: betPda(seed),
+      randomnessAccount: randomness.publicKey,
+    });
+
+  // The house signs the bet address followed by the serialized bet data; the
+  // account holds room for a referrer, which serializes to one byte when unset
+  // Under BIND_VAULT_STATE the house also signs the vault balance and open bet
+  // count as they stand before the resolution, or before the whole batch
+  const bindVaultState = program.idl.constants.some(
+    ({ name, value }) => name === "bindVaultState" && value === "true"
+  );
+  const vaultState = async (game: anchor.web3.Keypair) => {
+    const balance = await connection.getBalance(
+      pda(Buffer.from("vault"), game.publicKey.toBuffer())
+    );
+    const { openBets } = await program.account.betTracker.fetch(
+      pda(Buffer.from("tracker"), game.publicKey.toBuffer())
+    );
+    return Buffer.from(
+      sha256(
+        Buffer.concat([
+          new BN(balance).toArrayLike(Buffer, "le", 8),
+          new BN(openBets).toArrayLike(Buffer, "le", 4),
+        ])
+      )
+    );
+  };
+
+  const houseSignature = async (
+    seed: BN,
+    game = house,
+    owner = player.publicKey,
+    signer = game
+  ) => {
+    const bet = betPda(seed, owner);
+    const { data } = await connection.getAccountInfo(bet);
+    const { referrer } = await program.account.bet.fetch(bet);
+    // The bet message is the hash of the bet address and the serialized bet
+    const message = Buffer.concat([
+      sha256(
+        Buffer.concat([
+          bet.toBuffer(),
+          data.subarray(8, data.length - (referrer ? 0 : 32)),
+        ])
+      ),
+      bindVaultState ? await vaultState(game) : Buffer.alloc(0),
+    ]);
+    const ix = Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: signer.secretKey,
+      message,
+    });
+    // Header (16 bytes) and public key (32 bytes) precede the signature
+    return { ix, sig: Buffer.from(ix.data.subarray(48, 112)) };
+  };
+
+  // Accounts shared by every single-bet resolution path
+  const resolveAccounts = (seed: BN, game = house) => ({
+    house: game.publicKey,
+    authority: game.publicKey,
+    player: player.publicKey,
+    bet: betPda(seed),
+    randomnessAccount: randomness.publicKey,
+    insurancePool: null,
+    jackpotPool: null,
+    referral: null,
+    winnings: null,
+    treasury: null,
+    instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+  });
+
+  // Sent with the house paying the fee, which keeps the Ed25519 instruction
+  // and the resolution within the transaction size limit
+  const resolveBet = async (
+    seed: BN,
+    { ix, sig }: { ix: anchor.web3.TransactionInstruction; sig: Buffer },
+    game = house
+  ) => {
+    const resolve = await program.methods
+      .resolveBet(sig)
+      .accountsPartial(resolveAccounts(seed, game))
+      .instruction();
+    return sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(ix, resolve),
+      [game],
+      { commitment: "confirmed" }
+    );
+  };
+
+  // A game of its own, for tests that change or drain it
+  const newGame = async () => {
+    const game = Keypair.generate();
+    await airdrop(game.publicKey, 3);
+    await program.methods
+      .initialize(new BN(2 * LAMPORTS_PER_SOL), BET)
+      .accountsPartial({ house: game.publicKey })
+      .signers([game])
+      .rpc();
+    return game;
+  };
+
+  // The update_config arguments, at the values a new game starts with
+  const CONFIG = {
+    houseEdgeBp: 150,
+    minBet: BET,
+    maxBet: BET,
+    rollRange: 100,
+    minRoll: 2,
+    maxRoll: 96,
+    refundTimeoutSlots: new BN(150),
+    cancelFeeBps: 200,
+  };
+  const updateConfig = (
+    game: anchor.web3.Keypair,
+    changes: Partial<typeof CONFIG>
+  ) => {
+    const config = { ...CONFIG, ...changes };
+    return program.methods
+      .updateConfig(
+        config.houseEdgeBp,
+        config.minBet,
+        config.maxBet,
+        config.rollRange,
+        config.minRoll,
+        config.maxRoll,
+        config.refundTimeoutSlots,
+        config.cancelFeeBps
+      )
+      .accountsPartial({ house: game.publicKey, authority: game.publicKey })
+      .signers([game])
+      .rpc();
+  };
+
+  before(async () => {
+    await airdrop(house.publicKey, 10);
+    await airdrop(player.publicKey, 5);
+    await airdrop(randomness.publicKey, 1);
+  });
+
+  it("rejects a starting max bet above MAX_BET_LAMPORTS", async () => {
+    await expectError(
+      program.methods
+        .initialize(new BN(2 * LAMPORTS_PER_SOL), new BN(11 * LAMPORTS_PER_SOL))
+        .accountsPartial({ house: house.publicKey })
+        .signers([house])
+        .rpc(),
+      "InvalidBetLimits"
+    );
+  });
+
+  it("initializes the game", async () => {
+    await program.methods
+      .initialize(new BN(2 * LAMPORTS_PER_SOL), BET)
+      .accountsPartial({ house: house.publicKey })
+      .signers([house])
+      .rpc();
+
+    const config = await program.account.gameConfig.fetch(
+      pda(Buffer.from("config"), house.publicKey.toBuffer())
+    );
+    assert.ok(config.authority.equals(house.publicKey));
+    assert.ok(config.maxBet.eq(BET));
+    assert.isAtLeast(await connection.getBalance(vault), 2 * LAMPORTS_PER_SOL);
+  });
+
+  it("resolves a bet once and closes it", async () => {
+    const seed = new BN(1);
+    await placeBet(seed).rpc();
+    assert.equal((await program.account.betTracker.fetch(tracker)).openBets, 1);
+
+    await waitSlots(1);
+    const signed = await houseSignature(seed);
+    await resolveBet(seed, signed);
+
+    assert.isNull(await program.account.bet.fetchNullable(betPda(seed)));
+    const after = await program.account.betTracker.fetch(tracker);
+    assert.equal(after.openBets, 0);
+    assert.ok(after.openStakes.isZero());
+    assert.ok((await program.account.houseStats.fetch(stats)).totalBets.eqn(1));
+
+    // The closed bet can never be resolved again, even with a valid signature
+    await waitSlots(1);
+    await expectError(resolveBet(seed, signed), "AccountNotInitialized");
+  });
+
+  it("refunds a bet only after the refund timeout", async () => {
+    await program.methods
+      .updateConfig(150, BET, BET, 100, 2, 96, new BN(30), 200)
+      .accountsPartial({ house: house.publicKey, authority: house.publicKey })
+      .signers([house])
+      .rpc();
+
+    const seed = new BN(2);
+    await placeBet(seed).rpc();
+    const refund = () =>
+      program.methods
+        .refundBet()
+        .accountsPartial({
+          player: player.publicKey,
+          house: house.publicKey,
+          bet: betPda(seed),
+        })
+        .signers([player])
+        .rpc();
+
+    await expectError(refund(), "RefundNotEligible");
+    await waitSlots(30);
+    await refund();
+
+    assert.isTrue((await program.account.bet.fetch(betPda(seed))).isResolved);
+    assert.equal((await program.account.betTracker.fetch(tracker)).openBets, 0);
+    await waitSlots(1);
+    await expectError(refund(), "BetAlreadyResolved");
+
+    await program.methods
+      .closeBet()
+      .accountsPartial({ player: player.publicKey, bet: betPda(seed) })
+      .signers([player])
+      .rpc();
+    assert.isNull(await program.account.bet.fetchNullable(betPda(seed)));
+  });
+
+  it("cancels a bet only before it becomes resolvable", async () => {
+    const vaultBefore = await connection.getBalance(vault);
+
+    // Placed and cancelled in one transaction, so in the placement slot
+    const seed = new BN(3);
+    await placeBet(seed)
+      .postInstructions([await cancelBet(seed).instruction()])
+      .rpc();
+
+    assert.isTrue((await program.account.bet.fetch(betPda(seed))).isResolved);
+    assert.equal((await program.account.betTracker.fetch(tracker)).openBets, 0);
+    // The 2% fee stays in the vault
+    assert.equal(
+      (await connection.getBalance(vault)) - vaultBefore,
+      BET.toNumber() / 50
+    );
+
+    const late = new BN(4);
+    await placeBet(late).rpc();
+    await waitSlots(1);
+    await expectError(
+      cancelBet(late).signers([player]).rpc(),
+      "CancelWindowClosed"
+    );
+  });
+
+  it("finalizes only bets waiting out a dispute window", async () => {
+    const finalize = (seed: BN) =>
+      program.methods
+        .finalizeResolution()
+        .accountsPartial({
+          house: house.publicKey,
+          player: player.publicKey,
+          bet: betPda(seed),
+          referral: null,
+          winnings: null,
+          treasury: null,
+        })
+        .rpc();
+
+    // Still pending (placed in the previous test), and cancelled
+    await expectError(finalize(new BN(4)), "BetNotPendingFinalization");
+    await expectError(finalize(new BN(3)), "BetNotPendingFinalization");
+  });
+
+  it("resolves a full batch of bets", async () => {
+    type BatchBet = { seed: BN; bettor: anchor.web3.Keypair };
+    const resolveBatch = (bets: BatchBet[], sigs: Buffer[]) =>
+      program.methods
+        .resolveBetsBatch(sigs)
+        .accountsPartial({
+          house: house.publicKey,
+          authority: house.publicKey,
+          insurancePool: null,
+          jackpotPool: null,
+          treasury: null,
+          instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        })
+        .remainingAccounts(
+          [].concat(
+            ...bets.map(({ seed, bettor }) => [
+              {
+                pubkey: betPda(seed, bettor.publicKey),
+                isSigner: false,
+                isWritable: true,
+              },
+              { pubkey: bettor.publicKey, isSigner: false, isWritable: true },
+              {
+                pubkey: playerStatsPda(bettor.publicKey),
+                isSigner: false,
+                isWritable: true,
+              },
+              {
+                pubkey: randomness.publicKey,
+                isSigner: false,
+                isWritable: false,
+              },
+            ])
+          )
+        );
+
+    await expectError(
+      resolveBatch([], []).signers([house]).rpc(),
+      "InvalidBatchSize"
+    );
+
+    // MAX_BATCH_RESOLVE bets, two of them from the same player
+    const other = Keypair.generate();
+    await airdrop(other.publicKey, 1);
+    const bets: BatchBet[] = [
+      { seed: new BN(5), bettor: player },
+      { seed: new BN(18), bettor: other },
+      { seed: new BN(19), bettor: player },
+    ];
+    for (const { seed, bettor } of bets) {
+      await placeBet(seed, { bettor }).rpc();
+    }
+    const bettors = [player, other];
+    const statsBefore = await Promise.all(
+      bettors.map((bettor) =>
+        program.account.playerStats.fetch(playerStatsPda(bettor.publicKey))
+      )
+    );
+    const balancesBefore = await Promise.all(
+      bettors.map((bettor) => connection.getBalance(bettor.publicKey))
+    );
+    const openBets = (await program.account.betTracker.fetch(tracker)).openBets;
+    await waitSlots(1);
+
+    const signed = [];
+    for (const { seed, bettor } of bets) {
+      signed.push(await houseSignature(seed, house, bettor.publicKey));
+    }
+    const batch = await resolveBatch(
+      bets,
+      signed.map(({ sig }) => sig)
+    ).instruction();
+
+    // Three Ed25519 instructions only fit with the batch accounts looked up
+    // from a table; they must directly precede the batch, in bet order
+    const addresses = batch.keys
+      .filter(({ isSigner }) => !isSigner)
+      .map(({ pubkey }) => pubkey)
+      .filter((key, i, all) => all.findIndex((k) => k.equals(key)) === i);
+    const [create, table] = AddressLookupTableProgram.createLookupTable({
+      authority: house.publicKey,
+      payer: house.publicKey,
+      recentSlot: await connection.getSlot("finalized"),
+    });
+    const extend = AddressLookupTableProgram.extendLookupTable({
+      authority: house.publicKey,
+      payer: house.publicKey,
+      lookupTable: table,
+      addresses,
+    });
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(create, extend),
+      [house],
+      { commitment: "confirmed" }
+    );
+    // Addresses only become usable in the slot after they were added
+    await waitSlots(1);
+    const lookupTable = (await connection.getAddressLookupTable(table)).value;
+
+    const { blockhash, lastValidBlockHeight } =
+      await connection.getLatestBlockhash();
+    const tx = new VersionedTransaction(
+      new TransactionMessage({
+        payerKey: house.publicKey,
+        recentBlockhash: blockhash,
+        instructions: [
+          ComputeBudgetProgram.setComputeUnitLimit({ units: 1_000_000 }),
+          ...signed.map(({ ix }) => ix),
+          batch,
+        ],
+      }).compileToV0Message([lookupTable])
+    );
+    tx.sign([house]);
+    const signature = await connection.sendTransaction(tx);
+    await connection.confirmTransaction(
+      { signature, blockhash, lastValidBlockHeight },
+      "confirmed"
+    );
+
+    for (const { seed, bettor } of bets) {
+      assert.isNull(
+        await program.account.bet.fetchNullable(betPda(seed, bettor.publicKey))
+      );
+    }
+    assert.equal(
+      (await program.account.betTracker.fetch(tracker)).openBets,
+      openBets - 3
+    );
+
+    // Every bet was settled: each player received their payouts and bet rent,
+    // less anything deferred past the payout ceiling
+    const { meta } = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const resolved = [];
+    for (const event of new anchor.EventParser(
+      program.programId,
+      program.coder
+    ).parseLogs(meta.logMessages)) {
+      if (event.name === "betResolved") resolved.push(event.data);
+    }
+    assert.lengthOf(resolved, 3);
+
+    for (const [i, bettor] of bettors.entries()) {
+      const owed = resolved
+        .filter((event) => event.player.equals(bettor.publicKey))
+        .reduce(
+          (sum, event) => sum.add(event.payout).add(event.rentRefunded),
+          new BN(0)
+        );
+      const after = await program.account.playerStats.fetch(
+        playerStatsPda(bettor.publicKey)
+      );
+      const deferred = after.deferredPayout.sub(statsBefore[i].deferredPayout);
+      assert.equal(
+        (await connection.getBalance(bettor.publicKey)) -
+          balancesBefore[i] +
+          deferred.toNumber(),
+        owed.toNumber()
+      );
+      // Each bet's player stats were written back before the next bet loaded
+      assert.equal(
+        after.openBets,
+        statsBefore[i].openBets - (bettor === player ? 2 : 1)
+      );
+    }
+  });
+
+  it("binds signatures to the vault state under BIND_VAULT_STATE", async () => {
+    const seed = new BN(43);
+    await placeBet(seed).rpc();
+    await waitSlots(1);
+    const signed = await houseSignature(seed);
+
+    // Another bet changes the open bet count after the house signed
+    await placeBet(new BN(44)).rpc();
+    if (bindVaultState) {
+      await expectError(resolveBet(seed, signed), "Ed25519Signature");
+    } else {
+      await resolveBet(seed, signed);
+    }
+  });
+
+  it("mints LP shares pro rata and redeems them", async () => {
+    // Two providers deposit 1 and 2 SOL into the same vault
+    const providers = [Keypair.generate(), Keypair.generate()];
+    const lpOf = (provider: anchor.web3.Keypair) =>
+      pda(
+        Buffer.from("lp"),
+        house.publicKey.toBuffer(),
+        provider.publicKey.toBuffer()
+      );
+    for (const [i, provider] of providers.entries()) {
+      await airdrop(provider.publicKey, 3);
+      await program.methods
+        .fundVault(new BN((i + 1) * LAMPORTS_PER_SOL))
+        .accountsPartial({ funder: provider.publicKey, house: house.publicKey })
+        .signers([provider])
+        .rpc();
+    }
+
+    const [first, second] = await Promise.all(
+      providers.map(
+        async (provider) =>
+          (await program.account.vaultLp.fetch(lpOf(provider))).shares
+      )
+    );
+    const houseStats = await program.account.houseStats.fetch(stats);
+    assert.ok(first.gtn(0));
+    assert.ok(
+      houseStats.lpShares.eq(houseStats.houseShares.add(first).add(second))
+    );
+    // Twice the deposit at the same vault value is twice the shares, up to
+    // rounding of the first deposit's price
+    assert.isAtMost(second.sub(first.muln(2)).abs().toNumber(), 2);
+
+    // Each provider redeems their own share of the vault
+    for (const [i, provider] of providers.entries()) {
+      const { shares } = await program.account.vaultLp.fetch(lpOf(provider));
+      const vaultBefore = await connection.getBalance(vault);
+      await program.methods
+        .withdrawLp(shares)
+        .accountsPartial({
+          provider: provider.publicKey,
+          house: house.publicKey,
+        })
+        .signers([provider])
+        .rpc();
+
+      // Rounding never pays out more than was deposited
+      const left = await program.account.vaultLp.fetch(lpOf(provider));
+      assert.ok(left.shares.isZero());
+      const withdrawn = vaultBefore - (await connection.getBalance(vault));
+      assert.isAtMost(withdrawn, (i + 1) * LAMPORTS_PER_SOL);
+      assert.isAtLeast(withdrawn, (i + 1) * LAMPORTS_PER_SOL - 2);
+    }
+  });
+
+  it("resolves a committed bet only by revealing the secret", async () => {
+    const seed = new BN(10);
+    await placeBet(seed, { committedHash: Array(32).fill(7) }).rpc();
+    await waitSlots(1);
+
+    // Every other path fails before looking at its own randomness
+    await expectError(
+      resolveBet(seed, await houseSignature(seed)),
+      "RevealRequired"
+    );
+    await expectError(
+      program.methods
+        .resolveBetOracle(50, Buffer.alloc(64))
+        .accountsPartial(resolveAccounts(seed))
+        .signers([house])
+        .rpc(),
+      "RevealRequired"
+    );
+    await expectError(
+      program.methods
+        .resolveBetVrf()
+        .accountsPartial(resolveAccounts(seed))
+        .signers([house])
+        .rpc(),
+      "RevealRequired"
+    );
+  });
+
+  it("resolves a VRF bet only through Switchboard", async () => {
+    // One resolution method per bet: VRF cannot come with a commitment
+    await expectError(
+      placeBet(new BN(11), {
+        committedHash: Array(32).fill(7),
+        vrf: true,
+      }).rpc(),
+      "ResolutionMethodMismatch"
+    );
+
+    const seed = new BN(12);
+    await placeBet(seed, { vrf: true }).rpc();
+    assert.isTrue((await program.account.bet.fetch(betPda(seed))).vrf);
+    await waitSlots(1);
+
+    // The house cannot swap in its own randomness for the player's choice
+    await expectError(
+      resolveBet(seed, await houseSignature(seed)),
+      "ResolutionMethodMismatch"
+    );
+    await expectError(
+      program.methods
+        .resolveBetOracle(50, Buffer.alloc(64))
+        .accountsPartial(resolveAccounts(seed))
+        .signers([house])
+        .rpc(),
+      "ResolutionMethodMismatch"
+    );
+    await expectError(
+      program.methods
+        .revealAndResolve(Array(32).fill(0))
+        .accountsPartial(resolveAccounts(seed))
+        .signers([house])
+        .rpc(),
+      "ResolutionMethodMismatch"
+    );
+  });
+
+  it("resolves then leaves nothing to claim in push mode", async () => {
+    const winnings = pda(Buffer.from("winnings"), player.publicKey.toBuffer());
+    const seed = new BN(13);
+    await placeBet(seed).rpc();
+    await waitSlots(1);
+    await resolveBet(seed, await houseSignature(seed));
+
+    // The payout went straight to the player, so no escrow was ever created
+    assert.isNull(await connection.getAccountInfo(winnings));
+    await expectError(
+      program.methods
+        .claimWinnings()
+        .accountsPartial({ player: player.publicKey, winnings })
+        .signers([player])
+        .rpc(),
+      "AccountNotInitialized"
+    );
+  });
+
+  it("needs the insurance pool only for insured bets", async () => {
+    // Every other test places and resolves without either pool account
+    const seed = new BN(14);
+    await expectError(
+      placeBet(seed, { insured: true }).rpc(),
+      "PoolAccountRequired"
+    );
+
+    const pool = pda(Buffer.from("insurance"), house.publicKey.toBuffer());
+    const poolBefore = await connection.getBalance(pool);
+    await placeBet(seed, { insured: true, insurancePool: pool }).rpc();
+    // The 1% premium is paid into the pool on top of the wager
+    assert.equal(
+      (await connection.getBalance(pool)) - poolBefore,
+      BET.toNumber() / 100
+    );
+  });
+
+  it("makes no transfer for a zero payout", async () => {
+    // Resolve until both a loss and a win were seen, at about even odds each
+    const seen = { loss: false, win: false };
+    for (let i = 20; i < 40 && !(seen.loss && seen.win); i++) {
+      const seed = new BN(i);
+      await placeBet(seed).rpc();
+      await waitSlots(1);
+
+      const vaultBefore = await connection.getBalance(vault);
+      const signature = await resolveBet(seed, await houseSignature(seed));
+      const paid = vaultBefore - (await connection.getBalance(vault));
+
+      // A loss pays nothing and invokes nothing; a win is one system transfer
+      const cpis = await innerInstructionsOf(signature);
+      if (paid === 0) {
+        assert.lengthOf(cpis, 0);
+        seen.loss = true;
+      } else {
+        assert.lengthOf(cpis, 1);
+        seen.win = true;
+      }
+    }
+    assert.isTrue(seen.loss && seen.win);
+  });
+
+  it("rejects a payout the vault cannot cover", async () => {
+    // A game of its own, drained down to the stakes it owes
+    const game = await newGame();
+    const gameVault = pda(Buffer.from("vault"), game.publicKey.toBuffer());
+    const rent = await connection.getMinimumBalanceForRentExemption(0);
+
+    // Rolling over 2 wins 98% of the time, paying slightly more than the stake
+    for (let i = 40; i < 43; i++) {
+      const seed = new BN(i);
+      await placeBet(seed, { roll: 2, direction: OVER, game }).rpc();
+      const spare =
+        (await connection.getBalance(gameVault)) - rent - BET.toNumber();
+      await program.methods
+        .withdraw(new BN(spare))
+        .accountsPartial({
+          house: game.publicKey,
+          authority: game.publicKey,
+          primaryAuthority: game.publicKey,
+        })
+        .signers([game])
+        .rpc();
+      await waitSlots(1);
+
+      try {
+        // A loss pays nothing, so it settles; place another bet
+        await resolveBet(seed, await houseSignature(seed, game), game);
+      } catch (err) {
+        const logs = (err.logs ?? err.transactionLogs ?? []).join("\n");
+        assert.include(`${err}\n${logs}`, "InsufficientFunds");
+        assert.notInclude(logs, "insufficient lamports");
+        return;
+      }
+    }
+    assert.fail("expected a winning bet");
+  });
+
+  it("rejects resolving with a player the bet does not belong to", async () => {
+    // The intruder gets player stats in this game, so only the bet can fail
+    const intruder = Keypair.generate();
+    await airdrop(intruder.publicKey, 1);
+    await placeBet(new BN(15), { bettor: intruder }).rpc();
+
+    const seed = new BN(16);
+    await placeBet(seed).rpc();
+    await waitSlots(1);
+
+    const { ix, sig } = await houseSignature(seed);
+    const resolve = await program.methods
+      .resolveBet(sig)
+      .accountsPartial({ ...resolveAccounts(seed), player: intruder.publicKey })
+      .instruction();
+    await expectError(
+      sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(ix, resolve),
+        [house],
+        { commitment: "confirmed" }
+      ),
+      "NotPlayerBet"
+    );
+  });
+
+  it("reconciles a player's open bet count from their bets", async () => {
+    const bettor = Keypair.generate();
+    await airdrop(bettor.publicKey, 1);
+    const seeds = [new BN(50), new BN(51)];
+    for (const seed of seeds) {
+      await placeBet(seed, { bettor }).rpc();
+    }
+    const bets = seeds.map((seed) => ({
+      pubkey: betPda(seed, bettor.publicKey),
+      isSigner: false,
+      isWritable: false,
+    }));
+    const reconcile = (listed: typeof bets) =>
+      program.methods
+        .reconcilePlayerStats()
+        .accountsPartial({
+          house: house.publicKey,
+          authority: house.publicKey,
+          player: bettor.publicKey,
+        })
+        .remainingAccounts(listed)
+        .signers([house])
+        .rpc();
+
+    // A bet listed twice would be counted twice
+    await expectError(reconcile([bets[0], bets[0]]), "DuplicateBet");
+
+    // The count follows the listed open bets
+    await reconcile(bets.slice(0, 1));
+    const playerStats = playerStatsPda(bettor.publicKey);
+    assert.equal(
+      (await program.account.playerStats.fetch(playerStats)).openBets,
+      1
+    );
+    await reconcile(bets);
+    assert.equal(
+      (await program.account.playerStats.fetch(playerStats)).openBets,
+      2
+    );
+  });
+
+  it("settles open bets of a closing game before closing it", async () => {
+    const game = await newGame();
+    const seed = new BN(52);
+    await placeBet(seed, { game }).rpc();
+
+    await program.methods
+      .beginCloseGame()
+      .accountsPartial({ house: game.publicKey, authority: game.publicKey })
+      .signers([game])
+      .rpc();
+    await expectError(placeBet(new BN(53), { game }).rpc(), "GameClosing");
+
+    const closeGame = () =>
+      program.methods
+        .closeGame()
+        .accountsPartial({ house: game.publicKey, authority: game.publicKey })
+        .signers([game])
+        .rpc();
+    await expectError(closeGame(), "BetsOutstanding");
+
+    // The open bet still resolves, after which the game can close
+    await waitSlots(1);
+    await resolveBet(seed, await houseSignature(seed, game), game);
+    await closeGame();
+    assert.isNull(
+      await program.account.gameConfig.fetchNullable(
+        pda(Buffer.from("config"), game.publicKey.toBuffer())
+      )
+    );
+  });
+
+  it("names the misplaced account when the sysvar slot is wrong", async () => {
+    const seed = new BN(54);
+    await placeBet(seed).rpc();
+    await waitSlots(1);
+    const { ix, sig } = await houseSignature(seed);
+    const resolveWith = async (accounts: object) =>
+      sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          ix,
+          await program.methods
+            .resolveBet(sig)
+            .accountsPartial({ ...resolveAccounts(seed), ...accounts })
+            .instruction()
+        ),
+        [house],
+        { commitment: "confirmed" }
+      );
+
+    await expectError(
+      resolveWith({ instructionSysvar: randomness.publicKey }),
+      "InvalidInstructionSysvar"
+    );
+    // Swapped, the randomness slot is checked first
+    await expectError(
+      resolveWith({
+        randomnessAccount: SYSVAR_INSTRUCTIONS_PUBKEY,
+        instructionSysvar: randomness.publicKey,
+      }),
+      "RandomnessMismatch"
+    );
+    await resolveWith({});
+  });
+
+  it("resolves a bet below a minimum bet raised after placement", async () => {
+    const game = await newGame();
+    const seed = new BN(55);
+    await placeBet(seed, { game }).rpc();
+
+    // Bet limits apply at placement only
+    await updateConfig(game, { minBet: BET.muln(2), maxBet: BET.muln(2) });
+    await expectError(placeBet(new BN(56), { game }).rpc(), "MinimumBet");
+
+    await waitSlots(1);
+    await resolveBet(seed, await houseSignature(seed, game), game);
+    assert.isNull(await program.account.bet.fetchNullable(betPda(seed)));
+  });
+
+  it("rejects a house edge that would zero every payout", async () => {
+    const game = await newGame();
+    await expectError(
+      updateConfig(game, { houseEdgeBp: 10000 }),
+      "InvalidHouseEdge"
+    );
+    await expectError(
+      updateConfig(game, { houseEdgeBp: 1001 }),
+      "InvalidHouseEdge"
+    );
+    await updateConfig(game, { houseEdgeBp: 1000 });
+  });
+
+  it("resolves a co-signed bet only with the player's signature", async () => {
+    const seed = new BN(57);
+    await placeBet(seed).rpc();
+    await waitSlots(1);
+
+    const { ix, sig } = await houseSignature(seed);
+    const resolveCosigned = async (signers: anchor.web3.Keypair[]) =>
+      sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          ix,
+          await program.methods
+            .resolveBetCosigned(sig)
+            .accountsPartial(resolveAccounts(seed))
+            .instruction()
+        ),
+        signers,
+        { commitment: "confirmed" }
+      );
+
+    await expectError(resolveCosigned([house]), "PlayerSignatureRequired");
+    await resolveCosigned([house, player]);
+    assert.isNull(await program.account.bet.fetchNullable(betPda(seed)));
+  });
+
+  it("fails to resolve once the randomness account was closed", async () => {
+    const game = await newGame();
+    const seed = new BN(58);
+    const source = Keypair.generate();
+    await airdrop(source.publicKey, 1);
+    await placeBet(seed, { game, randomnessAccount: source.publicKey }).rpc();
+
+    // Drain the randomness account, which closes it
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction({ feePayer: player.publicKey }).add(
+        SystemProgram.transfer({
+          fromPubkey: source.publicKey,
+          toPubkey: player.publicKey,
+          lamports: await connection.getBalance(source.publicKey),
+        })
+      ),
+      [player, source],
+      { commitment: "confirmed" }
+    );
+    assert.isNull(await connection.getAccountInfo(source.publicKey));
+
+    await waitSlots(1);
+    const { ix, sig } = await houseSignature(seed, game);
+    await expectError(
+      sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          ix,
+          await program.methods
+            .resolveBet(sig)
+            .accountsPartial({
+              ...resolveAccounts(seed, game),
+              randomnessAccount: source.publicKey,
+            })
+            .instruction()
+        ),
+        [game],
+        { commitment: "confirmed" }
+      ),
+      "RandomnessAccountClosed"
+    );
+  });
+
+  it("refunds a win below the minimum acceptable payout", async () => {
+    const game = await newGame();
+
+    // Rolling over 2 wins 98% of the time, never paying ten times the stake
+    let won = false;
+    for (let i = 59; i < 62 && !won; i++) {
+      const seed = new BN(i);
+      await placeBet(seed, {
+        roll: 2,
+        direction: OVER,
+        game,
+        minAcceptablePayout: BET.muln(10),
+      }).rpc();
+      await waitSlots(1);
+
+      const signature = await resolveBet(
+        seed,
+        await houseSignature(seed, game),
+        game
+      );
+      const { meta } = await connection.getTransaction(signature, {
+        commitment: "confirmed",
+      });
+      for (const event of new anchor.EventParser(
+        program.programId,
+        program.coder
+      ).parseLogs(meta.logMessages)) {
+        if (event.name === "betResolved" && event.data.won) {
+          // The stake comes back instead of the lower payout
+          assert.equal(event.data.payout.toString(), BET.toString());
+          won = true;
+        }
+      }
+    }
+    assert.isTrue(won);
+  });
+
+  it("rejects refunding a resolved bet, open or closed", async () => {
+    const refund = (seed: BN) =>
+      program.methods
+        .refundBet()
+        .accountsPartial({
+          player: player.publicKey,
+          house: house.publicKey,
+          bet: betPda(seed),
+        })
+        .signers([player])
+        .rpc();
+
+    // Resolved but open: a cancelled bet keeps its account, flagged resolved
+    const cancelled = new BN(62);
+    await placeBet(cancelled)
+      .postInstructions([await cancelBet(cancelled).instruction()])
+      .rpc();
+    await expectError(refund(cancelled), "BetAlreadyResolved");
+
+    // Resolved and closed: the account is gone
+    const resolved = new BN(63);
+    await placeBet(resolved).rpc();
+    await waitSlots(1);
+    await resolveBet(resolved, await houseSignature(resolved));
+    await expectError(refund(resolved), "AccountNotInitialized");
+  });
+
+  it("rejects an oracle-signed roll with no oracle configured", async () => {
+    const seed = new BN(64);
+    await placeBet(seed).rpc();
+    await waitSlots(1);
+
+    // The oracle signs the bet message followed by the roll
+    const roll = 1;
+    const bet = betPda(seed);
+    const { data } = await connection.getAccountInfo(bet);
+    const ix = Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: Keypair.generate().secretKey,
+      message: Buffer.concat([
+        sha256(Buffer.concat([bet.toBuffer(), data.subarray(8, -32)])),
+        new BN(roll).toArrayLike(Buffer, "le", 2),
+      ]),
+    });
+    const sig = Buffer.from(ix.data.subarray(48, 112));
+
+    await expectError(
+      sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          ix,
+          await program.methods
+            .resolveBetOracle(roll, sig)
+            .accountsPartial(resolveAccounts(seed))
+            .instruction()
+        ),
+        [house],
+        { commitment: "confirmed" }
+      ),
+      "OracleNotConfigured"
+    );
+  });
+
+  it("emits a receipt that recomputes the resolution offline", async () => {
+    const seed = new BN(65);
+    await placeBet(seed).rpc();
+    await waitSlots(1);
+    const signed = await houseSignature(seed);
+    const signature = await resolveBet(seed, signed);
+
+    const { meta } = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+    });
+    const [receipt] = [
+      ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+        meta.logMessages
+      ),
+    ]
+      .filter((event) => event.name === "fairnessReceipt")
+      .map((event) => event.data);
+    assert.ok(receipt.signature.equals(signed.sig));
+    assert.ok(receipt.authority.equals(house.publicKey));
+
+    // The entropy is the hash of the house signature
+    const entropy = Buffer.from(sha256(receipt.signature));
+    assert.ok(entropy.equals(Buffer.from(receipt.entropy)));
+
+    // The roll is the first byte below the largest multiple of the range,
+    // rehashing if there is none
+    const limit = 256 - (256 % receipt.rollRange);
+    let block = entropy;
+    let sample = block.find((byte) => byte < limit);
+    while (sample === undefined) {
+      block = Buffer.from(sha256(block));
+      sample = block.find((byte) => byte < limit);
+    }
+    assert.equal(receipt.roll, (sample % receipt.rollRange) + 1);
+
+    // Rolling under wins on the prediction - 1 faces below it
+    const payout =
+      receipt.roll < receipt.prediction
+        ? receipt.amount
+            .muln(10000 - receipt.houseEdgeBp)
+            .muln(receipt.rollRange)
+            .divn(receipt.prediction - 1)
+            .divn(10000)
+        : new BN(0);
+    assert.equal(receipt.payout.toString(), payout.toString());
+  });
+
+  it("places, resolves and refunds token bets", async () => {
+    const { TOKEN_PROGRAM_ID, associatedAddress } = anchor.utils.token;
+    const game = Keypair.generate();
+    await airdrop(game.publicKey, 3);
+
+    // A mock mint with 6 decimals, minted by the game
+    const mint = Keypair.generate();
+    const tokenIx = (data: Buffer, ...keys: anchor.web3.PublicKey[]) =>
+      new TransactionInstruction({
+        programId: TOKEN_PROGRAM_ID,
+        keys: keys.map((pubkey, i) => ({
+          pubkey,
+          isSigner: pubkey.equals(game.publicKey),
+          isWritable: i < 2,
+        })),
+        data,
+      });
+    const createAccount = async (account: anchor.web3.Keypair, space: number) =>
+      SystemProgram.createAccount({
+        fromPubkey: game.publicKey,
+        newAccountPubkey: account.publicKey,
+        lamports: await connection.getMinimumBalanceForRentExemption(space),
+        space,
+        programId: TOKEN_PROGRAM_ID,
+      });
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(
+        await createAccount(mint, 82),
+        // InitializeMint2 without a freeze authority
+        tokenIx(
+          Buffer.concat([
+            Buffer.from([20, 6]),
+            game.publicKey.toBuffer(),
+            Buffer.from([0]),
+          ]),
+          mint.publicKey
+        )
+      ),
+      [game, mint],
+      { commitment: "confirmed" }
+    );
+
+    // A token account for `owner` holding `amount`
+    const fund = async (owner: anchor.web3.PublicKey, amount: BN) => {
+      const account = Keypair.generate();
+      await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          await createAccount(account, 165),
+          // InitializeAccount3, then MintTo
+          tokenIx(
+            Buffer.concat([Buffer.from([18]), owner.toBuffer()]),
+            account.publicKey,
+            mint.publicKey
+          ),
+          tokenIx(
+            Buffer.concat([
+              Buffer.from([7]),
+              amount.toArrayLike(Buffer, "le", 8),
+            ]),
+            mint.publicKey,
+            account.publicKey,
+            game.publicKey
+          )
+        ),
+        [game, account],
+        { commitment: "confirmed" }
+      );
+      return account.publicKey;
+    };
+    const balance = async (account: anchor.web3.PublicKey) =>
+      new BN(
+        (await connection.getTokenAccountBalance(account, "confirmed")).value
+          .amount
+      );
+
+    const funding = new BN(10).pow(new BN(12));
+    const houseTokens = await fund(game.publicKey, funding);
+    const playerTokens = await fund(player.publicKey, BET.muln(2));
+    const vaultTokens = associatedAddress({
+      mint: mint.publicKey,
+      owner: pda(Buffer.from("vault"), game.publicKey.toBuffer()),
+    });
+    await program.methods
+      .initializeToken(funding)
+      .accountsPartial({
+        house: game.publicKey,
+        mint: mint.publicKey,
+        houseTokenAccount: houseTokens,
+      })
+      .signers([game])
+      .rpc();
+    await updateConfig(game, { refundTimeoutSlots: new BN(30) });
+
+    const placeBetToken = (seed: BN) =>
+      program.methods
+        .placeBetToken(seed, 50, UNDER, BET, new BN(0))
+        .accountsPartial({
+          player: player.publicKey,
+          house: game.publicKey,
+          mint: mint.publicKey,
+          playerTokenAccount: playerTokens,
+          randomnessAccount: randomness.publicKey,
+        })
+        .signers([player])
+        .rpc();
+
+    // The wager moves into the vault token account
+    const seed = new BN(66);
+    await placeBetToken(seed);
+    assert.equal(
+      (await balance(vaultTokens)).toString(),
+      funding.add(BET).toString()
+    );
+
+    // A win is paid in tokens from the vault token account
+    await waitSlots(1);
+    const { ix, sig } = await houseSignature(seed, game);
+    const before = await balance(playerTokens);
+    const signature = await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(
+        ix,
+        await program.methods
+          .resolveBetToken(sig)
+          .accountsPartial({
+            house: game.publicKey,
+            authority: game.publicKey,
+            player: player.publicKey,
+            playerTokenAccount: playerTokens,
+            mint: mint.publicKey,
+            bet: betPda(seed),
+            randomnessAccount: randomness.publicKey,
+            treasuryTokenAccount: null,
+            instructionSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          })
+          .instruction()
+      ),
+      [game],
+      { commitment: "confirmed" }
+    );
+    const { meta } = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+    });
+    const [resolved] = [
+      ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+        meta.logMessages
+      ),
+    ].filter((event) => event.name === "betResolved");
+    assert.equal(
+      (await balance(playerTokens)).sub(before).toString(),
+      resolved.data.payout.toString()
+    );
+    assert.isNull(await program.account.bet.fetchNullable(betPda(seed)));
+
+    // A refund after the timeout returns the wager in tokens
+    const late = new BN(67);
+    await placeBetToken(late);
+    const refund = () =>
+      program.methods
+        .refundBetToken()
+        .accountsPartial({
+          player: player.publicKey,
+          house: game.publicKey,
+          mint: mint.publicKey,
+          playerTokenAccount: playerTokens,
+          bet: betPda(late),
+        })
+        .signers([player])
+        .rpc();
+    await expectError(refund(), "RefundNotEligible");
+
+    const beforeRefund = await balance(playerTokens);
+    await waitSlots(30);
+    await refund();
+    assert.equal(
+      (await balance(playerTokens)).sub(beforeRefund).toString(),
+      BET.toString()
+    );
+    assert.isTrue((await program.account.bet.fetch(betPda(late))).isResolved);
+  });
+
+  it("lets one of a racing refund and resolve settle a bet", async () => {
+    const game = await newGame();
+    await updateConfig(game, { refundTimeoutSlots: new BN(30) });
+    const seed = new BN(68);
+    await placeBet(seed, { game }).rpc();
+    await waitSlots(30);
+
+    const refund = program.methods
+      .refundBet()
+      .accountsPartial({
+        player: player.publicKey,
+        house: game.publicKey,
+        bet: betPda(seed),
+      })
+      .signers([player])
+      .rpc();
+    const resolve = resolveBet(seed, await houseSignature(seed, game), game);
+    const errors = await Promise.all(
+      [refund, resolve].map((race: Promise<unknown>) =>
+        race.then(() => null, (err: any) => err)
+      )
+    );
+
+    // The loser fails cleanly: the bet is flagged resolved, or already closed
+    const lost = errors.filter((err) => err !== null);
+    assert.lengthOf(lost, 1);
+    const logs = (lost[0].logs ?? lost[0].transactionLogs ?? []).join("\n");
+    assert.match(
+      `${lost[0]}\n${logs}`,
+      /BetAlreadyResolved|AccountNotInitialized/
+    );
+  });
+
+  it("withdraws down to the vault floor and no further", async () => {
+    const game = await newGame();
+    const gameVault = pda(Buffer.from("vault"), game.publicKey.toBuffer());
+    const rent = await connection.getMinimumBalanceForRentExemption(0);
+    const withdraw = (amount: number) =>
+      program.methods
+        .withdraw(new BN(amount))
+        .accountsPartial({
+          house: game.publicKey,
+          authority: game.publicKey,
+          primaryAuthority: game.publicKey,
+        })
+        .signers([game])
+        .rpc();
+
+    // MIN_VAULT_RESERVE_LAMPORTS is 0, so the floor is rent exemption
+    const spare = (await connection.getBalance(gameVault)) - rent;
+    await expectError(withdraw(spare + 1), "InsufficientFunds");
+    await withdraw(spare);
+    assert.equal(await connection.getBalance(gameVault), rent);
+    await expectError(withdraw(1), "InsufficientFunds");
+  });
+
+  it("publishes the RNG commitment at initialization", async () => {
+    const game = Keypair.generate();
+    await airdrop(game.publicKey, 3);
+    const signature = await program.methods
+      .initialize(new BN(2 * LAMPORTS_PER_SOL), BET)
+      .accountsPartial({ house: game.publicKey })
+      .signers([game])
+      .rpc({ commitment: "confirmed" });
+
+    const { meta } = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+    });
+    const [initialized] = [
+      ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+        meta.logMessages
+      ),
+    ]
+      .filter((event) => event.name === "gameInitialized")
+      .map((event) => event.data);
+    const config = pda(Buffer.from("config"), game.publicKey.toBuffer());
+    assert.ok(initialized.house.equals(game.publicKey));
+    assert.ok(initialized.config.equals(config));
+    assert.equal(
+      initialized.houseEdgeBp,
+      (await program.account.gameConfig.fetch(config)).houseEdgeBp
+    );
+
+    // The default scheme: the house alone, no co-signers, no oracle, the
+    // Switchboard program and no player co-signature
+    const commitment = sha256(
+      Buffer.concat([
+        game.publicKey.toBuffer(),
+        Buffer.alloc(8),
+        Buffer.alloc(32),
+        new PublicKey("SBondMDrcV3K4kxZR1HNVT7osZxAHVHgYXL5Ze1oMUv").toBuffer(),
+        Buffer.from([0]),
+      ])
+    );
+    assert.ok(Buffer.from(initialized.rngCommitment).equals(commitment));
+  });
+
+  it("rejects a randomness account other than the committed one", async () => {
+    const seed = new BN(69);
+    await placeBet(seed).rpc();
+    await waitSlots(1);
+
+    const other = Keypair.generate();
+    await airdrop(other.publicKey, 1);
+    const { ix, sig } = await houseSignature(seed);
+    await expectError(
+      sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          ix,
+          await program.methods
+            .resolveBet(sig)
+            .accountsPartial({
+              ...resolveAccounts(seed),
+              randomnessAccount: other.publicKey,
+            })
+            .instruction()
+        ),
+        [house],
+        { commitment: "confirmed" }
+      ),
+      "RandomnessMismatch"
+    );
+    await resolveBet(seed, { ix, sig });
+  });
+
+  it("bounds the house edge between 0 and 10%", async () => {
+    const game = await newGame();
+    await updateConfig(game, { houseEdgeBp: 0 });
+    await updateConfig(game, { houseEdgeBp: 1000 });
+    await expectError(
+      updateConfig(game, { houseEdgeBp: 1001 }),
+      "InvalidHouseEdge"
+    );
+    const config = pda(Buffer.from("config"), game.publicKey.toBuffer());
+    assert.equal(
+      (await program.account.gameConfig.fetch(config)).houseEdgeBp,
+      1000
+    );
+  });
+
+  it("stops new bets while paused but settles open ones", async () => {
+    const game = await newGame();
+    await updateConfig(game, { refundTimeoutSlots: new BN(30) });
+    const setPause = (paused: boolean) =>
+      program.methods
+        .setPause(paused)
+        .accountsPartial({ house: game.publicKey, authority: game.publicKey })
+        .signers([game])
+        .rpc();
+
+    const resolved = new BN(70);
+    const refunded = new BN(71);
+    await placeBet(resolved, { game }).rpc();
+    await placeBet(refunded, { game }).rpc();
+    await setPause(true);
+    await expectError(placeBet(new BN(72), { game }).rpc(), "GamePaused");
+
+    // Open bets still resolve and refund
+    await waitSlots(1);
+    await resolveBet(resolved, await houseSignature(resolved, game), game);
+    await waitSlots(30);
+    await program.methods
+      .refundBet()
+      .accountsPartial({
+        player: player.publicKey,
+        house: game.publicKey,
+        bet: betPda(refunded),
+      })
+      .signers([player])
+      .rpc();
+    assert.isTrue(
+      (await program.account.bet.fetch(betPda(refunded))).isResolved
+    );
+
+    await setPause(false);
+    await placeBet(new BN(72), { game }).rpc();
+  });
+
+  it("times refunds by the game's configured refund timeout", async () => {
+    const game = await newGame();
+    await expectError(
+      updateConfig(game, { refundTimeoutSlots: new BN(29) }),
+      "InvalidRefundTimeout"
+    );
+    await updateConfig(game, { refundTimeoutSlots: new BN(45) });
+
+    const seed = new BN(73);
+    await placeBet(seed, { game }).rpc();
+    const refund = () =>
+      program.methods
+        .refundBet()
+        .accountsPartial({
+          player: player.publicKey,
+          house: game.publicKey,
+          bet: betPda(seed),
+        })
+        .signers([player])
+        .rpc();
+
+    // Past the minimum of 30 slots, but not the game's 45
+    await waitSlots(30);
+    await expectError(refund(), "RefundNotEligible");
+    await waitSlots(15);
+    await refund();
+  });
+
+  it("returns every lamport of a closed game to the house", async () => {
+    const game = await newGame();
+    const accounts = [
+      "vault",
+      "insurance",
+      "jackpot",
+      "config",
+      "tracker",
+      "stats",
+    ].map((seed) => pda(Buffer.from(seed), game.publicKey.toBuffer()));
+    const held = async () => {
+      let lamports = 0;
+      for (const key of accounts) lamports += await connection.getBalance(key);
+      return lamports;
+    };
+
+    await program.methods
+      .beginCloseGame()
+      .accountsPartial({ house: game.publicKey, authority: game.publicKey })
+      .signers([game])
+      .rpc();
+    const total = await held();
+    const before = await connection.getBalance(game.publicKey);
+    await program.methods
+      .closeGame()
+      .accountsPartial({ house: game.publicKey, authority: game.publicKey })
+      .signers([game])
+      .rpc();
+
+    // The provider pays the fee, so the house receives exactly what was held
+    assert.equal((await connection.getBalance(game.publicKey)) - before, total);
+    for (const key of accounts) {
+      assert.isNull(await connection.getAccountInfo(key));
+    }
+  });
+
+  it("rejects a signature made for another bet", async () => {
+    // Identical bets apart from their seed and so their address
+    const first = new BN(74);
+    const second = new BN(75);
+    await placeBet(first).rpc();
+    await placeBet(second).rpc();
+    await waitSlots(1);
+
+    const signed = await houseSignature(first);
+    await resolveBet(first, signed);
+    await expectError(resolveBet(second, signed), "Ed25519Signature");
+    await resolveBet(second, await houseSignature(second));
+  });
+
+  it("quotes what a winning bet is paid", async () => {
+    const game = await newGame();
+    const quote = await program.methods
+      .quotePayout(BET, 2, OVER)
+      .accountsPartial({ house: game.publicKey })
+      .view();
+    assert.equal(quote.winProbabilityBp, 9800);
+
+    // Rolling over 2 wins 98% of the time
+    let won = false;
+    for (let i = 76; i < 79 && !won; i++) {
+      const seed = new BN(i);
+      await placeBet(seed, { roll: 2, direction: OVER, game }).rpc();
+      await waitSlots(1);
+
+      const signature = await resolveBet(
+        seed,
+        await houseSignature(seed, game),
+        game
+      );
+      const { meta } = await connection.getTransaction(signature, {
+        commitment: "confirmed",
+      });
+      for (const event of new anchor.EventParser(
+        program.programId,
+        program.coder
+      ).parseLogs(meta.logMessages)) {
+        if (event.name === "betResolved" && event.data.won) {
+          assert.equal(event.data.payout.toString(), quote.payout.toString());
+          won = true;
+        }
+      }
+    }
+    assert.isTrue(won);
+  });
+
+  it("caps the open bets of a player at MAX_OPEN_BETS_PER_PLAYER", async () => {
+    // The cap counts the player's open bets in this game only
+    const game = await newGame();
+    for (let i = 80; i < 100; i++) {
+      await placeBet(new BN(i), { game }).rpc();
+    }
+    await expectError(placeBet(new BN(100), { game }).rpc(), "TooManyOpenBets");
+
+    // Settling one frees a place
+    const seed = new BN(80);
+    await waitSlots(1);
+    await resolveBet(seed, await houseSignature(seed, game), game);
+    await placeBet(new BN(100), { game }).rpc();
+  });
+
+  it("rejects a bet whose win the vault could not pay", async () => {
+    const game = await newGame();
+    const gameVault = pda(Buffer.from("vault"), game.publicKey.toBuffer());
+    const rent = await connection.getMinimumBalanceForRentExemption(0);
+
+    // Leave the vault one stake above its floor; rolling under 50 pays ~2x
+    const spare =
+      (await connection.getBalance(gameVault)) - rent - BET.toNumber();
+    await program.methods
+      .withdraw(new BN(spare))
+      .accountsPartial({
+        house: game.publicKey,
+        authority: game.publicKey,
+        primaryAuthority: game.publicKey,
+      })
+      .signers([game])
+      .rpc();
+    await expectError(
+      placeBet(new BN(101), { game }).rpc(),
+      "InsufficientFunds"
+    );
+  });
+
+  it("resolves a bet signed by an additional authority", async () => {
+    const game = await newGame();
+    const secondary = Keypair.generate();
+    await airdrop(secondary.publicKey, 1);
+    const seed = new BN(102);
+    await placeBet(seed, { game }).rpc();
+    await waitSlots(1);
+    const { ix, sig } = await houseSignature(
+      seed,
+      game,
+      player.publicKey,
+      secondary
+    );
+    const resolve = async () =>
+      sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          ix,
+          await program.methods
+            .resolveBet(sig)
+            .accountsPartial({
+              ...resolveAccounts(seed, game),
+              authority: secondary.publicKey,
+            })
+            .instruction()
+        ),
+        [secondary],
+        { commitment: "confirmed" }
+      );
+
+    await expectError(resolve(), "UnauthorizedAuthority");
+    await program.methods
+      .addAuthority(secondary.publicKey)
+      .accountsPartial({ house: game.publicKey, authority: game.publicKey })
+      .signers([game])
+      .rpc();
+    await resolve();
+    assert.isNull(await program.account.bet.fetchNullable(betPda(seed)));
+  });
+
+  it("stops the old authority withdrawing once transferred", async () => {
+    const game = await newGame();
+    const successor = Keypair.generate();
+    await airdrop(successor.publicKey, 1);
+    await program.methods
+      .transferAuthority(successor.publicKey)
+      .accountsPartial({ house: game.publicKey, authority: game.publicKey })
+      .signers([game])
+      .rpc();
+    const withdraw = (authority: Keypair) =>
+      program.methods
+        .withdraw(new BN(1))
+        .accountsPartial({
+          house: game.publicKey,
+          authority: authority.publicKey,
+          primaryAuthority: successor.publicKey,
+        })
+        .signers([authority])
+        .rpc();
+
+    await expectError(withdraw(game), "UnauthorizedAuthority");
+    const before = await connection.getBalance(successor.publicKey);
+    await withdraw(successor);
+    assert.equal(await connection.getBalance(successor.publicKey), before + 1);
+  });
+
+  it("resolves within the refund timeout but not after it", async () => {
+    const game = await newGame();
+    await updateConfig(game, { refundTimeoutSlots: new BN(30) });
+    const [fresh, stale] = [new BN(103), new BN(104)];
+    await placeBet(fresh, { game }).rpc();
+    await placeBet(stale, { game }).rpc();
+
+    await waitSlots(1);
+    await resolveBet(fresh, await houseSignature(fresh, game), game);
+    await waitSlots(30);
+    await expectError(
+      resolveBet(stale, await houseSignature(stale, game), game),
+      "RandomnessExpired"
+    );
+  });
+
+  it("reports the rent a resolved bet returns to the player", async () => {
+    const seed = new BN(105);
+    await placeBet(seed).rpc();
+    await waitSlots(1);
+    const rent = await connection.getBalance(betPda(seed));
+    const before = await connection.getBalance(player.publicKey);
+    const signature = await resolveBet(seed, await houseSignature(seed));
+
+    const { meta } = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+    });
+    const [resolved] = [
+      ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+        meta.logMessages
+      ),
+    ]
+      .filter((event) => event.name === "betResolved")
+      .map((event) => event.data);
+    assert.equal(resolved.rentRefunded.toNumber(), rent);
+
+    // A loss pays nothing, so the player only gets the rent back
+    const delta = (await connection.getBalance(player.publicKey)) - before;
+    assert.equal(delta, resolved.payout.toNumber() + rent);
+    if (!resolved.won) assert.equal(delta, rent);
+  });
+
+  it("credits a referrer and rejects self-referral", async () => {
+    const referrer = Keypair.generate();
+    await airdrop(referrer.publicKey, 1);
+    const register = (signer: anchor.web3.Keypair) =>
+      program.methods
+        .registerReferrer()
+        .accountsPartial({
+          referrer: signer.publicKey,
+          house: house.publicKey,
+        })
+        .signers([signer])
+        .rpc();
+    await register(referrer);
+    await register(player);
+
+    await expectError(
+      placeBet(new BN(106), { referrer: player.publicKey }).rpc(),
+      "SelfReferral"
+    );
+
+    const seed = new BN(107);
+    await placeBet(seed, { referrer: referrer.publicKey }).rpc();
+    await waitSlots(1);
+    const { ix, sig } = await houseSignature(seed);
+    await sendAndConfirmTransaction(
+      connection,
+      new Transaction().add(
+        ix,
+        await program.methods
+          .resolveBet(sig)
+          .accountsPartial({
+            ...resolveAccounts(seed),
+            referral: referralPda(referrer.publicKey),
+          })
+          .instruction()
+      ),
+      [house],
+      { commitment: "confirmed" }
+    );
+
+    // REFERRAL_BPS is 50, below the 1.5% house edge
+    const earnings = await program.account.referralEarnings.fetch(
+      referralPda(referrer.publicKey)
+    );
+    const reward = BET.muln(50).divn(10000).toNumber();
+    assert.equal(earnings.unclaimed.toNumber(), reward);
+    assert.equal(earnings.totalEarned.toNumber(), reward);
+  });
+
+  it("lets a keeper refund a timed-out bet to its player", async () => {
+    const game = await newGame();
+    await updateConfig(game, { refundTimeoutSlots: new BN(30) });
+    const keeper = Keypair.generate();
+    await airdrop(keeper.publicKey, 1);
+    const seed = new BN(108);
+    await placeBet(seed, { game }).rpc();
+    const keeperRefund = () =>
+      program.methods
+        .keeperRefund()
+        .accountsPartial({
+          keeper: keeper.publicKey,
+          player: player.publicKey,
+          house: game.publicKey,
+          bet: betPda(seed),
+        })
+        .signers([keeper])
+        .rpc();
+
+    await expectError(keeperRefund(), "RefundNotEligible");
+    await waitSlots(30);
+    const before = await connection.getBalance(player.publicKey);
+    await keeperRefund();
+
+    // The stake goes back to the player; the keeper only paid the fees
+    assert.equal(
+      (await connection.getBalance(player.publicKey)) - before,
+      BET.toNumber()
+    );
+    assert.isBelow(
+      await connection.getBalance(keeper.publicKey),
+      LAMPORTS_PER_SOL
+    );
+    assert.isTrue((await program.account.bet.fetch(betPda(seed))).isResolved);
+  });
+
+  it("rejects resolving to the vault or the house as player", async () => {
+    const game = await newGame();
+    const gameVault = pda(Buffer.from("vault"), game.publicKey.toBuffer());
+    const seed = new BN(109);
+    await placeBet(seed, { game }).rpc();
+    await placeBet(seed, { game, bettor: game }).rpc();
+    await waitSlots(1);
+    const resolve = async (
+      owner: anchor.web3.PublicKey,
+      payee: anchor.web3.PublicKey
+    ) => {
+      const { ix, sig } = await houseSignature(seed, game, owner);
+      return sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          ix,
+          await program.methods
+            .resolveBet(sig)
+            .accountsPartial({
+              ...resolveAccounts(seed, game),
+              player: payee,
+              bet: betPda(seed, owner),
+            })
+            .instruction()
+        ),
+        [game],
+        { commitment: "confirmed" }
+      );
+    };
+
+    // The vault never owns a bet, so the bet's player check stops it first
+    await expectError(resolve(player.publicKey, gameVault), "NotPlayerBet");
+    // The house can bet on its own game, but is never paid as the player
+    await expectError(
+      resolve(game.publicKey, game.publicKey),
+      "InvalidPlayer"
+    );
+  });
+
+  it("tops up the vault of a running game", async () => {
+    const game = await newGame();
+    const gameVault = pda(Buffer.from("vault"), game.publicKey.toBuffer());
+    const before = await connection.getBalance(gameVault);
+
+    await program.methods
+      .fundVault(new BN(LAMPORTS_PER_SOL / 2))
+      .accountsPartial({ funder: game.publicKey, house: game.publicKey })
+      .signers([game])
+      .rpc();
+    assert.equal(
+      await connection.getBalance(gameVault),
+      before + LAMPORTS_PER_SOL / 2
+    );
+  });
+
+  it("emits the amount and vault balance of every vault flow", async () => {
+    const game = await newGame();
+    const gameVault = pda(Buffer.from("vault"), game.publicKey.toBuffer());
+    const amount = LAMPORTS_PER_SOL / 4;
+    const eventOf = async (signature: string, name: string) => {
+      const { meta } = await connection.getTransaction(signature, {
+        commitment: "confirmed",
+      });
+      return [
+        ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+          meta.logMessages
+        ),
+      ].find((event) => event.name === name).data;
+    };
+
+    const funded = await eventOf(
+      await program.methods
+        .fundVault(new BN(amount))
+        .accountsPartial({ funder: game.publicKey, house: game.publicKey })
+        .signers([game])
+        .rpc({ commitment: "confirmed" }),
+      "vaultFunded"
+    );
+    assert.ok(funded.actor.equals(game.publicKey));
+    assert.equal(funded.amount.toNumber(), amount);
+    assert.equal(
+      funded.vaultBalance.toNumber(),
+      await connection.getBalance(gameVault, "confirmed")
+    );
+
+    const withdrawn = await eventOf(
+      await program.methods
+        .withdraw(new BN(amount))
+        .accountsPartial({
+          house: game.publicKey,
+          authority: game.publicKey,
+          primaryAuthority: game.publicKey,
+        })
+        .signers([game])
+        .rpc({ commitment: "confirmed" }),
+      "vaultWithdrawn"
+    );
+    assert.ok(withdrawn.recipient.equals(game.publicKey));
+    assert.equal(withdrawn.amount.toNumber(), amount);
+    assert.equal(
+      withdrawn.vaultBalance.toNumber(),
+      funded.vaultBalance.toNumber() - amount
+    );
+  });
+
+  it("caps long shots at a tenth of the vault", async () => {
+    // MAX_PAYOUT_VAULT_BPS is 1000: a fresh game's ~2 SOL vault risks ~0.2 SOL
+    const game = await newGame();
+    await expectError(
+      placeBet(new BN(110), { game, roll: 2 }).rpc(),
+      "MaximumBet"
+    );
+
+    // The same stake under 11 wins ~0.11 SOL
+    await placeBet(new BN(111), { game, roll: 11 }).rpc();
+  });
+
+  it("rejects a reused seed until its bet closes", async () => {
+    const game = await newGame();
+    const seed = new BN(112);
+    await placeBet(seed, { game }).rpc();
+    await expectError(placeBet(seed, { game }).rpc(), "BetAlreadyPlaced");
+
+    // Resolving closes the bet account, which frees the seed
+    await waitSlots(1);
+    await resolveBet(seed, await houseSignature(seed, game), game);
+    await placeBet(seed, { game }).rpc();
+  });
+
+  it("pays a bet at the house edge it was placed under", async () => {
+    const game = await newGame();
+    const seed = new BN(113);
+    await placeBet(seed, { game }).rpc();
+    await updateConfig(game, { houseEdgeBp: 500 });
+    assert.equal(
+      (await program.account.bet.fetch(betPda(seed))).houseEdgeBp,
+      150
+    );
+
+    await waitSlots(1);
+    const signature = await resolveBet(
+      seed,
+      await houseSignature(seed, game),
+      game
+    );
+    const { meta } = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+    });
+    const [receipt] = [
+      ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+        meta.logMessages
+      ),
+    ]
+      .filter((event) => event.name === "fairnessReceipt")
+      .map((event) => event.data);
+    assert.equal(receipt.houseEdgeBp, 150);
+    if (receipt.roll < 50) {
+      // Under 50 of 100 at 1.5%: BET * 9850 * 100 / (49 * 10000)
+      const payout = BET.muln(9850 * 100).divn(49 * 10000);
+      assert.equal(receipt.payout.toString(), payout.toString());
+    }
+  });
+
+  it("reproduces a resolution with verify_fairness", async () => {
+    const seed = new BN(114);
+    await placeBet(seed).rpc();
+    await waitSlots(1);
+    const signature = await resolveBet(seed, await houseSignature(seed));
+    const { meta } = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+    });
+    const [receipt] = [
+      ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+        meta.logMessages
+      ),
+    ]
+      .filter((event) => event.name === "fairnessReceipt")
+      .map((event) => event.data);
+
+    const check = await program.methods
+      .verifyFairness(
+        [Array.from(receipt.signature as Buffer)],
+        receipt.amount,
+        receipt.prediction,
+        receipt.direction,
+        receipt.rollRange,
+        receipt.houseEdgeBp,
+        receipt.tieIsPush,
+        { floor: {} }
+      )
+      .view();
+    assert.deepEqual(check.entropy, receipt.entropy);
+    assert.equal(check.roll, receipt.roll);
+    assert.equal(check.won, receipt.roll < receipt.prediction);
+    assert.equal(check.payout.toString(), receipt.payout.toString());
+  });
+
+  it("sends the treasury its cut of losses only", async () => {
+    const game = await newGame();
+    const treasury = Keypair.generate();
+    await airdrop(treasury.publicKey, 1);
+    await program.methods
+      .setTreasury(treasury.publicKey, 1000)
+      .accountsPartial({ house: game.publicKey, authority: game.publicKey })
+      .signers([game])
+      .rpc();
+
+    // Under 11 mostly loses, over 2 mostly wins
+    const bets: [BN, BetOptions][] = [
+      [new BN(115), { game, roll: 11 }],
+      [new BN(116), { game, roll: 2, direction: OVER }],
+    ];
+    for (const [seed, options] of bets) {
+      await placeBet(seed, options).rpc();
+      await waitSlots(1);
+      const before = await connection.getBalance(treasury.publicKey);
+      const { ix, sig } = await houseSignature(seed, game);
+      const signature = await sendAndConfirmTransaction(
+        connection,
+        new Transaction().add(
+          ix,
+          await program.methods
+            .resolveBet(sig)
+            .accountsPartial({
+              ...resolveAccounts(seed, game),
+              treasury: treasury.publicKey,
+            })
+            .instruction()
+        ),
+        [game],
+        { commitment: "confirmed" }
+      );
+      const { meta } = await connection.getTransaction(signature, {
+        commitment: "confirmed",
+      });
+      const [resolved] = [
+        ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+          meta.logMessages
+        ),
+      ]
+        .filter((event) => event.name === "betResolved")
+        .map((event) => event.data);
+
+      // treasury_bps is 1000: a tenth of a lost wager
+      assert.equal(
+        (await connection.getBalance(treasury.publicKey)) - before,
+        resolved.won ? 0 : BET.toNumber() / 10
+      );
+    }
+  });
+
+  it("signs for the vault with its canonical bump", async () => {
+    const [, bump] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), house.publicKey.toBuffer()],
+      program.programId
+    );
+    const config = await program.account.gameConfig.fetch(
+      pda(Buffer.from("config"), house.publicKey.toBuffer())
+    );
+    assert.equal(config.vaultBump, bump);
+
+    // Resolution checks the bump it derives against the recorded one
+    const seed = new BN(117);
+    await placeBet(seed).rpc();
+    await waitSlots(1);
+    await resolveBet(seed, await houseSignature(seed));
+    assert.isNull(await program.account.bet.fetchNullable(betPda(seed)));
+  });
+
+  it("lets the house refund a fresh bet right away, once", async () => {
+    const game = await newGame();
+    const seed = new BN(118);
+    await placeBet(seed, { game }).rpc();
+    const adminRefund = () =>
+      program.methods
+        .adminRefund()
+        .accountsPartial({
+          house: game.publicKey,
+          authority: game.publicKey,
+          player: player.publicKey,
+          bet: betPda(seed),
+        })
+        .signers([game])
+        .rpc();
+
+    // Well before the refund timeout
+    const before = await connection.getBalance(player.publicKey);
+    await adminRefund();
+    assert.equal(
+      (await connection.getBalance(player.publicKey)) - before,
+      BET.toNumber()
+    );
+    assert.isTrue((await program.account.bet.fetch(betPda(seed))).isResolved);
+    await expectError(adminRefund(), "BetAlreadyResolved");
+  });
+
+  it("returns the roll of a resolution as return data", async () => {
+    const seed = new BN(119);
+    await placeBet(seed).rpc();
+    await waitSlots(1);
+    const signature = await resolveBet(seed, await houseSignature(seed));
+    const { meta } = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const [resolved] = [
+      ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+        meta.logMessages
+      ),
+    ]
+      .filter((event) => event.name === "betResolved")
+      .map((event) => event.data);
+
+    // ResolutionOutcome: roll (u16), won (bool), payout (u64), little-endian
+    const { programId, data } = meta.returnData;
+    assert.ok(new PublicKey(programId).equals(program.programId));
+    const outcome = Buffer.from(data[0], "base64");
+    assert.equal(outcome.readUInt16LE(0), resolved.roll);
+    assert.equal(outcome[2] === 1, resolved.won);
+    assert.ok(new BN(outcome.subarray(3, 11), "le").eq(resolved.payout));
+  });
+
+  it("rejects predictions outside the roll limits", async () => {
+    // min_roll and max_roll default to 2 and 96
+    await expectError(placeBet(new BN(120), { roll: 1 }).rpc(), "MinimumRoll");
+    await expectError(placeBet(new BN(120), { roll: 97 }).rpc(), "MaximumRoll");
+
+    // Rolling over the top face can never win
+    const game = await newGame();
+    await updateConfig(game, { maxRoll: 100 });
+    await expectError(
+      placeBet(new BN(120), { game, roll: 100, direction: OVER }).rpc(),
+      "MaximumRoll"
+    );
+  });
+
+  it("accepts stakes at both bet limits and none beyond", async () => {
+    const game = await newGame();
+    await updateConfig(game, { minBet: BET.muln(2), maxBet: BET.muln(3) });
+
+    await expectError(
+      placeBet(new BN(121), { game, amount: BET.muln(2).subn(1) }).rpc(),
+      "MinimumBet"
+    );
+    await expectError(
+      placeBet(new BN(121), { game, amount: BET.muln(3).addn(1) }).rpc(),
+      "MaximumBet"
+    );
+    await placeBet(new BN(121), { game, amount: BET.muln(2) }).rpc();
+    await placeBet(new BN(122), { game, amount: BET.muln(3) }).rpc();
+  });
+
+  it("emits BetPlaced with the bet's parameters", async () => {
+    const game = await newGame();
+    const seed = new BN(123);
+    const signature = await placeBet(seed, {
+      game,
+      roll: 40,
+      direction: OVER,
+    }).rpc({ commitment: "confirmed" });
+    const { meta, slot } = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+    });
+    const [placed] = [
+      ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+        meta.logMessages
+      ),
+    ]
+      .filter((event) => event.name === "betPlaced")
+      .map((event) => event.data);
+
+    assert.ok(placed.bet.equals(betPda(seed)));
+    assert.ok(placed.player.equals(player.publicKey));
+    assert.equal(placed.seed.toString(), seed.toString());
+    assert.equal(placed.roll, 40);
+    assert.deepEqual(placed.direction, OVER);
+    assert.equal(placed.amount.toString(), BET.toString());
+    assert.equal(placed.slot.toNumber(), slot);
+  });
+
+  it("emits BetResolved with the roll and its outcome", async () => {
+    const seed = new BN(124);
+    await placeBet(seed).rpc();
+    await waitSlots(1);
+    const signature = await resolveBet(seed, await houseSignature(seed));
+    const { meta } = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+    });
+    const [resolved] = [
+      ...new anchor.EventParser(program.programId, program.coder).parseLogs(
+        meta.logMessages
+      ),
+    ]
+      .filter((event) => event.name === "betResolved")
+      .map((event) => event.data);
+
+    assert.ok(resolved.bet.equals(betPda(seed)));
+    assert.ok(resolved.player.equals(player.publicKey));
+    assert.equal(resolved.seed.toString(), seed.toString());
+    assert.equal(resolved.prediction, 50);
+    assert.isTrue(resolved.roll >= 1 && resolved.roll <= 100);
+    assert.equal(resolved.won, resolved.roll < 50);
+    assert.equal(resolved.payout.gtn(0), resolved.won);
+  });
+
+  it("reports the compute used to resolve a bet", async () => {
+    const seed = new BN(17);
+    await placeBet(seed).rpc();
+    await waitSlots(1);
+    const signature = await resolveBet(seed, await houseSignature(seed));
+
+    const { meta } = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    console.log(`      resolve_bet: ${meta.computeUnitsConsumed} CU`);
+
+    // Built with verbose-logs, the signature check logs the units remaining
+    // before and after it runs
+    const remaining = meta.logMessages
+      .map((log) => /Program consumption: (\d+) units remaining/.exec(log))
+      .filter((match) => match)
+      .map((match) => Number(match[1]));
+    if (remaining.length === 2) {
+      const [before, after] = remaining;
+      console.log(
+        `      signature check: ${before} -> ${after} (${before - after} CU)`
+      );
+    }
+
+    assert.isBelow(meta.computeUnitsConsumed, 200_000);
   });
 });